@@ -0,0 +1,133 @@
+//! Hardware address windows, for drivers which can write a block of pixels
+//! after setting the area they go to.
+
+use crate::{geometry, util, Orientation};
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+
+#[cfg(test)]
+mod tests;
+
+/// A display driver's address window primitive.
+///
+/// Many display controllers draw by setting a rectangular window and then
+/// streaming pixels into it, which fill the window row by row. Implementing
+/// this for a driver allows the transforms' `fill_window` methods, such as
+/// [`Rotate90::fill_window`](crate::Rotate90::fill_window), to send
+/// transformed areas to it as windowed writes, rather than pixel by pixel.
+pub trait AddressWindow: DrawTarget {
+    /// Set the window that following pixels are written to.
+    ///
+    /// The window is always within the display's bounding box.
+    fn set_window(&mut self, area: &Rectangle) -> Result<(), Self::Error>;
+
+    /// Write pixels to the window, in row-major order.
+    ///
+    /// Writes may stop before the window is full, and the next write may
+    /// continue where the last one stopped.
+    fn write_pixels<I>(&mut self, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>;
+}
+
+/// Fill a logical area of a transformed display with windowed writes.
+///
+/// The area is clipped to the display. If the orientation is the identity,
+/// the whole area is one window. Otherwise each logical row is a
+/// physical row or column, which is its own window, streamed directly if it
+/// runs in the display's direction or reversed through an `N` pixel buffer
+/// if not.
+pub(crate) fn fill_window<D: AddressWindow, const N: usize>(
+    target: &mut D,
+    orientation: Orientation,
+    size: Size,
+    offset: Point,
+    area: &Rectangle,
+    colors: impl IntoIterator<Item = D::Color>,
+) -> Result<(), D::Error> {
+    let bounds = Rectangle::new(offset, geometry::inverse_transform_size(orientation, size));
+    let clip = Rectangle::new(Point::zero(), size)
+        .intersection(area)
+        .intersection(&geometry::inverse_transform_rect(
+            orientation,
+            bounds,
+            target.bounding_box(),
+        ));
+    if util::is_empty(&clip) {
+        return Ok(());
+    }
+
+    let mut colors = util::points(area)
+        .zip(colors)
+        .filter(|(p, _)| clip.contains(*p))
+        .map(|(_, color)| color)
+        .peekable();
+
+    if orientation == Orientation::IDENTITY {
+        target.set_window(&Rectangle::new(clip.top_left + offset, clip.size))?;
+        return target.write_pixels(colors);
+    }
+
+    let width = clip.size.width as usize;
+    let mut buf = match colors.peek() {
+        Some(&first) => [first; N],
+        None => return Ok(()),
+    };
+
+    for y in clip.rows() {
+        if colors.peek().is_none() {
+            break;
+        }
+        let row = Rectangle::new(
+            Point::new(clip.top_left.x, y),
+            Size::new(clip.size.width, 1),
+        );
+        let run = geometry::transform_rect(orientation, bounds, row);
+
+        if geometry::transform_point(orientation, bounds, row.top_left) == run.top_left {
+            target.set_window(&run)?;
+            target.write_pixels(colors.by_ref().take(width))?;
+            continue;
+        }
+
+        // The row runs backwards on the display, so write it in chunks from
+        // the far end, each reversed.
+        let mut x = 0;
+        while x < width {
+            let len = (width - x).min(N.max(1));
+            let chunk = Rectangle::new(
+                row.top_left + Point::new(x as i32, 0),
+                Size::new(len as u32, 1),
+            );
+            let window = geometry::transform_rect(orientation, bounds, chunk);
+
+            if N == 0 {
+                target.set_window(&window)?;
+                target.write_pixels(colors.by_ref().take(1))?;
+            } else {
+                let read = buf[..len]
+                    .iter_mut()
+                    .zip(colors.by_ref())
+                    .map(|(slot, color)| *slot = color)
+                    .count();
+                if read < len {
+                    // Ran out of colors, so only write the ones there are.
+                    let window = geometry::transform_rect(
+                        orientation,
+                        bounds,
+                        Rectangle::new(chunk.top_left, Size::new(read as u32, 1)),
+                    );
+                    if read > 0 {
+                        target.set_window(&window)?;
+                        target.write_pixels(buf[..read].iter().rev().copied())?;
+                    }
+                    return Ok(());
+                }
+                target.set_window(&window)?;
+                target.write_pixels(buf[..len].iter().rev().copied())?;
+            }
+            x += len;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,151 @@
+use super::*;
+use crate::{
+    tests::{color, rect},
+    Reorient, Rotate, Rotate90, Rotation,
+};
+use embedded_graphics_core::pixelcolor::Rgb565;
+
+/// A 12x8 display which can only be drawn to through windows.
+struct Windowed {
+    pixels: [Option<Rgb565>; 96],
+    window: Rectangle,
+    cursor: usize,
+    windows: usize,
+}
+
+impl Windowed {
+    fn new() -> Self {
+        Windowed {
+            pixels: [None; 96],
+            window: rect(0, 0, 0, 0),
+            cursor: 0,
+            windows: 0,
+        }
+    }
+
+    fn get(&self, p: Point) -> Option<Rgb565> {
+        self.pixels[p.y as usize * 12 + p.x as usize]
+    }
+}
+
+impl OriginDimensions for Windowed {
+    fn size(&self) -> Size {
+        Size::new(12, 8)
+    }
+}
+
+impl DrawTarget for Windowed {
+    type Color = Rgb565;
+    type Error = ();
+
+    fn draw_iter<I>(&mut self, _pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        panic!("pixels drawn outside a window")
+    }
+}
+
+impl AddressWindow for Windowed {
+    fn set_window(&mut self, area: &Rectangle) -> Result<(), Self::Error> {
+        assert_eq!(self.bounding_box().intersection(area), *area);
+        self.window = *area;
+        self.cursor = 0;
+        self.windows += 1;
+        Ok(())
+    }
+
+    fn write_pixels<I>(&mut self, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let width = self.window.size.width as usize;
+        for color in colors {
+            let p = self.window.top_left
+                + Point::new((self.cursor % width) as i32, (self.cursor / width) as i32);
+            assert!(self.window.contains(p), "wrote past end of window");
+            let px = &mut self.pixels[p.y as usize * 12 + p.x as usize];
+            assert!(px.is_none(), "overdraw at {:?}", p);
+            *px = Some(color);
+            self.cursor += 1;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn all_orientations() {
+    // Partly off the display in every orientation
+    let area = rect(-2, 1, 10, 10);
+
+    for orientation in Orientation::ALL {
+        for offset in [Point::zero(), Point::new(1, 2)] {
+            let mut disp = Reorient::new(Windowed::new(), orientation).with_offset(offset);
+            let logical = disp.bounding_box();
+            disp.fill_window::<3, _>(&area, util::points(&area).map(color))
+                .unwrap();
+
+            let disp = disp.into_inner();
+            let bounds = Rectangle::new(
+                offset,
+                geometry::inverse_transform_size(orientation, logical.size),
+            );
+            let clip = logical
+                .intersection(&area)
+                .intersection(&geometry::inverse_transform_rect(
+                    orientation,
+                    bounds,
+                    disp.bounding_box(),
+                ));
+            let drawn = disp.pixels.iter().filter(|px| px.is_some()).count();
+            assert_eq!(
+                drawn as u32,
+                clip.size.width * clip.size.height,
+                "{}",
+                orientation
+            );
+            for p in util::points(&clip) {
+                let physical = geometry::transform_point(orientation, bounds, p);
+                assert_eq!(
+                    disp.get(physical),
+                    Some(color(p)),
+                    "{} {:?}",
+                    orientation,
+                    p
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn windows() {
+    let area = rect(1, 1, 5, 4);
+    let colors = || util::points(&area).map(color);
+
+    // Untransformed areas are a single window
+    let mut disp = Rotate::new(Rotation::Rotate0, Windowed::new());
+    disp.fill_window::<8, _>(&area, colors()).unwrap();
+    assert_eq!(disp.windows, 1);
+
+    // Rotated rows are display columns running downwards
+    let mut disp = Rotate90::new(Windowed::new());
+    disp.fill_window::<0, _>(&area, colors()).unwrap();
+    assert_eq!(disp.windows, 4);
+
+    // and backwards rows are reversed a chunk at a time
+    let mut disp = Rotate::new(Rotation::Rotate180, Windowed::new());
+    disp.fill_window::<2, _>(&area, colors()).unwrap();
+    assert_eq!(disp.windows, 4 * 3);
+    let mut disp = Rotate::new(Rotation::Rotate180, Windowed::new());
+    disp.fill_window::<0, _>(&area, colors()).unwrap();
+    assert_eq!(disp.windows, 4 * 5);
+
+    // Running out of colors stops at the last one
+    let mut disp = Rotate::new(Rotation::Rotate180, Windowed::new());
+    disp.fill_window::<2, _>(&area, colors().take(8)).unwrap();
+    assert_eq!(disp.windows, 5);
+    assert_eq!(disp.pixels.iter().filter(|px| px.is_some()).count(), 8);
+    assert_eq!(disp.get(Point::new(10, 5)), Some(color(Point::new(1, 2))));
+    assert_eq!(disp.get(Point::new(8, 5)), Some(color(Point::new(3, 2))));
+}
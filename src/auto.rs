@@ -0,0 +1,250 @@
+//! Choose the rotation automatically.
+
+use crate::{Orientation, Reorient, Rotate, Rotation};
+use core::ops::{Deref, DerefMut};
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+
+#[cfg(test)]
+mod tests;
+
+/// A source of the rotation needed to show the display upright.
+///
+/// This is typically backed by a sensor such as an accelerometer, whose
+/// readings can be converted with [`Rotation::from_gravity_vector`], or a hall
+/// effect switch. It's implemented for closures returning `Option<Rotation>`.
+pub trait OrientationProvider {
+    /// Return the rotation to apply to the image, or `None` to keep the current
+    /// one, for example if the sensor reading is ambiguous.
+    fn rotation(&mut self) -> Option<Rotation>;
+}
+
+impl<F: FnMut() -> Option<Rotation>> OrientationProvider for F {
+    fn rotation(&mut self) -> Option<Rotation> {
+        self()
+    }
+}
+
+impl Rotation {
+    /// Determine the rotation which shows the display upright from an
+    /// accelerometer reading.
+    ///
+    /// `x` and `y` are the acceleration due to gravity, in milli-g (or any
+    /// consistent unit), along the display's X axis (pointing right) and Y axis
+    /// (pointing down). The result is the rotation whose "down" is closest to
+    /// the direction of gravity.
+    ///
+    /// To avoid flickering between two rotations when the display is held near
+    /// 45 degrees, the `previous` rotation is kept unless another one is
+    /// favoured by more than `hysteresis`. This also acts as a deadband when the
+    /// display is lying flat: if there's no previous rotation, `None` is
+    /// returned unless one is favoured by more than `hysteresis`.
+    pub fn from_gravity_vector(
+        x: i32,
+        y: i32,
+        previous: Option<Rotation>,
+        hysteresis: u32,
+    ) -> Option<Rotation> {
+        // How far gravity points towards the bottom of the image with each
+        // rotation.
+        let score = |rotation| match rotation {
+            Rotation::Rotate0 => i64::from(y),
+            Rotation::Rotate90 => -i64::from(x),
+            Rotation::Rotate180 => -i64::from(y),
+            Rotation::Rotate270 => i64::from(x),
+        };
+        let best = [
+            Rotation::Rotate0,
+            Rotation::Rotate90,
+            Rotation::Rotate180,
+            Rotation::Rotate270,
+        ]
+        .into_iter()
+        .max_by_key(|&rotation| score(rotation))?;
+        let threshold = previous.map_or(0, score) + i64::from(hysteresis);
+
+        if score(best) > threshold {
+            Some(best)
+        } else {
+            previous
+        }
+    }
+}
+
+/// Return the largest scale at which `content` fits in `available`, as a
+/// fraction.
+fn fit_scale(content: Size, available: Size) -> (u64, u64) {
+    let (cw, ch) = (u64::from(content.width), u64::from(content.height));
+    let (aw, ah) = (u64::from(available.width), u64::from(available.height));
+
+    if aw * ch <= ah * cw {
+        (aw, cw)
+    } else {
+        (ah, ch)
+    }
+}
+
+impl Rotation {
+    /// Choose the rotation which fits `content` on a display of `display` size
+    /// at the largest scale.
+    ///
+    /// This is either [`Rotate0`](Rotation::Rotate0) or
+    /// [`Rotate90`](Rotation::Rotate90), preferring `Rotate0` if both fit
+    /// equally well, for example for square content.
+    pub fn best_fit(content: Size, display: Size) -> Rotation {
+        if content.width == 0 || content.height == 0 {
+            return Rotation::Rotate0;
+        }
+
+        let (n0, d0) = fit_scale(content, display);
+        let (n90, d90) = fit_scale(content, Size::new(display.height, display.width));
+        if n90 * d0 > n0 * d90 {
+            Rotation::Rotate90
+        } else {
+            Rotation::Rotate0
+        }
+    }
+}
+
+impl<D: Dimensions> Rotate<D> {
+    /// Rotate a display to best fit `content`, as chosen by
+    /// [`Rotation::best_fit`].
+    ///
+    /// This is useful for fixed-aspect content, such as a camera preview or a
+    /// plot, on displays which may be mounted either way.
+    pub fn fit(target: D, content: Size) -> Self {
+        let rotation = Rotation::best_fit(content, target.bounding_box().size);
+        Rotate::new(rotation, target)
+    }
+}
+
+/// Rotate a display according to an [`OrientationProvider`].
+///
+/// The provider is only queried by [`update`](Self::update), which the
+/// application calls when convenient, such as once per frame or when the
+/// sensor signals a change, so the rotation never changes part way through
+/// drawing. A change sets a flag, which the application can check with
+/// [`take_changed`](Self::take_changed) to know that it needs to redraw
+/// everything in the new orientation.
+pub struct AutoRotate<D, P> {
+    target: Reorient<D>,
+    provider: P,
+    rotation: Rotation,
+    changed: bool,
+}
+
+impl<D, P: OrientationProvider> AutoRotate<D, P> {
+    /// Rotate a display implementing [`DrawTarget`] according to `provider`,
+    /// which is queried for the initial rotation.
+    pub fn new(target: D, mut provider: P) -> Self {
+        let rotation = provider.rotation().unwrap_or(Rotation::Rotate0);
+
+        AutoRotate {
+            target: Reorient::new(target, rotation.into()),
+            provider,
+            rotation,
+            changed: false,
+        }
+    }
+
+    /// Query the provider and apply any new rotation, returning true if it
+    /// changed.
+    pub fn update(&mut self) -> bool {
+        match self.provider.rotation() {
+            Some(rotation) if rotation != self.rotation => {
+                self.rotation = rotation;
+                self.target.set_orientation(Orientation::from(rotation));
+                self.changed = true;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<D, P> AutoRotate<D, P> {
+    /// Return the current rotation.
+    pub fn rotation(&self) -> Rotation {
+        self.rotation
+    }
+
+    /// Return true if the rotation has changed since this was last called.
+    pub fn take_changed(&mut self) -> bool {
+        core::mem::take(&mut self.changed)
+    }
+
+    /// Get a reference to the provider.
+    pub fn provider(&self) -> &P {
+        &self.provider
+    }
+
+    /// Get a mutable reference to the provider.
+    pub fn provider_mut(&mut self) -> &mut P {
+        &mut self.provider
+    }
+
+    /// Recover the inner display instance.
+    pub fn into_inner(self) -> D {
+        self.target.into_inner()
+    }
+}
+
+impl<D, P> Deref for AutoRotate<D, P> {
+    type Target = D;
+
+    fn deref(&self) -> &D {
+        self.as_ref()
+    }
+}
+
+impl<D, P> DerefMut for AutoRotate<D, P> {
+    fn deref_mut(&mut self) -> &mut D {
+        self.as_mut()
+    }
+}
+
+impl<D, P> AsRef<D> for AutoRotate<D, P> {
+    fn as_ref(&self) -> &D {
+        self.target.as_ref()
+    }
+}
+
+impl<D, P> AsMut<D> for AutoRotate<D, P> {
+    fn as_mut(&mut self) -> &mut D {
+        self.target.as_mut()
+    }
+}
+
+impl_by_mut_ref!([D, P] AutoRotate<D, P>);
+
+impl<D: Dimensions, P> Dimensions for AutoRotate<D, P> {
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+impl<D: DrawTarget, P> DrawTarget for AutoRotate<D, P> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.target.draw_iter(pixels)
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        self.target.fill_contiguous(area, colors)
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.target.fill_solid(area, color)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.target.clear(color)
+    }
+}
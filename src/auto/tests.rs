@@ -0,0 +1,104 @@
+use super::*;
+use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+#[test]
+fn auto_rotate() {
+    let mut sensor = [None, Some(Rotation::Rotate90), Some(Rotation::Rotate90)].into_iter();
+    let mut disp = AutoRotate::new(
+        crate::WithSize::new(MockDisplay::new(), Size::new(64, 32)),
+        move || sensor.next().flatten(),
+    );
+    assert_eq!(disp.rotation(), Rotation::Rotate0);
+    assert_eq!(disp.bounding_box().size, Size::new(64, 32));
+
+    assert!(disp.update());
+    assert_eq!(disp.rotation(), Rotation::Rotate90);
+    assert_eq!(disp.bounding_box().size, Size::new(32, 64));
+    assert!(!disp.update());
+    assert!(disp.take_changed());
+    assert!(!disp.take_changed());
+
+    disp.draw_iter([Pixel(Point::new(0, 0), BinaryColor::On)])
+        .unwrap();
+    assert_eq!(
+        disp.as_ref().as_ref().get_pixel(Point::new(63, 0)),
+        Some(BinaryColor::On)
+    );
+}
+
+#[test]
+fn gravity_vector() {
+    use Rotation::*;
+
+    assert_eq!(
+        Rotation::from_gravity_vector(0, 1000, None, 200),
+        Some(Rotate0)
+    );
+    assert_eq!(
+        Rotation::from_gravity_vector(-1000, 0, None, 200),
+        Some(Rotate90)
+    );
+    assert_eq!(
+        Rotation::from_gravity_vector(100, -900, None, 200),
+        Some(Rotate180)
+    );
+    assert_eq!(
+        Rotation::from_gravity_vector(900, 100, None, 200),
+        Some(Rotate270)
+    );
+
+    // Lying flat
+    assert_eq!(Rotation::from_gravity_vector(50, 100, None, 200), None);
+    assert_eq!(
+        Rotation::from_gravity_vector(50, 100, Some(Rotate90), 200),
+        Some(Rotate90)
+    );
+
+    // Near 45 degrees the previous rotation is kept
+    assert_eq!(
+        Rotation::from_gravity_vector(750, 650, Some(Rotate0), 200),
+        Some(Rotate0)
+    );
+    assert_eq!(
+        Rotation::from_gravity_vector(650, 750, Some(Rotate270), 200),
+        Some(Rotate270)
+    );
+    assert_eq!(
+        Rotation::from_gravity_vector(900, 400, Some(Rotate0), 200),
+        Some(Rotate270)
+    );
+}
+
+#[test]
+fn best_fit() {
+    let wide = Size::new(320, 240);
+    let tall = Size::new(240, 320);
+
+    assert_eq!(Rotation::best_fit(wide, wide), Rotation::Rotate0);
+    assert_eq!(Rotation::best_fit(tall, wide), Rotation::Rotate90);
+    assert_eq!(
+        Rotation::best_fit(Size::new(100, 100), wide),
+        Rotation::Rotate0
+    );
+    // Slightly tall content fits better rotated
+    assert_eq!(
+        Rotation::best_fit(Size::new(100, 110), wide),
+        Rotation::Rotate90
+    );
+    assert_eq!(
+        Rotation::best_fit(Size::new(100, 90), wide),
+        Rotation::Rotate0
+    );
+    assert_eq!(
+        Rotation::best_fit(Size::new(10, 40), wide),
+        Rotation::Rotate90
+    );
+    assert_eq!(Rotation::best_fit(Size::zero(), wide), Rotation::Rotate0);
+
+    let disp = crate::Rotate::fit(
+        crate::WithSize::new(MockDisplay::<BinaryColor>::new(), Size::new(64, 32)),
+        Size::new(20, 40),
+    );
+    assert_eq!(disp.rotation(), Rotation::Rotate90);
+    assert_eq!(disp.bounding_box().size, Size::new(32, 64));
+}
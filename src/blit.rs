@@ -0,0 +1,118 @@
+//! Hardware 2D engines, for drivers which can copy blocks of pixels without
+//! the CPU.
+
+use crate::{geometry, util, Orientation};
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+
+#[cfg(test)]
+mod tests;
+
+/// A display with a 2D graphics engine, such as STM32 DMA2D (Chrom-ART) or
+/// i.MX PXP.
+///
+/// Solid fills already reach the display through every transform as a single
+/// `fill_solid` of the physical area, so an engine accelerates them, and
+/// `clear`, in the display's own [`DrawTarget`] implementation. This adds the
+/// operations which can't be expressed as a `DrawTarget`: copying an area of
+/// the display, and copying a block of colors from memory while applying the
+/// transform. The transforms' `copy_area` and `blit` methods, such as
+/// [`Rotate90::blit`](crate::Rotate90::blit), send them to the engine, and
+/// fall back to drawing in software where the engine can't do what's needed.
+pub trait Blitter: DrawTarget {
+    /// Copy the pixels in `area` so that its top left is at `to`.
+    ///
+    /// Both areas are always within the display's bounding box, but may
+    /// overlap, in which case the copy must behave as if the whole of `area`
+    /// was read before anything was written.
+    fn copy_area(&mut self, area: &Rectangle, to: Point) -> Result<(), Self::Error>;
+
+    /// Write `colors`, a block of pixels in row-major order, to `area` with
+    /// `orientation` applied, returning whether the engine could do it.
+    ///
+    /// The block's size is the logical size of `area` in `orientation`, and its
+    /// pixel at `p` goes to
+    /// [`geometry::transform_point(orientation, *area, p)`](crate::geometry::transform_point).
+    /// `area` is always within the display's bounding box, and there are
+    /// always enough colors.
+    ///
+    /// Returning `Ok(false)` leaves the display unchanged, and the block is
+    /// drawn in software instead. This is the default, for engines which
+    /// only copy areas.
+    fn blit(
+        &mut self,
+        area: &Rectangle,
+        orientation: Orientation,
+        colors: &[Self::Color],
+    ) -> Result<bool, Self::Error> {
+        let _ = (area, orientation, colors);
+        Ok(false)
+    }
+}
+
+/// The logical area of a transformed display which is on the target, and the
+/// physical bounds the logical display maps to.
+fn visible<D: Dimensions>(
+    target: &D,
+    orientation: Orientation,
+    size: Size,
+    offset: Point,
+) -> (Rectangle, Rectangle) {
+    let bounds = Rectangle::new(offset, geometry::inverse_transform_size(orientation, size));
+    let visible = Rectangle::new(Point::zero(), size).intersection(
+        &geometry::inverse_transform_rect(orientation, bounds, target.bounding_box()),
+    );
+    (visible, bounds)
+}
+
+/// Copy a logical area of a transformed display to `to` with the display's
+/// engine, clipped so that both the source and destination are on the display.
+pub(crate) fn copy_area<D: Blitter>(
+    target: &mut D,
+    orientation: Orientation,
+    size: Size,
+    offset: Point,
+    area: &Rectangle,
+    to: Point,
+) -> Result<(), D::Error> {
+    let (visible, bounds) = visible(target, orientation, size, offset);
+    let delta = to - area.top_left;
+    let from = area
+        .intersection(&visible)
+        .intersection(&util::translate(&visible, -delta));
+    if util::is_empty(&from) {
+        return Ok(());
+    }
+
+    let to = geometry::transform_rect(orientation, bounds, util::translate(&from, delta));
+    target.copy_area(
+        &geometry::transform_rect(orientation, bounds, from),
+        to.top_left,
+    )
+}
+
+/// Write a block of colors to a logical area of a transformed display with the
+/// display's engine, returning whether it was done. If the area isn't entirely
+/// on the display, or there are too few colors, it's left to the caller.
+pub(crate) fn blit<D: Blitter>(
+    target: &mut D,
+    orientation: Orientation,
+    size: Size,
+    offset: Point,
+    area: &Rectangle,
+    colors: &[D::Color],
+) -> Result<bool, D::Error> {
+    let (visible, bounds) = visible(target, orientation, size, offset);
+    let len = area.size.width as usize * area.size.height as usize;
+    if !util::contains_rect(&visible, area) || colors.len() < len {
+        return Ok(false);
+    }
+    if len == 0 {
+        return Ok(true);
+    }
+
+    target.blit(
+        &geometry::transform_rect(orientation, bounds, *area),
+        orientation,
+        &colors[..len],
+    )
+}
@@ -0,0 +1,164 @@
+use super::*;
+use crate::{
+    tests::{color, rect},
+    Reorient, Rotate90,
+};
+use core::convert::Infallible;
+use embedded_graphics::{mock_display::MockDisplay, pixelcolor::Rgb565};
+
+/// A display with an engine which can copy, and blit in the orientations
+/// `rotates` allows.
+struct Engine {
+    disp: MockDisplay<Rgb565>,
+    rotates: bool,
+    blits: usize,
+}
+
+impl Engine {
+    fn new(rotates: bool) -> Self {
+        let mut disp = MockDisplay::new();
+        disp.set_allow_overdraw(true);
+        Engine {
+            disp,
+            rotates,
+            blits: 0,
+        }
+    }
+}
+
+impl Dimensions for Engine {
+    fn bounding_box(&self) -> Rectangle {
+        self.disp.bounding_box()
+    }
+}
+
+impl DrawTarget for Engine {
+    type Color = Rgb565;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let bbox = self.bounding_box();
+        self.disp
+            .draw_iter(pixels.into_iter().filter(|p| bbox.contains(p.0)))
+    }
+}
+
+impl Blitter for Engine {
+    fn copy_area(&mut self, area: &Rectangle, to: Point) -> Result<(), Self::Error> {
+        assert!(util::contains_rect(&self.bounding_box(), area));
+        let mut saved = [None; 64 * 64];
+        for (slot, p) in saved.iter_mut().zip(util::points(area)) {
+            *slot = self.disp.get_pixel(p);
+        }
+        let delta = to - area.top_left;
+        for (&color, p) in saved.iter().zip(util::points(area)) {
+            self.disp.set_pixel(p + delta, color);
+        }
+        Ok(())
+    }
+
+    fn blit(
+        &mut self,
+        area: &Rectangle,
+        orientation: Orientation,
+        colors: &[Self::Color],
+    ) -> Result<bool, Self::Error> {
+        assert!(util::contains_rect(&self.bounding_box(), area));
+        if !self.rotates && orientation != Orientation::IDENTITY {
+            return Ok(false);
+        }
+
+        self.blits += 1;
+        let block = Rectangle::new(
+            Point::zero(),
+            geometry::transform_size(orientation, area.size),
+        );
+        for (p, &color) in util::points(&block).zip(colors) {
+            let p = geometry::transform_point(orientation, *area, p);
+            self.disp.set_pixel(p, Some(color));
+        }
+        Ok(true)
+    }
+}
+
+#[test]
+fn copy_area() {
+    let pattern = rect(0, 0, 3, 2);
+
+    for o in Orientation::ALL {
+        let mut disp = Reorient::new(Engine::new(false), o);
+        disp.fill_contiguous(&pattern, util::points(&pattern).map(color))
+            .unwrap();
+        // Overlapping the source
+        disp.copy_area(&pattern, Point::new(2, 1)).unwrap();
+
+        let mut expected = Reorient::new(MockDisplay::new(), o);
+        expected.set_allow_overdraw(true);
+        expected
+            .fill_contiguous(&pattern, util::points(&pattern).map(color))
+            .unwrap();
+        expected
+            .fill_contiguous(
+                &util::translate(&pattern, Point::new(2, 1)),
+                util::points(&pattern).map(color),
+            )
+            .unwrap();
+
+        disp.into_inner()
+            .disp
+            .assert_eq_with_message(&expected.into_inner(), |f| write!(f, "{:?}", o));
+    }
+}
+
+#[test]
+fn copy_area_clipped() {
+    let mut disp = Rotate90::new(Engine::new(false));
+    let row = rect(0, 0, 4, 1);
+    disp.fill_contiguous(&row, util::points(&row).map(color))
+        .unwrap();
+
+    // Only the part which fits is copied
+    disp.copy_area(&row, Point::new(62, 0)).unwrap();
+    assert_eq!(disp.as_ref().disp.affected_area(), rect(63, 0, 1, 64));
+    let disp = disp.into_inner().disp;
+    assert_eq!(
+        disp.get_pixel(Point::new(63, 62)),
+        Some(color(Point::new(0, 0)))
+    );
+    assert_eq!(
+        disp.get_pixel(Point::new(63, 63)),
+        Some(color(Point::new(1, 0)))
+    );
+}
+
+#[test]
+fn blit() {
+    let area = rect(1, 2, 4, 3);
+    let colors: [Rgb565; 12] = core::array::from_fn(|i| Rgb565::new(i as u8, 0, 0));
+
+    for rotates in [true, false] {
+        for o in Orientation::ALL {
+            let mut disp = Reorient::new(Engine::new(rotates), o);
+            disp.blit(&area, &colors).unwrap();
+
+            let mut expected = Reorient::new(MockDisplay::new(), o);
+            expected.fill_contiguous(&area, colors).unwrap();
+
+            let engine = disp.into_inner();
+            let used = rotates || o == Orientation::IDENTITY;
+            assert_eq!(engine.blits, used as usize, "{:?}", o);
+            engine
+                .disp
+                .assert_eq_with_message(&expected.into_inner(), |f| write!(f, "{:?}", o));
+        }
+    }
+
+    // Partly off the display, so drawn in software
+    let mut disp = Rotate90::new(Engine::new(true));
+    disp.blit(&rect(62, 0, 4, 3), &colors).unwrap();
+    assert_eq!(disp.as_ref().blits, 0);
+    assert_eq!(disp.as_ref().disp.affected_area().size, Size::new(3, 2));
+}
@@ -0,0 +1,223 @@
+//! Limit how much drawing is done each frame.
+
+use crate::Clock;
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+
+#[cfg(test)]
+mod tests;
+
+/// A limit on the drawing done by a [`Budget`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Limit {
+    /// The number of pixels drawn, including by fills.
+    Pixels,
+    /// The number of draw calls, of any kind.
+    Calls,
+    /// The time since the budget was reset.
+    Time,
+}
+
+/// Error from drawing to a [`Budget`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum BudgetError<E> {
+    /// The budget for the frame has been used up.
+    Exceeded(Limit),
+    /// The wrapped display returned an error.
+    Display(E),
+}
+
+impl<E> From<E> for BudgetError<E> {
+    fn from(err: E) -> Self {
+        BudgetError::Display(err)
+    }
+}
+
+/// Limit the pixels, draw calls and time spent drawing each frame.
+///
+/// This is for real-time systems which can't afford to let a misbehaving
+/// widget take too long to draw. The main loop calls
+/// [`reset_budget`](Self::reset_budget) at the start of each frame, and once
+/// any of the limits set with [`max_pixels`](Self::max_pixels),
+/// [`max_calls`](Self::max_calls) and [`with_clock`](Self::with_clock) is
+/// reached, drawing returns [`BudgetError::Exceeded`] until the next reset.
+///
+/// Fills which would go over the pixel limit aren't drawn at all, while
+/// `draw_iter` draws pixels up to the limit. The time is checked at the start
+/// of each draw call, so a single long call can overrun it, by as much as the
+/// pixel limit allows.
+pub struct Budget<D, K = fn() -> u32> {
+    target: D,
+    clock: K,
+    max_pixels: Option<u32>,
+    max_calls: Option<u32>,
+    max_micros: Option<u32>,
+    pixels: u32,
+    calls: u32,
+    start: u32,
+    exceeded: Option<Limit>,
+}
+
+impl<D> Budget<D> {
+    /// Wrap `target`, initially with no limits.
+    pub fn new(target: D) -> Self {
+        Budget {
+            target,
+            clock: || 0,
+            max_pixels: None,
+            max_calls: None,
+            max_micros: None,
+            pixels: 0,
+            calls: 0,
+            start: 0,
+            exceeded: None,
+        }
+    }
+}
+
+impl<D, K: Clock> Budget<D, K> {
+    /// Limit the time each frame to `max_micros` microseconds, measured with
+    /// `clock`. This resets the budget.
+    pub fn with_clock<L: Clock>(self, clock: L, max_micros: u32) -> Budget<D, L> {
+        let mut budget = Budget {
+            target: self.target,
+            clock,
+            max_pixels: self.max_pixels,
+            max_calls: self.max_calls,
+            max_micros: Some(max_micros),
+            pixels: 0,
+            calls: 0,
+            start: 0,
+            exceeded: None,
+        };
+        budget.reset_budget();
+        budget
+    }
+
+    /// Limit the pixels drawn each frame.
+    pub fn max_pixels(mut self, max_pixels: u32) -> Self {
+        self.max_pixels = Some(max_pixels);
+        self
+    }
+
+    /// Limit the draw calls each frame.
+    pub fn max_calls(mut self, max_calls: u32) -> Self {
+        self.max_calls = Some(max_calls);
+        self
+    }
+
+    /// Start a new frame, with the whole budget available.
+    pub fn reset_budget(&mut self) {
+        self.pixels = 0;
+        self.calls = 0;
+        self.start = self.clock.micros();
+        self.exceeded = None;
+    }
+
+    /// Return the pixels drawn so far this frame.
+    pub fn pixels(&self) -> u32 {
+        self.pixels
+    }
+
+    /// Return the draw calls made so far this frame.
+    pub fn calls(&self) -> u32 {
+        self.calls
+    }
+
+    /// Return the limit which has been reached this frame, if any.
+    pub fn exceeded(&self) -> Option<Limit> {
+        self.exceeded
+    }
+
+    /// Check the budget for a draw call of `pixels` pixels, if known, and
+    /// charge it.
+    fn charge<E>(&mut self, pixels: Option<u32>) -> Result<(), BudgetError<E>> {
+        if self.exceeded.is_none() {
+            let over = |max: Option<u32>, used: u32| max.is_some_and(|max| used > max);
+
+            self.exceeded = if over(self.max_calls, self.calls + 1) {
+                Some(Limit::Calls)
+            } else if over(
+                self.max_micros,
+                self.clock.micros().wrapping_sub(self.start),
+            ) {
+                Some(Limit::Time)
+            } else if over(
+                self.max_pixels,
+                self.pixels.saturating_add(pixels.unwrap_or(0)),
+            ) {
+                Some(Limit::Pixels)
+            } else {
+                None
+            };
+        }
+
+        match self.exceeded {
+            Some(limit) => Err(BudgetError::Exceeded(limit)),
+            None => {
+                self.calls += 1;
+                self.pixels = self.pixels.saturating_add(pixels.unwrap_or(0));
+                Ok(())
+            }
+        }
+    }
+}
+
+impl_wrapper!(Budget<D, K>);
+
+impl<D: Dimensions, K> Dimensions for Budget<D, K> {
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+impl<D: DrawTarget, K: Clock> DrawTarget for Budget<D, K> {
+    type Color = D::Color;
+    type Error = BudgetError<D::Error>;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.charge(None)?;
+
+        let remaining = self.max_pixels.map_or(u32::MAX, |max| max - self.pixels);
+        let mut count = 0;
+        let mut cut = false;
+        let res = self.target.draw_iter(pixels.into_iter().take_while(|_| {
+            if count < remaining {
+                count += 1;
+                true
+            } else {
+                cut = true;
+                false
+            }
+        }));
+
+        self.pixels += count;
+        res?;
+        if cut {
+            self.exceeded = Some(Limit::Pixels);
+            return Err(BudgetError::Exceeded(Limit::Pixels));
+        }
+        Ok(())
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        self.charge(Some(area.size.width.saturating_mul(area.size.height)))?;
+        Ok(self.target.fill_contiguous(area, colors)?)
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.charge(Some(area.size.width.saturating_mul(area.size.height)))?;
+        Ok(self.target.fill_solid(area, color)?)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        let size = self.target.bounding_box().size;
+        self.charge(Some(size.width.saturating_mul(size.height)))?;
+        Ok(self.target.clear(color)?)
+    }
+}
@@ -0,0 +1,63 @@
+use super::*;
+use crate::{tests::rect, Rotate90};
+use core::cell::Cell;
+use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+#[test]
+fn budget_pixels() {
+    let mut disp = Budget::new(Rotate90::new(MockDisplay::<BinaryColor>::new())).max_pixels(10);
+    disp.as_mut().as_mut().set_allow_overdraw(true);
+
+    disp.fill_solid(&rect(0, 0, 2, 2), BinaryColor::On).unwrap();
+    // Too big, so not drawn, but a smaller fill still fits until the limit is
+    // reached.
+    assert_eq!(
+        disp.fill_solid(&rect(0, 2, 4, 2), BinaryColor::On),
+        Err(BudgetError::Exceeded(Limit::Pixels))
+    );
+    assert_eq!(disp.exceeded(), Some(Limit::Pixels));
+    assert_eq!(
+        disp.draw_iter([Pixel(Point::new(4, 0), BinaryColor::On)]),
+        Err(BudgetError::Exceeded(Limit::Pixels))
+    );
+
+    disp.reset_budget();
+    assert_eq!(disp.exceeded(), None);
+    disp.fill_solid(&rect(0, 0, 2, 2), BinaryColor::On).unwrap();
+    // Pixels are drawn up to the limit
+    assert_eq!(
+        disp.draw_iter((0..8).map(|x| Pixel(Point::new(x, 4), BinaryColor::On))),
+        Err(BudgetError::Exceeded(Limit::Pixels))
+    );
+    assert_eq!((disp.pixels(), disp.calls()), (10, 2));
+    assert_eq!(disp.as_ref().as_ref().affected_area(), rect(59, 0, 5, 6));
+}
+
+#[test]
+fn budget_calls_and_time() {
+    let mut disp = Budget::new(MockDisplay::<BinaryColor>::new()).max_calls(2);
+    disp.draw_iter([Pixel(Point::new(0, 0), BinaryColor::On)])
+        .unwrap();
+    disp.draw_iter([Pixel(Point::new(1, 0), BinaryColor::On)])
+        .unwrap();
+    assert_eq!(
+        disp.clear(BinaryColor::Off),
+        Err(BudgetError::Exceeded(Limit::Calls))
+    );
+    assert_eq!(disp.calls(), 2);
+
+    let now = Cell::new(5);
+    let mut disp = Budget::new(MockDisplay::<BinaryColor>::new()).with_clock(|| now.get(), 2000);
+    disp.fill_solid(&rect(0, 0, 2, 2), BinaryColor::On).unwrap();
+    now.set(7);
+    disp.fill_solid(&rect(2, 0, 2, 2), BinaryColor::On).unwrap();
+    now.set(8);
+    assert_eq!(
+        disp.fill_solid(&rect(4, 0, 2, 2), BinaryColor::On),
+        Err(BudgetError::Exceeded(Limit::Time))
+    );
+
+    disp.reset_budget();
+    disp.fill_solid(&rect(4, 0, 2, 2), BinaryColor::On).unwrap();
+    assert_eq!(disp.as_ref().affected_area(), rect(0, 0, 6, 2));
+}
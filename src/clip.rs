@@ -0,0 +1,557 @@
+//! Adapters which clip drawing to a shape.
+
+use crate::util;
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+
+#[cfg(test)]
+mod tests;
+
+/// Clip drawing to a circle.
+///
+/// By default the circle is the largest one which fits in the center of the
+/// display, which is the visible area of round displays. Pixels outside the
+/// circle are discarded, and solid fills are trimmed to the circle one row at
+/// a time, so nothing is sent to the invisible corners of the display.
+///
+/// The circle is in the coordinates of this adapter, so it can be combined with
+/// rotation in either order. The pixels covered match those of an
+/// `embedded_graphics` `Circle` with the same center and diameter.
+pub struct CircleClip<D> {
+    target: D,
+    circle: Option<(Point, u32)>,
+}
+
+/// A circle in doubled coordinates, so that the center and pixel centers are
+/// integers.
+#[derive(Clone, Copy)]
+struct Circle {
+    center: Point,
+    threshold: i64,
+}
+
+impl Circle {
+    fn new(top_left: Point, diameter: u32) -> Self {
+        let d = i64::from(diameter);
+        Circle {
+            center: top_left * 2 + Point::new(diameter as i32, diameter as i32),
+            // Small circles are a little smaller, as in `embedded_graphics`.
+            threshold: if d <= 4 { d * d - d / 2 } else { d * d },
+        }
+    }
+
+    /// Squared distance from the center of pixel `p` to the circle center.
+    fn dist_sq(&self, p: Point) -> i64 {
+        let d = p * 2 + Point::new(1, 1) - self.center;
+        i64::from(d.x) * i64::from(d.x) + i64::from(d.y) * i64::from(d.y)
+    }
+
+    fn contains(&self, p: Point) -> bool {
+        self.dist_sq(p) < self.threshold
+    }
+
+    /// Range of x coordinates inside the circle on row `y`, if any.
+    fn span(&self, y: i32) -> Option<(i32, i32)> {
+        let dy = i64::from(2 * y + 1 - self.center.y);
+        let remaining = self.threshold - dy * dy;
+        if remaining <= 0 {
+            return None;
+        }
+
+        // Largest |2x + 1 - cx| strictly inside the circle
+        let s = util::isqrt((remaining - 1) as u64) as i32;
+        let start = (self.center.x - s - 1).div_euclid(2) + ((self.center.x - s - 1) & 1);
+        let end = (self.center.x + s - 1).div_euclid(2);
+        Some((start, end))
+    }
+}
+
+impl<D> CircleClip<D> {
+    /// Clip drawing to the largest circle which fits in the display.
+    pub fn new(target: D) -> Self {
+        CircleClip {
+            target,
+            circle: None,
+        }
+    }
+
+    /// Clip drawing to the circle with the given center and diameter instead.
+    pub fn with_circle(mut self, center: Point, diameter: u32) -> Self {
+        let offset = (diameter as i32 - 1) / 2;
+        self.circle = Some((center - Point::new(offset, offset), diameter));
+        self
+    }
+}
+
+impl<D: Dimensions> CircleClip<D> {
+    fn circle(&self) -> Circle {
+        let (top_left, diameter) = self.circle.unwrap_or_else(|| {
+            let bounds = self.target.bounding_box();
+            let diameter = bounds.size.width.min(bounds.size.height);
+            let offset = Point::new(
+                ((bounds.size.width - diameter) / 2) as i32,
+                ((bounds.size.height - diameter) / 2) as i32,
+            );
+            (bounds.top_left + offset, diameter)
+        });
+        Circle::new(top_left, diameter)
+    }
+}
+
+impl_wrapper!(CircleClip<D>);
+
+impl<D: Dimensions> Dimensions for CircleClip<D> {
+    #[inline]
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+impl<D: DrawTarget> DrawTarget for CircleClip<D> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let circle = self.circle();
+
+        self.target.draw_iter(
+            pixels
+                .into_iter()
+                .filter(|Pixel(loc, _)| circle.contains(*loc)),
+        )
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let circle = self.circle();
+
+        // The circle is convex, so the area is inside it if all its corners are.
+        let inside = area.bottom_right().is_some_and(|br| {
+            [
+                area.top_left,
+                br,
+                Point::new(area.top_left.x, br.y),
+                Point::new(br.x, area.top_left.y),
+            ]
+            .iter()
+            .all(|p| circle.contains(*p))
+        });
+
+        if inside {
+            self.target.fill_contiguous(area, colors)
+        } else {
+            self.target.draw_iter(
+                util::points(area)
+                    .zip(colors)
+                    .map(|(loc, col)| Pixel(loc, col))
+                    .filter(|Pixel(loc, _)| circle.contains(*loc)),
+            )
+        }
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let circle = self.circle();
+        let (left, right) = (
+            area.top_left.x,
+            area.top_left.x + area.size.width as i32 - 1,
+        );
+
+        for y in area.top_left.y..area.top_left.y + area.size.height as i32 {
+            if let Some((start, end)) = circle.span(y) {
+                let (start, end) = (start.max(left), end.min(right));
+                if start <= end {
+                    let row = Rectangle::new(
+                        Point::new(start, y),
+                        Size::new((end - start + 1) as u32, 1),
+                    );
+                    self.target.fill_solid(&row, color)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.fill_solid(&self.bounding_box(), color)
+    }
+}
+
+/// Clip drawing to a rectangle with rounded corners.
+///
+/// By default the rectangle is the whole display, so only the corners are
+/// clipped, giving a rounded panel. Like [`CircleClip`], pixels outside are
+/// discarded and fills are trimmed to fit, with the rows between the corners
+/// filled all at once, and the rectangle is in the coordinates of this
+/// adapter. The pixels covered match those of an `embedded_graphics`
+/// `RoundedRectangle` with equal circular corners of the same radius.
+pub struct RoundedClip<D> {
+    target: D,
+    rect: Option<Rectangle>,
+    radius: u32,
+}
+
+/// A rounded rectangle, as the corner circles it's trimmed to.
+#[derive(Clone, Copy)]
+struct Rounded {
+    rect: Rectangle,
+    radius: i32,
+    top: (Circle, Circle),
+    bottom: (Circle, Circle),
+}
+
+impl Rounded {
+    fn new(rect: Rectangle, radius: u32) -> Self {
+        let radius = radius.min(rect.size.width / 2).min(rect.size.height / 2);
+        let far = rect.top_left + rect.size - Size::new(radius * 2, radius * 2);
+        let corner = |x, y| Circle::new(Point::new(x, y), radius * 2);
+        let (left, top) = (rect.top_left.x, rect.top_left.y);
+
+        Rounded {
+            rect,
+            radius: radius as i32,
+            top: (corner(left, top), corner(far.x, top)),
+            bottom: (corner(left, far.y), corner(far.x, far.y)),
+        }
+    }
+
+    /// Range of x coordinates inside the rectangle on row `y`, if any.
+    fn span(&self, y: i32) -> Option<(i32, i32)> {
+        let rows = self.rect.rows();
+        if !rows.contains(&y) || self.rect.size.width == 0 {
+            return None;
+        }
+
+        let (left, right) = if y < rows.start + self.radius {
+            self.top
+        } else if y >= rows.end - self.radius {
+            self.bottom
+        } else {
+            return Some((self.rect.top_left.x, self.rect.columns().end - 1));
+        };
+        Some((left.span(y)?.0, right.span(y)?.1))
+    }
+
+    fn contains(&self, p: Point) -> bool {
+        self.span(p.y)
+            .is_some_and(|(start, end)| (start..=end).contains(&p.x))
+    }
+
+    /// The rows which aren't in a corner, and so are the full width.
+    fn straight(&self) -> Rectangle {
+        Rectangle::new(
+            self.rect.top_left + Point::new(0, self.radius),
+            self.rect.size - Size::new(0, self.radius as u32 * 2),
+        )
+    }
+}
+
+impl<D> RoundedClip<D> {
+    /// Round the corners of the display with the given radius.
+    ///
+    /// The radius is limited to half the rectangle's shorter side.
+    pub fn new(target: D, radius: u32) -> Self {
+        RoundedClip {
+            target,
+            rect: None,
+            radius,
+        }
+    }
+
+    /// Clip drawing to `rect`, rather than the whole display.
+    pub fn with_rect(mut self, rect: Rectangle) -> Self {
+        self.rect = Some(rect);
+        self
+    }
+
+    /// Return the corner radius.
+    pub fn radius(&self) -> u32 {
+        self.radius
+    }
+
+    /// Change the corner radius.
+    pub fn set_radius(&mut self, radius: u32) {
+        self.radius = radius;
+    }
+}
+
+impl<D: Dimensions> RoundedClip<D> {
+    fn rounded(&self) -> Rounded {
+        let rect = self.rect.unwrap_or_else(|| self.target.bounding_box());
+        Rounded::new(rect, self.radius)
+    }
+}
+
+impl_wrapper!(RoundedClip<D>);
+
+impl<D: Dimensions> Dimensions for RoundedClip<D> {
+    #[inline]
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+impl<D: DrawTarget> DrawTarget for RoundedClip<D> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let rounded = self.rounded();
+
+        self.target.draw_iter(
+            pixels
+                .into_iter()
+                .filter(|Pixel(loc, _)| rounded.contains(*loc)),
+        )
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let rounded = self.rounded();
+
+        // Like a circle, this is convex, so checking the corners is enough.
+        let inside = area.bottom_right().is_some_and(|br| {
+            [
+                area.top_left,
+                br,
+                Point::new(area.top_left.x, br.y),
+                Point::new(br.x, area.top_left.y),
+            ]
+            .iter()
+            .all(|p| rounded.contains(*p))
+        });
+
+        if inside {
+            self.target.fill_contiguous(area, colors)
+        } else {
+            let target = &mut self.target;
+            let mut colors = colors.into_iter();
+            mask_runs(&|p| rounded.contains(p), area, |skipped, run| {
+                if skipped > 0 {
+                    colors.nth(skipped - 1);
+                }
+                target.fill_contiguous(&run, colors.by_ref().take(run.size.width as usize))
+            })
+        }
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let rounded = self.rounded();
+        let straight = rounded.straight().intersection(area);
+        let (left, right) = (
+            area.top_left.x,
+            area.top_left.x + area.size.width as i32 - 1,
+        );
+
+        for y in area.rows() {
+            if straight.rows().contains(&y) {
+                if y == straight.top_left.y {
+                    self.target.fill_solid(&straight, color)?;
+                }
+                continue;
+            }
+            if let Some((start, end)) = rounded.span(y) {
+                let (start, end) = (start.max(left), end.min(right));
+                if start <= end {
+                    let row = Rectangle::new(
+                        Point::new(start, y),
+                        Size::new((end - start + 1) as u32, 1),
+                    );
+                    self.target.fill_solid(&row, color)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.fill_solid(&self.bounding_box(), color)
+    }
+}
+
+/// A set of points which a [`Masked`] display draws to.
+///
+/// This is implemented for predicates, `Fn(Point) -> bool`, and for
+/// [`Bitmap`].
+pub trait Mask {
+    /// Return whether `p` is drawn to.
+    fn contains(&self, p: Point) -> bool;
+}
+
+impl<F: Fn(Point) -> bool> Mask for F {
+    fn contains(&self, p: Point) -> bool {
+        self(p)
+    }
+}
+
+/// A 1 bit per pixel mask, with its top left at the origin.
+///
+/// Each row is packed into whole bytes, most significant bit first, in the
+/// same layout as an `embedded_graphics` `ImageRaw<BinaryColor>`. Set bits are
+/// drawn to, and anything outside the bitmap is masked out.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Bitmap<'a> {
+    data: &'a [u8],
+    width: u32,
+}
+
+impl<'a> Bitmap<'a> {
+    /// A bitmap `width` pixels wide, with as many rows as there are in `data`.
+    pub const fn new(data: &'a [u8], width: u32) -> Self {
+        Bitmap { data, width }
+    }
+
+    const fn stride(&self) -> usize {
+        (self.width as usize).div_ceil(8)
+    }
+
+    /// Return the size of the bitmap.
+    pub const fn size(&self) -> Size {
+        let height = match self.data.len().checked_div(self.stride()) {
+            Some(height) => height,
+            None => 0,
+        };
+        Size::new(self.width, height as u32)
+    }
+}
+
+impl Mask for Bitmap<'_> {
+    fn contains(&self, p: Point) -> bool {
+        if p.x < 0 || p.y < 0 || p.x as u32 >= self.width {
+            return false;
+        }
+        let (x, y) = (p.x as usize, p.y as usize);
+        self.data
+            .get(y * self.stride() + x / 8)
+            .is_some_and(|byte| byte & (0x80 >> (x % 8)) != 0)
+    }
+}
+
+/// Clip drawing to an arbitrary [`Mask`].
+///
+/// This is for clipping to shapes which aren't rectangles, such as the wedge of
+/// a gauge or a speech bubble. Pixels outside the mask are discarded, and fills
+/// are split into the runs of each row which are inside it, so that they're
+/// still sent as fills.
+///
+/// Like [`CircleClip`], the mask is in the coordinates of this adapter, so
+/// wrapping a rotated display puts it in logical coordinates.
+pub struct Masked<D, M> {
+    target: D,
+    mask: M,
+}
+
+impl<D, M> Masked<D, M> {
+    /// Clip drawing on `target` to `mask`.
+    pub fn new(target: D, mask: M) -> Self {
+        Masked { target, mask }
+    }
+
+    /// Return the mask.
+    pub fn mask(&self) -> &M {
+        &self.mask
+    }
+
+    /// Change the mask.
+    pub fn set_mask(&mut self, mask: M) {
+        self.mask = mask;
+    }
+}
+
+/// Call `f` with each run of points in `area` inside `mask`, along with the
+/// number of points outside the mask since the previous run.
+fn mask_runs<E>(
+    mask: &impl Mask,
+    area: &Rectangle,
+    mut f: impl FnMut(usize, Rectangle) -> Result<(), E>,
+) -> Result<(), E> {
+    let end = area.top_left.x + area.size.width as i32;
+    let run = |start: i32, x: i32, y: i32| {
+        Rectangle::new(Point::new(start, y), Size::new((x - start) as u32, 1))
+    };
+
+    let mut skipped = 0;
+    for y in area.rows() {
+        let mut start = None;
+        for x in area.columns() {
+            match (start, mask.contains(Point::new(x, y))) {
+                (None, true) => start = Some(x),
+                (None, false) => skipped += 1,
+                (Some(s), false) => {
+                    f(skipped, run(s, x, y))?;
+                    start = None;
+                    skipped = 1;
+                }
+                (Some(_), true) => {}
+            }
+        }
+        if let Some(s) = start {
+            f(skipped, run(s, end, y))?;
+            skipped = 0;
+        }
+    }
+    Ok(())
+}
+
+impl_wrapper!(Masked<D, M>);
+
+impl<D: Dimensions, M> Dimensions for Masked<D, M> {
+    #[inline]
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+impl<D: DrawTarget, M: Mask> DrawTarget for Masked<D, M> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let mask = &self.mask;
+        self.target.draw_iter(
+            pixels
+                .into_iter()
+                .filter(|Pixel(loc, _)| mask.contains(*loc)),
+        )
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        if util::points(area).all(|p| self.mask.contains(p)) {
+            return self.target.fill_contiguous(area, colors);
+        }
+
+        let mut colors = colors.into_iter();
+        let target = &mut self.target;
+        mask_runs(&self.mask, area, |skipped, run| {
+            if skipped > 0 {
+                colors.nth(skipped - 1);
+            }
+            target.fill_contiguous(&run, colors.by_ref().take(run.size.width as usize))
+        })
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let target = &mut self.target;
+        mask_runs(&self.mask, area, |_, run| target.fill_solid(&run, color))
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.fill_solid(&self.bounding_box(), color)
+    }
+}
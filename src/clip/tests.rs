@@ -0,0 +1,226 @@
+use super::*;
+use crate::{tests::rect, Rotate90};
+use embedded_graphics::{
+    mock_display::MockDisplay,
+    pixelcolor::BinaryColor,
+    primitives::{Circle, Primitive, PrimitiveStyle, RoundedRectangle},
+    Drawable,
+};
+
+#[test]
+fn circle_matches_primitive() {
+    for diameter in [3, 4, 5, 8, 21] {
+        let center = Point::new(12, 12);
+
+        let mut expected = MockDisplay::<BinaryColor>::new();
+        Circle::with_center(center, diameter)
+            .into_styled(PrimitiveStyle::with_fill(BinaryColor::On))
+            .draw(&mut expected)
+            .unwrap();
+
+        let mut filled = CircleClip::new(MockDisplay::new()).with_circle(center, diameter);
+        filled
+            .fill_solid(&rect(0, 0, 30, 30), BinaryColor::On)
+            .unwrap();
+        filled.assert_eq(&expected);
+
+        let mut drawn = CircleClip::new(MockDisplay::new()).with_circle(center, diameter);
+        drawn
+            .fill_contiguous(&rect(0, 0, 30, 30), core::iter::repeat(BinaryColor::On))
+            .unwrap();
+        drawn.assert_eq(&expected);
+    }
+}
+
+#[test]
+fn circle_inscribed() {
+    let mut disp = CircleClip::new(MockDisplay::<BinaryColor>::new());
+
+    disp.clear(BinaryColor::On).unwrap();
+    assert_eq!(disp.affected_area(), rect(0, 0, 64, 64));
+    assert_eq!(disp.get_pixel(Point::new(0, 0)), None);
+    assert_eq!(disp.get_pixel(Point::new(0, 32)), Some(BinaryColor::On));
+    assert_eq!(disp.get_pixel(Point::new(63, 63)), None);
+
+    // Entirely inside, so drawn directly
+    let mut disp = CircleClip::new(MockDisplay::<BinaryColor>::new());
+    disp.fill_contiguous(&rect(30, 30, 2, 2), [BinaryColor::On; 4])
+        .unwrap();
+    assert_eq!(disp.affected_area(), rect(30, 30, 2, 2));
+
+    // Composed with rotation
+    let mut disp = CircleClip::new(Rotate90::new(MockDisplay::<BinaryColor>::new()));
+    disp.draw_iter([
+        Pixel(Point::new(0, 0), BinaryColor::On),
+        Pixel(Point::new(0, 32), BinaryColor::On),
+    ])
+    .unwrap();
+    assert_eq!(disp.as_ref().as_ref().affected_area(), rect(31, 0, 1, 1));
+}
+
+#[test]
+fn rounded_matches_primitive() {
+    for (size, radius) in [
+        (Size::new(20, 12), 0),
+        (Size::new(20, 12), 1),
+        (Size::new(20, 12), 2),
+        (Size::new(20, 12), 5),
+        (Size::new(9, 30), 4),
+        (Size::new(9, 30), 10),
+    ] {
+        let area = Rectangle::new(Point::new(3, 2), size);
+
+        let mut expected = MockDisplay::<BinaryColor>::new();
+        RoundedRectangle::with_equal_corners(area, Size::new(radius, radius))
+            .into_styled(PrimitiveStyle::with_fill(BinaryColor::On))
+            .draw(&mut expected)
+            .unwrap();
+
+        let mut filled = RoundedClip::new(MockDisplay::new(), radius).with_rect(area);
+        filled
+            .fill_solid(&rect(0, 0, 30, 40), BinaryColor::On)
+            .unwrap();
+        filled.assert_eq(&expected);
+
+        let mut drawn = RoundedClip::new(MockDisplay::new(), radius).with_rect(area);
+        drawn
+            .draw_iter(crate::util::points(&rect(0, 0, 30, 40)).map(|p| Pixel(p, BinaryColor::On)))
+            .unwrap();
+        drawn.assert_eq(&expected);
+    }
+}
+
+#[test]
+fn rounded_fills() {
+    use BinaryColor::{Off, On};
+
+    let mut disp =
+        RoundedClip::new(crate::Instrument::new(MockDisplay::new()), 2).with_rect(rect(0, 0, 6, 6));
+    disp.fill_solid(&rect(0, 0, 8, 8), On).unwrap();
+    // Two rows for each pair of corners, and one for the rest
+    assert_eq!(disp.stats().fills, 5);
+    disp.assert_pattern(&[
+        " #### ", //
+        "######", //
+        "######", //
+        "######", //
+        "######", //
+        " #### ", //
+    ]);
+
+    let mut disp = RoundedClip::new(MockDisplay::new(), 2).with_rect(rect(0, 0, 6, 6));
+    disp.fill_contiguous(
+        &rect(0, 4, 6, 2),
+        [
+            On, Off, On, Off, On, Off, //
+            Off, On, Off, On, Off, On,
+        ],
+    )
+    .unwrap();
+    disp.assert_pattern(&[
+        "      ", //
+        "      ", //
+        "      ", //
+        "      ", //
+        "#.#.#.", //
+        " #.#. ", //
+    ]);
+}
+
+#[test]
+fn rounded_rotated() {
+    // The whole display, rounded in logical coordinates
+    let mut expected = MockDisplay::<BinaryColor>::new();
+    RoundedRectangle::with_equal_corners(rect(0, 0, 64, 64), Size::new(7, 7))
+        .into_styled(PrimitiveStyle::with_fill(BinaryColor::On))
+        .draw(&mut expected)
+        .unwrap();
+
+    let mut disp = RoundedClip::new(Rotate90::new(MockDisplay::new()), 7);
+    disp.clear(BinaryColor::On).unwrap();
+    disp.into_inner().into_inner().assert_eq(&expected);
+
+    let mut disp = Rotate90::new(RoundedClip::new(MockDisplay::new(), 7));
+    disp.clear(BinaryColor::On).unwrap();
+    disp.into_inner().into_inner().assert_eq(&expected);
+}
+
+#[test]
+fn mask_predicate() {
+    use BinaryColor::{Off, On};
+
+    // A diagonal wedge
+    let mut disp = Masked::new(MockDisplay::new(), |p: Point| p.x >= p.y);
+    disp.fill_solid(&rect(0, 0, 4, 4), On).unwrap();
+    disp.draw_iter([Pixel(Point::new(0, 5), On), Pixel(Point::new(5, 0), On)])
+        .unwrap();
+    disp.assert_pattern(&[
+        "#### #", //
+        " ###  ", //
+        "  ##  ", //
+        "   #  ", //
+    ]);
+
+    let mut disp = Masked::new(MockDisplay::new(), |p: Point| p.x != 1);
+    disp.fill_contiguous(&rect(0, 0, 3, 2), [On, Off, Off, Off, On, On])
+        .unwrap();
+    disp.assert_pattern(&[
+        "# .", //
+        ". #", //
+    ]);
+}
+
+#[test]
+fn mask_bitmap() {
+    use BinaryColor::{Off, On};
+
+    let data = [0b0110_0000, 0b1001_0000, 0b0110_0000];
+    let bitmap = Bitmap::new(&data, 4);
+    assert_eq!(bitmap.size(), Size::new(4, 3));
+
+    let mut disp = Masked::new(MockDisplay::new(), bitmap);
+    disp.fill_contiguous(
+        &rect(0, 0, 5, 3),
+        [
+            On, On, Off, On, On, //
+            On, On, Off, On, On, //
+            Off, Off, On, On, On,
+        ],
+    )
+    .unwrap();
+    disp.assert_pattern(&[
+        " #. ", //
+        "#  #", //
+        " .# ", //
+    ]);
+
+    // Fills inside the mask are passed straight through, and others are split
+    // into runs.
+    let mut disp = Masked::new(crate::Instrument::new(MockDisplay::new()), bitmap);
+    disp.as_mut().as_mut().set_allow_overdraw(true);
+    disp.fill_contiguous(&rect(1, 0, 2, 1), [On, On]).unwrap();
+    assert_eq!(disp.stats().fills, 1);
+    disp.fill_solid(&rect(0, 0, 8, 8), Off).unwrap();
+    assert_eq!(disp.stats().fills, 1 + 4);
+}
+
+#[test]
+fn mask_logical() {
+    let mask = |p: Point| (p.x + p.y) % 3 == 0;
+
+    let mut expected = Rotate90::new(MockDisplay::<BinaryColor>::new());
+    expected
+        .draw_iter(
+            crate::util::points(&rect(2, 1, 10, 7))
+                .filter(|&p| mask(p))
+                .map(|p| Pixel(p, BinaryColor::On)),
+        )
+        .unwrap();
+
+    let mut disp = Masked::new(Rotate90::new(MockDisplay::new()), mask);
+    disp.fill_solid(&rect(2, 1, 10, 7), BinaryColor::On)
+        .unwrap();
+    disp.into_inner()
+        .into_inner()
+        .assert_eq(&expected.into_inner());
+}
@@ -0,0 +1,1110 @@
+//! Adapters which transform colors as they're drawn.
+
+use crate::util;
+use core::marker::PhantomData;
+use embedded_graphics_core::{
+    pixelcolor::{
+        raw::{RawData, RawU8},
+        Bgr555, Bgr565, Bgr666, Bgr888, BinaryColor, Gray2, Gray4, Gray8, Rgb555, Rgb565, Rgb666,
+        Rgb888,
+    },
+    prelude::*,
+    primitives::Rectangle,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Maximum number of pixels forwarded as a single run by [`ChromaKey`].
+const KEY_RUN: usize = 32;
+
+/// Access to the individual channels of a color.
+///
+/// This is implemented for all the color types in
+/// [`embedded_graphics_core::pixelcolor`], and allows color adapters to work on
+/// each channel independently. Grayscale and binary colors have the same value
+/// in each channel.
+pub trait ColorChannels: PixelColor {
+    /// The maximum value of the red, green and blue channels.
+    const MAX: [u8; 3];
+
+    /// Return the red, green and blue channels.
+    fn to_channels(self) -> [u8; 3];
+
+    /// Construct a color from red, green and blue channels. Grayscale and
+    /// binary colors use the luminance of the channels.
+    fn from_channels(channels: [u8; 3]) -> Self;
+}
+
+/// Weighted luminance of channels which all have the same maximum value.
+fn luma(channels: [u8; 3]) -> u8 {
+    let [r, g, b] = channels.map(u32::from);
+    ((r * 299 + g * 587 + b * 114 + 500) / 1000) as u8
+}
+
+/// Luminance of a color, scaled to the range 0 to 255.
+pub(crate) fn luminance<C: ColorChannels>(color: C) -> u8 {
+    let channels = color.to_channels();
+    luma([0, 1, 2].map(|i| (u32::from(channels[i]) * 255 / u32::from(C::MAX[i])) as u8))
+}
+
+macro_rules! rgb_channels {
+    ($($color:ident),*) => {
+        $(
+            impl ColorChannels for $color {
+                const MAX: [u8; 3] = [$color::MAX_R, $color::MAX_G, $color::MAX_B];
+
+                fn to_channels(self) -> [u8; 3] {
+                    [self.r(), self.g(), self.b()]
+                }
+
+                fn from_channels([r, g, b]: [u8; 3]) -> Self {
+                    $color::new(r, g, b)
+                }
+            }
+        )*
+    };
+}
+
+rgb_channels!(Rgb555, Bgr555, Rgb565, Bgr565, Rgb666, Bgr666, Rgb888, Bgr888);
+
+macro_rules! gray_channels {
+    ($($color:ident: $max:expr),*) => {
+        $(
+            impl ColorChannels for $color {
+                const MAX: [u8; 3] = [$max; 3];
+
+                fn to_channels(self) -> [u8; 3] {
+                    [self.luma(); 3]
+                }
+
+                fn from_channels(channels: [u8; 3]) -> Self {
+                    $color::new(luma(channels))
+                }
+            }
+        )*
+    };
+}
+
+gray_channels!(Gray2: 3, Gray4: 15, Gray8: 255);
+
+impl ColorChannels for BinaryColor {
+    const MAX: [u8; 3] = [1; 3];
+
+    fn to_channels(self) -> [u8; 3] {
+        [self.is_on() as u8; 3]
+    }
+
+    fn from_channels(channels: [u8; 3]) -> Self {
+        BinaryColor::from(luma(channels) != 0)
+    }
+}
+
+/// A mapping from one color to another.
+///
+/// This is implemented for closures, and for the color mappings used by the
+/// adapters in this crate.
+pub trait ColorMap<C> {
+    /// The resulting color type.
+    type Output: PixelColor;
+
+    /// Map a color.
+    fn map_color(&mut self, color: C) -> Self::Output;
+}
+
+impl<C, O, F> ColorMap<C> for F
+where
+    O: PixelColor,
+    F: FnMut(C) -> O,
+{
+    type Output = O;
+
+    fn map_color(&mut self, color: C) -> O {
+        self(color)
+    }
+}
+
+/// Complement each channel.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Invert;
+
+impl<C: ColorChannels> ColorMap<C> for Invert {
+    type Output = C;
+
+    fn map_color(&mut self, color: C) -> C {
+        let channels = color.to_channels();
+        C::from_channels([0, 1, 2].map(|i| C::MAX[i] - channels[i]))
+    }
+}
+
+/// Invert all colors.
+///
+/// Binary colors are swapped, and each channel of grayscale and RGB colors is
+/// complemented.
+pub struct InvertColor<D> {
+    target: D,
+    map: Invert,
+}
+
+impl<D> InvertColor<D> {
+    /// Invert colors drawn to a display.
+    pub fn new(target: D) -> Self {
+        InvertColor {
+            target,
+            map: Invert,
+        }
+    }
+}
+
+impl_wrapper!(InvertColor<D>);
+impl_color_map!(InvertColor<D>, D::Color, where D::Color: ColorChannels);
+
+/// Map colors with a closure or other [`ColorMap`].
+///
+/// The logical display's color type `C` is the input to the mapping, which
+/// must produce the wrapped display's color type. This allows, for example, a
+/// display with one color type to be drawn to with another, or colors to be
+/// adjusted before they're drawn. The map is called once for
+/// [`fill_solid`](DrawTarget::fill_solid) and [`clear`](DrawTarget::clear),
+/// and for each color as it's streamed from
+/// [`fill_contiguous`](DrawTarget::fill_contiguous).
+pub struct MapColor<D, F, C> {
+    target: D,
+    map: F,
+    color: PhantomData<fn(C)>,
+}
+
+impl<D, F, C> MapColor<D, F, C> {
+    /// Map colors drawn to a display with `map`.
+    pub fn new(target: D, map: F) -> Self {
+        MapColor {
+            target,
+            map,
+            color: PhantomData,
+        }
+    }
+
+    /// Return a reference to the color map.
+    pub fn map(&self) -> &F {
+        &self.map
+    }
+
+    /// Return a mutable reference to the color map.
+    pub fn map_mut(&mut self) -> &mut F {
+        &mut self.map
+    }
+}
+
+impl_wrapper!(MapColor<D, F, C>);
+impl_color_map!(MapColor<D, F, C>, C, where C: PixelColor, F: ColorMap<C, Output = D::Color>);
+
+/// Swap the red and blue channels.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct SwapRb;
+
+impl<C: RgbColor + ColorChannels> ColorMap<C> for SwapRb {
+    type Output = C;
+
+    #[inline]
+    fn map_color(&mut self, color: C) -> C {
+        C::from_channels([color.b(), color.g(), color.r()])
+    }
+}
+
+/// Swap the red and blue channels of RGB colors.
+///
+/// This corrects the colors of panels which are wired as BGR when the driver
+/// assumes RGB, or vice versa.
+pub struct SwapRgb<D> {
+    target: D,
+    map: SwapRb,
+}
+
+impl<D> SwapRgb<D> {
+    /// Swap red and blue for colors drawn to a display.
+    pub fn new(target: D) -> Self {
+        SwapRgb {
+            target,
+            map: SwapRb,
+        }
+    }
+}
+
+impl_wrapper!(SwapRgb<D>);
+impl_color_map!(SwapRgb<D>, D::Color, where D::Color: RgbColor + ColorChannels);
+
+/// Look up each channel in a table.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Lut<'a> {
+    tables: [&'a [u8]; 3],
+}
+
+impl<'a> Lut<'a> {
+    fn new(tables: [&'a [u8]; 3]) -> Self {
+        assert!(
+            tables.iter().all(|table| !table.is_empty()),
+            "lookup tables must not be empty"
+        );
+        Lut { tables }
+    }
+
+    /// Look up a channel with maximum value `max`.
+    fn lookup(table: &[u8], value: u8, max: u8) -> u8 {
+        let (value, max) = (u32::from(value), u32::from(max));
+        let last = table.len() as u32 - 1;
+        let level = u32::from(table[((value * last + max / 2) / max) as usize]);
+
+        ((level * max + 127) / 255) as u8
+    }
+}
+
+impl<C: ColorChannels> ColorMap<C> for Lut<'_> {
+    type Output = C;
+
+    fn map_color(&mut self, color: C) -> C {
+        let channels = color.to_channels();
+        C::from_channels([0, 1, 2].map(|i| Self::lookup(self.tables[i], channels[i], C::MAX[i])))
+    }
+}
+
+/// Correct colors with lookup tables, such as gamma curves.
+///
+/// Each channel value is scaled to an index into the table, which may have any
+/// number of entries (typically 32, 64 or 256). Table entries are output levels
+/// from 0 to 255, which are scaled back to the channel's range.
+pub struct ColorLut<'a, D> {
+    target: D,
+    map: Lut<'a>,
+}
+
+impl<'a, D> ColorLut<'a, D> {
+    /// Correct all channels with the same table.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `table` is empty.
+    pub fn new(target: D, table: &'a [u8]) -> Self {
+        ColorLut {
+            target,
+            map: Lut::new([table; 3]),
+        }
+    }
+
+    /// Correct the red, green and blue channels with separate tables.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the tables are empty.
+    pub fn per_channel(target: D, tables: [&'a [u8]; 3]) -> Self {
+        ColorLut {
+            target,
+            map: Lut::new(tables),
+        }
+    }
+}
+
+impl_wrapper!(ColorLut<'a, D>);
+impl_color_map!(ColorLut<'a, D>, D::Color, where D::Color: ColorChannels);
+
+/// Scale each channel by contrast and brightness factors.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Scale {
+    brightness: u8,
+    contrast: u16,
+}
+
+impl Scale {
+    fn scale(&self, value: u8, max: u8) -> u8 {
+        let (value, max) = (i32::from(value), i32::from(max));
+
+        // Contrast is applied around the middle of the channel's range, working
+        // in doubled values to avoid rounding the midpoint.
+        let contrasted = ((2 * value - max) * i32::from(self.contrast) / 256 + max) / 2;
+        let contrasted = contrasted.clamp(0, max);
+
+        ((contrasted * i32::from(self.brightness) + 127) / 255) as u8
+    }
+}
+
+impl<C: ColorChannels> ColorMap<C> for Scale {
+    type Output = C;
+
+    fn map_color(&mut self, color: C) -> C {
+        let channels = color.to_channels();
+        C::from_channels([0, 1, 2].map(|i| self.scale(channels[i], C::MAX[i])))
+    }
+}
+
+/// Adjust brightness and contrast.
+///
+/// This allows a display without hardware dimming to be dimmed, for example
+/// for a night mode. Brightness ranges from 0 (black) to 255 (unchanged).
+/// Contrast is a factor in units of 1/256, so 256 leaves colors unchanged,
+/// lower values move them towards mid-gray and higher values away from it.
+/// Both can be changed at runtime, and affect subsequent drawing.
+pub struct Brightness<D> {
+    target: D,
+    map: Scale,
+}
+
+impl<D> Brightness<D> {
+    /// Scale colors drawn to a display by `brightness`.
+    pub fn new(target: D, brightness: u8) -> Self {
+        Brightness {
+            target,
+            map: Scale {
+                brightness,
+                contrast: 256,
+            },
+        }
+    }
+
+    /// Set the initial contrast.
+    pub fn with_contrast(mut self, contrast: u16) -> Self {
+        self.set_contrast(contrast);
+        self
+    }
+
+    /// Return the current brightness.
+    pub fn brightness(&self) -> u8 {
+        self.map.brightness
+    }
+
+    /// Set the brightness.
+    pub fn set_brightness(&mut self, brightness: u8) {
+        self.map.brightness = brightness;
+    }
+
+    /// Return the current contrast.
+    pub fn contrast(&self) -> u16 {
+        self.map.contrast
+    }
+
+    /// Set the contrast.
+    pub fn set_contrast(&mut self, contrast: u16) {
+        self.map.contrast = contrast;
+    }
+}
+
+impl_wrapper!(Brightness<D>);
+impl_color_map!(Brightness<D>, D::Color, where D::Color: ColorChannels);
+
+/// Per-channel gains for [`ColorTemperature`].
+///
+/// Each gain ranges from 0 (channel off) to 255 (unchanged).
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Tint {
+    /// The red, green and blue gains.
+    pub gains: [u8; 3],
+}
+
+impl Tint {
+    /// A tint which leaves colors unchanged.
+    pub const NEUTRAL: Tint = Tint::new(255, 255, 255);
+
+    /// A tint with the given red, green and blue gains.
+    pub const fn new(red: u8, green: u8, blue: u8) -> Self {
+        Tint {
+            gains: [red, green, blue],
+        }
+    }
+
+    /// A warmer tint, reducing blue and to a lesser extent green, by `amount`
+    /// from 0 (neutral) to 255 (a deep orange, like candlelight).
+    pub const fn warm(amount: u8) -> Self {
+        let amount = amount as u16;
+        Tint::new(255, (255 - amount / 2) as u8, (255 - amount * 7 / 8) as u8)
+    }
+
+    /// A cooler tint, reducing red and to a lesser extent green, by `amount`
+    /// from 0 (neutral) to 255.
+    pub const fn cool(amount: u8) -> Self {
+        let amount = amount as u16;
+        Tint::new((255 - amount / 2) as u8, (255 - amount / 4) as u8, 255)
+    }
+}
+
+impl Default for Tint {
+    fn default() -> Self {
+        Tint::NEUTRAL
+    }
+}
+
+impl<C: ColorChannels> ColorMap<C> for Tint {
+    type Output = C;
+
+    fn map_color(&mut self, color: C) -> C {
+        let channels = color.to_channels();
+        C::from_channels(
+            [0, 1, 2]
+                .map(|i| ((u16::from(channels[i]) * u16::from(self.gains[i]) + 127) / 255) as u8),
+        )
+    }
+}
+
+/// A [`Tint`] which can be switched off.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Temperature {
+    tint: Tint,
+    enabled: bool,
+}
+
+impl<C: ColorChannels> ColorMap<C> for Temperature {
+    type Output = C;
+
+    fn map_color(&mut self, color: C) -> C {
+        if self.enabled {
+            self.tint.map_color(color)
+        } else {
+            color
+        }
+    }
+}
+
+/// Tint all colors, for example for a warm night mode.
+///
+/// Each channel is scaled by the gains of a [`Tint`], such as
+/// [`Tint::warm`] to reduce blue light at night. The tint can be changed or
+/// switched off at runtime, and affects subsequent drawing, so the whole
+/// display changes when it's next redrawn. Grayscale and binary colors are
+/// tinted by their luminance.
+pub struct ColorTemperature<D> {
+    target: D,
+    map: Temperature,
+}
+
+impl<D> ColorTemperature<D> {
+    /// Tint colors drawn to a display.
+    pub fn new(target: D, tint: Tint) -> Self {
+        ColorTemperature {
+            target,
+            map: Temperature {
+                tint,
+                enabled: true,
+            },
+        }
+    }
+
+    /// Return the tint.
+    pub fn tint(&self) -> Tint {
+        self.map.tint
+    }
+
+    /// Set the tint.
+    pub fn set_tint(&mut self, tint: Tint) {
+        self.map.tint = tint;
+    }
+
+    /// Return whether the tint is applied.
+    pub fn is_enabled(&self) -> bool {
+        self.map.enabled
+    }
+
+    /// Apply the tint or not, leaving colors unchanged when it's disabled.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.map.enabled = enabled;
+    }
+}
+
+impl_wrapper!(ColorTemperature<D>);
+impl_color_map!(ColorTemperature<D>, D::Color, where D::Color: ColorChannels);
+
+/// Convert to binary by comparing luminance with a threshold.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Threshold {
+    threshold: u8,
+}
+
+impl<C: ColorChannels> ColorMap<C> for Threshold {
+    type Output = BinaryColor;
+
+    fn map_color(&mut self, color: C) -> BinaryColor {
+        BinaryColor::from(luminance(color) >= self.threshold)
+    }
+}
+
+/// Draw grayscale or RGB colors to a monochrome display.
+///
+/// Colors are on if their luminance, on a scale from 0 to 255, is at least the
+/// threshold, and off otherwise. This allows color assets and anti-aliased
+/// fonts to be used on 1-bit displays.
+pub struct ToBinary<D, C> {
+    target: D,
+    map: Threshold,
+    color: PhantomData<fn(C)>,
+}
+
+impl<D, C> ToBinary<D, C> {
+    /// Convert colors drawn to a monochrome display, turning on pixels with at
+    /// least `threshold` luminance.
+    pub fn new(target: D, threshold: u8) -> Self {
+        ToBinary {
+            target,
+            map: Threshold { threshold },
+            color: PhantomData,
+        }
+    }
+
+    /// Return the current threshold.
+    pub fn threshold(&self) -> u8 {
+        self.map.threshold
+    }
+
+    /// Set the threshold.
+    pub fn set_threshold(&mut self, threshold: u8) {
+        self.map.threshold = threshold;
+    }
+}
+
+impl_wrapper!(ToBinary<D, C>);
+impl_color_map!(ToBinary<D, C>, C, where C: ColorChannels, D: DrawTarget<Color = BinaryColor>);
+
+/// Convert to grayscale by luminance.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Luminance;
+
+impl Luminance {
+    fn map_color<C, O>(&mut self, color: C) -> O
+    where
+        C: ColorChannels,
+        O: GrayColor + ColorChannels,
+    {
+        let max = u32::from(O::MAX[0]);
+        let level = (u32::from(luminance(color)) * max + 127) / 255;
+        O::from_channels([level as u8; 3])
+    }
+}
+
+/// Draw RGB colors to a grayscale display.
+///
+/// Colors of type `C` are converted to the wrapped display's grayscale color
+/// type, such as [`Gray4`], by their luminance with the usual weighting of the
+/// red, green and blue channels.
+pub struct Grayscale<D, C> {
+    target: D,
+    map: Luminance,
+    color: PhantomData<fn(C)>,
+}
+
+impl<D, C> Grayscale<D, C> {
+    /// Convert colors drawn to a grayscale display.
+    pub fn new(target: D) -> Self {
+        Grayscale {
+            target,
+            map: Luminance,
+            color: PhantomData,
+        }
+    }
+}
+
+impl_wrapper!(Grayscale<D, C>);
+impl_color_map!(Grayscale<D, C>, C, where C: ColorChannels, D::Color: GrayColor + ColorChannels);
+
+/// How [`Quantize`] chooses a level for colors between two levels.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub enum Rounding {
+    /// Use the nearest level.
+    #[default]
+    Nearest,
+    /// Use the level below, by dropping the least significant bits, as a
+    /// display's own conversion often does.
+    Truncate,
+}
+
+/// Reduce colors to a number of bits per channel.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Quantizer {
+    bits: [u8; 3],
+    rounding: Rounding,
+}
+
+impl Quantizer {
+    fn map_color<C, O>(&mut self, color: C) -> O
+    where
+        C: ColorChannels,
+        O: ColorChannels,
+    {
+        let channels = color.to_channels();
+        O::from_channels([0, 1, 2].map(|i| {
+            let (in_max, out_max) = (u32::from(C::MAX[i]), u32::from(O::MAX[i]));
+            let levels = ((1u32 << self.bits[i].clamp(1, 8)) - 1).min(out_max);
+            let scaled = u32::from(channels[i]) * levels;
+            let level = match self.rounding {
+                Rounding::Nearest => (scaled + in_max / 2) / in_max,
+                Rounding::Truncate => scaled / in_max,
+            };
+            ((level * out_max + levels / 2) / levels) as u8
+        }))
+    }
+}
+
+/// Convert colors to a lower bit depth.
+///
+/// Colors of type `C` are converted to the wrapped display's color type, such
+/// as `Rgb888` to `Rgb565` or `Gray8` to `Gray4`, choosing the level of each
+/// channel according to the [`Rounding`]. The depth can also be limited
+/// further than the display's own, with [`with_bits`](Self::with_bits), so
+/// that one display can preview how drawing looks on another with fewer
+/// colors; for example `Rgb332` on an `Rgb888` display. To dither rather than
+/// round, put an [`OrderedDither`](crate::OrderedDither) or
+/// [`ErrorDiffusion`](crate::ErrorDiffusion) between this and the display,
+/// converting to the color type it quantizes to.
+pub struct Quantize<D, C> {
+    target: D,
+    map: Quantizer,
+    color: PhantomData<fn(C)>,
+}
+
+impl<D, C> Quantize<D, C> {
+    /// Convert colors drawn to a display to its depth with `rounding`.
+    pub fn new(target: D, rounding: Rounding) -> Self {
+        Quantize {
+            target,
+            map: Quantizer {
+                bits: [8; 3],
+                rounding,
+            },
+            color: PhantomData,
+        }
+    }
+
+    /// Limit the red, green and blue channels to `bits` bits each, from 1 to 8.
+    ///
+    /// Channels which the display has fewer bits for are limited to those.
+    pub fn with_bits(mut self, bits: [u8; 3]) -> Self {
+        self.set_bits(bits);
+        self
+    }
+
+    /// Return the limit on the bits of each channel.
+    pub fn bits(&self) -> [u8; 3] {
+        self.map.bits
+    }
+
+    /// Change the limit on the bits of each channel.
+    pub fn set_bits(&mut self, bits: [u8; 3]) {
+        self.map.bits = bits;
+    }
+
+    /// Return the rounding.
+    pub fn rounding(&self) -> Rounding {
+        self.map.rounding
+    }
+
+    /// Change the rounding.
+    pub fn set_rounding(&mut self, rounding: Rounding) {
+        self.map.rounding = rounding;
+    }
+}
+
+impl_wrapper!(Quantize<D, C>);
+impl_color_map!(Quantize<D, C>, C, where C: ColorChannels, D::Color: ColorChannels);
+
+/// Treat one color as transparent.
+///
+/// Pixels of the key color are dropped, so a rectangular image with a
+/// background of that color can be drawn over existing content. Contiguous
+/// fills are forwarded as runs of non-transparent pixels within each row,
+/// each of up to 32 pixels, so they keep the wrapped display's fast path.
+pub struct ChromaKey<D, C> {
+    target: D,
+    key: C,
+}
+
+impl<D: DrawTarget> ChromaKey<D, D::Color> {
+    /// Drop pixels of color `key` drawn to a display.
+    pub fn new(target: D, key: D::Color) -> Self {
+        ChromaKey { target, key }
+    }
+
+    /// Return the transparent color.
+    pub fn key(&self) -> D::Color {
+        self.key
+    }
+
+    /// Set the transparent color.
+    pub fn set_key(&mut self, key: D::Color) {
+        self.key = key;
+    }
+
+    /// Forward a run of colors in a row.
+    fn flush(&mut self, start: Point, run: &[D::Color]) -> Result<(), D::Error> {
+        if run.is_empty() {
+            return Ok(());
+        }
+        let area = Rectangle::new(start, Size::new(run.len() as u32, 1));
+        self.target.fill_contiguous(&area, run.iter().copied())
+    }
+}
+
+impl_wrapper!(ChromaKey<D, C>);
+
+impl<D: Dimensions, C> Dimensions for ChromaKey<D, C> {
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+impl<D: DrawTarget> DrawTarget for ChromaKey<D, D::Color> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let key = self.key;
+        self.target
+            .draw_iter(pixels.into_iter().filter(|Pixel(_, col)| *col != key))
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let key = self.key;
+        let mut run = [key; KEY_RUN];
+        let mut len = 0;
+        let mut start = area.top_left;
+
+        for (loc, color) in util::points(area).zip(colors) {
+            // End the current run at a transparent pixel, the end of a row, or
+            // when the buffer is full.
+            if len > 0 && (color == key || loc.y != start.y || len == KEY_RUN) {
+                self.flush(start, &run[..len])?;
+                len = 0;
+            }
+            if color != key {
+                if len == 0 {
+                    start = loc;
+                }
+                run[len] = color;
+                len += 1;
+            }
+        }
+        self.flush(start, &run[..len])
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        if color == self.key {
+            Ok(())
+        } else {
+            self.target.fill_solid(area, color)
+        }
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        if color == self.key {
+            Ok(())
+        } else {
+            self.target.clear(color)
+        }
+    }
+}
+
+/// Invert colors drawn inside highlighted areas.
+///
+/// Up to `N` rectangles can be highlighted at a time, and can be changed at
+/// runtime. Colors drawn inside any of them are inverted as by
+/// [`InvertColor`], so, for example, the selected row of a list is drawn
+/// inverted without the list having its own inverted drawing mode. Solid fills
+/// are split into rectangles which are entirely inside or outside the
+/// highlights, so a fill across a highlighted row is only a few fills.
+///
+/// Like [`Lockout`](crate::Lockout), the highlighted areas are in this
+/// adapter's coordinates, so if it's outside a rotation they're logical.
+pub struct Highlight<D, const N: usize> {
+    target: D,
+    areas: [Option<Rectangle>; N],
+}
+
+impl<D, const N: usize> Highlight<D, N> {
+    /// Wrap a display, initially with nothing highlighted.
+    pub fn new(target: D) -> Self {
+        Highlight {
+            target,
+            areas: [None; N],
+        }
+    }
+
+    /// Highlight `area` in slot `index`, replacing any area already there.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than `N`.
+    pub fn highlight(&mut self, index: usize, area: Rectangle) {
+        self.areas[index] = Some(area);
+    }
+
+    /// Remove the highlighted area in slot `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than `N`.
+    pub fn unhighlight(&mut self, index: usize) {
+        self.areas[index] = None;
+    }
+
+    /// Return the highlighted areas.
+    pub fn areas(&self) -> &[Option<Rectangle>; N] {
+        &self.areas
+    }
+
+    fn is_highlighted(&self, p: Point) -> bool {
+        self.areas.iter().flatten().any(|area| area.contains(p))
+    }
+
+    fn overlaps(&self, area: &Rectangle) -> bool {
+        self.areas
+            .iter()
+            .flatten()
+            .any(|highlight| !util::is_empty(&highlight.intersection(area)))
+    }
+
+    /// Call `f` with rectangles covering `area`, each either entirely inside
+    /// the highlights or entirely outside them, and which it is.
+    fn split<E>(
+        &mut self,
+        area: &Rectangle,
+        mut f: impl FnMut(&mut D, &Rectangle, bool) -> Result<(), E>,
+    ) -> Result<(), E> {
+        // The first edge after `pos`, or `end`.
+        fn next_edge(pos: i32, end: i32, edges: impl Iterator<Item = i32>) -> i32 {
+            edges.filter(|&e| e > pos).fold(end, i32::min)
+        }
+
+        let (rows, columns) = (area.rows(), area.columns());
+        let mut y = rows.start;
+        while y < rows.end {
+            // Rows between horizontal edges of highlights all look the same.
+            let band_end = next_edge(
+                y,
+                rows.end,
+                self.areas
+                    .iter()
+                    .flatten()
+                    .flat_map(|a| [a.rows().start, a.rows().end]),
+            );
+            let mut x = columns.start;
+            while x < columns.end {
+                let start = x;
+                let inside = self.is_highlighted(Point::new(x, y));
+                while x < columns.end && self.is_highlighted(Point::new(x, y)) == inside {
+                    x = next_edge(
+                        x,
+                        columns.end,
+                        self.areas
+                            .iter()
+                            .flatten()
+                            .filter(|a| a.rows().contains(&y))
+                            .flat_map(|a| [a.columns().start, a.columns().end]),
+                    );
+                }
+                let part = Rectangle::new(
+                    Point::new(start, y),
+                    Size::new((x - start) as u32, (band_end - y) as u32),
+                );
+                f(&mut self.target, &part, inside)?;
+            }
+            y = band_end;
+        }
+        Ok(())
+    }
+}
+
+impl_wrapper!(Highlight<D; const N: usize>);
+
+impl<D: Dimensions, const N: usize> Dimensions for Highlight<D, N> {
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+impl<D: DrawTarget, const N: usize> DrawTarget for Highlight<D, N>
+where
+    D::Color: ColorChannels,
+{
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let Highlight { target, areas } = self;
+        target.draw_iter(pixels.into_iter().map(|Pixel(loc, col)| {
+            if areas.iter().flatten().any(|area| area.contains(loc)) {
+                Pixel(loc, Invert.map_color(col))
+            } else {
+                Pixel(loc, col)
+            }
+        }))
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        if !self.overlaps(area) {
+            return self.target.fill_contiguous(area, colors);
+        }
+
+        let Highlight { target, areas } = self;
+        target.fill_contiguous(
+            area,
+            util::points(area).zip(colors).map(|(loc, col)| {
+                if areas.iter().flatten().any(|area| area.contains(loc)) {
+                    Invert.map_color(col)
+                } else {
+                    col
+                }
+            }),
+        )
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        if !self.overlaps(area) {
+            return self.target.fill_solid(area, color);
+        }
+
+        let area = area.intersection(&self.bounding_box());
+        let inverted = Invert.map_color(color);
+        self.split(&area, |target, part, inside| {
+            target.fill_solid(part, if inside { inverted } else { color })
+        })
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        if self.areas.iter().all(Option::is_none) {
+            self.target.clear(color)
+        } else {
+            self.fill_solid(&self.bounding_box(), color)
+        }
+    }
+}
+
+/// A color which is an index into a [`Palette`].
+///
+/// The raw type `R` sets the number of bits per pixel, such as `RawU4` for a
+/// 16 entry palette, so that images stored as `ImageRaw<PaletteIndex<RawU4>>`
+/// take 4 bits per pixel.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Default)]
+pub struct PaletteIndex<R = RawU8>(R);
+
+impl<R: RawData<Storage = u8>> PaletteIndex<R> {
+    /// The color with palette index `index`, truncated to the raw type's bits.
+    pub fn new(index: u8) -> Self {
+        PaletteIndex(R::from_u32(index.into()))
+    }
+
+    /// Return the palette index.
+    pub fn index(self) -> u8 {
+        self.0.into_inner()
+    }
+}
+
+impl<R: RawData + Copy + PartialEq> PixelColor for PaletteIndex<R> {
+    type Raw = R;
+}
+
+impl<R> From<R> for PaletteIndex<R> {
+    fn from(raw: R) -> Self {
+        PaletteIndex(raw)
+    }
+}
+
+/// Draw with indexed colors, looked up in a palette.
+///
+/// The logical display's colors are [`PaletteIndex`]es into a palette of `N`
+/// of the wrapped display's colors, with indices past the end wrapping around.
+/// The palette can be changed at any time, for example to switch between
+/// themes, without changing anything which is drawn; already drawn pixels
+/// keep their old colors until they're redrawn.
+///
+/// The index type's raw type `R` is usually inferred from what's drawn.
+pub struct Palette<D, C, R, const N: usize> {
+    target: D,
+    colors: [C; N],
+    _raw: PhantomData<R>,
+}
+
+impl<D, C, R, const N: usize> Palette<D, C, R, N> {
+    /// Draw to `target` with the colors in `palette`.
+    pub fn new(target: D, palette: [C; N]) -> Self {
+        Palette {
+            target,
+            colors: palette,
+            _raw: PhantomData,
+        }
+    }
+
+    /// Return the palette.
+    pub fn palette(&self) -> &[C; N] {
+        &self.colors
+    }
+
+    /// Return the palette, to change individual entries.
+    pub fn palette_mut(&mut self) -> &mut [C; N] {
+        &mut self.colors
+    }
+
+    /// Replace the palette, returning the old one.
+    pub fn set_palette(&mut self, palette: [C; N]) -> [C; N] {
+        core::mem::replace(&mut self.colors, palette)
+    }
+}
+
+/// Look up a color in a palette, wrapping around past the end.
+fn lookup<C: Copy, R: RawData<Storage = u8>, const N: usize>(
+    palette: &[C; N],
+    index: PaletteIndex<R>,
+) -> C {
+    palette[usize::from(index.index()) % N]
+}
+
+impl_wrapper!(Palette<D, C, R; const N: usize>);
+
+impl<D: Dimensions, C, R, const N: usize> Dimensions for Palette<D, C, R, N> {
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+impl<D: DrawTarget, R, const N: usize> DrawTarget for Palette<D, D::Color, R, N>
+where
+    R: RawData<Storage = u8> + Copy + PartialEq,
+{
+    type Color = PaletteIndex<R>;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let palette = &self.colors;
+        self.target.draw_iter(
+            pixels
+                .into_iter()
+                .map(|Pixel(loc, index)| Pixel(loc, lookup(palette, index))),
+        )
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let palette = &self.colors;
+        self.target
+            .fill_contiguous(area, colors.into_iter().map(|index| lookup(palette, index)))
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let color = lookup(&self.colors, color);
+        self.target.fill_solid(area, color)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        let color = lookup(&self.colors, color);
+        self.target.clear(color)
+    }
+}
@@ -0,0 +1,431 @@
+use super::*;
+use crate::{tests::rect, FlipX, Rotate90};
+use embedded_graphics::mock_display::MockDisplay;
+
+#[test]
+fn channels() {
+    assert_eq!(Rgb565::new(1, 2, 3).to_channels(), [1, 2, 3]);
+    assert_eq!(Bgr888::from_channels([1, 2, 3]), Bgr888::new(1, 2, 3));
+    assert_eq!(Gray4::new(7).to_channels(), [7, 7, 7]);
+    assert_eq!(Gray8::from_channels([255, 0, 0]), Gray8::new(76));
+    assert_eq!(BinaryColor::from_channels([1, 1, 0]), BinaryColor::On);
+    assert_eq!(BinaryColor::from_channels([1, 0, 0]), BinaryColor::Off);
+}
+
+#[test]
+fn invert() {
+    let mut map = Invert;
+    assert_eq!(map.map_color(BinaryColor::On), BinaryColor::Off);
+    assert_eq!(map.map_color(Gray2::new(1)), Gray2::new(2));
+    assert_eq!(map.map_color(Rgb565::new(1, 2, 3)), Rgb565::new(30, 61, 28));
+}
+
+#[test]
+fn invert_color() {
+    let mut disp = Rotate90::new(InvertColor::new(MockDisplay::<BinaryColor>::new()));
+
+    disp.draw_iter([Pixel(Point::new(0, 0), BinaryColor::Off)])
+        .unwrap();
+    disp.fill_contiguous(&rect(0, 1, 2, 1), [BinaryColor::On, BinaryColor::Off])
+        .unwrap();
+    disp.as_ref().as_ref().assert_pattern(&[
+        "                                                              .#",
+        "                                                              # ",
+    ]);
+
+    let mut disp = InvertColor::new(MockDisplay::<BinaryColor>::new());
+    disp.clear(BinaryColor::Off).unwrap();
+    assert_eq!(
+        disp.as_ref().get_pixel(Point::new(63, 63)),
+        Some(BinaryColor::On)
+    );
+}
+
+#[test]
+fn map_color() {
+    let mut disp = FlipX::new(MapColor::new(
+        MockDisplay::<BinaryColor>::new(),
+        |c: Gray8| BinaryColor::from(c.luma() >= 0x80),
+    ));
+    assert_eq!(disp.bounding_box(), rect(0, 0, 64, 64));
+
+    disp.fill_contiguous(
+        &rect(0, 0, 3, 1),
+        [Gray8::new(0x10), Gray8::new(0x80), Gray8::new(0xff)],
+    )
+    .unwrap();
+    disp.draw_iter([Pixel(Point::new(0, 1), Gray8::new(0x7f))])
+        .unwrap();
+    disp.as_ref().as_ref().assert_pattern(&[
+        "                                                             ##.",
+        "                                                               .",
+    ]);
+}
+
+#[test]
+fn map_color_stateful() {
+    let mut calls = 0;
+    let mut disp = MapColor::new(MockDisplay::<Rgb565>::new(), |c: Rgb888| {
+        calls += 1;
+        c.into()
+    });
+
+    // Solid fills are only mapped once
+    disp.clear(Rgb888::RED).unwrap();
+    disp.as_mut().set_allow_overdraw(true);
+    disp.fill_solid(&rect(0, 0, 10, 10), Rgb888::GREEN).unwrap();
+    disp.draw_iter([Pixel(Point::zero(), Rgb888::BLUE)])
+        .unwrap();
+    assert_eq!(disp.as_ref().get_pixel(Point::zero()), Some(Rgb565::BLUE));
+    assert_eq!(
+        disp.as_ref().get_pixel(Point::new(1, 1)),
+        Some(Rgb565::GREEN)
+    );
+    assert_eq!(
+        disp.as_ref().get_pixel(Point::new(63, 63)),
+        Some(Rgb565::RED)
+    );
+
+    assert_eq!(calls, 3);
+}
+
+#[test]
+fn swap_rgb() {
+    let mut disp = SwapRgb::new(MockDisplay::<Rgb565>::new());
+
+    disp.fill_contiguous(&rect(0, 0, 2, 1), [Rgb565::RED, Rgb565::new(1, 2, 3)])
+        .unwrap();
+    disp.fill_solid(&rect(0, 1, 1, 1), Rgb565::CYAN).unwrap();
+    assert_eq!(
+        disp.as_ref().get_pixel(Point::new(0, 0)),
+        Some(Rgb565::BLUE)
+    );
+    assert_eq!(
+        disp.as_ref().get_pixel(Point::new(1, 0)),
+        Some(Rgb565::new(3, 2, 1))
+    );
+    assert_eq!(
+        disp.as_ref().get_pixel(Point::new(0, 1)),
+        Some(Rgb565::YELLOW)
+    );
+}
+
+#[test]
+fn color_lut() {
+    // Squaring curve
+    let table: [u8; 16] = core::array::from_fn(|i| ((i * i * 255) / 225) as u8);
+    let mut map = Lut::new([&table; 3]);
+    assert_eq!(map.map_color(Gray8::new(0)), Gray8::new(0));
+    assert_eq!(map.map_color(Gray8::new(255)), Gray8::new(255));
+    assert_eq!(map.map_color(Gray8::new(128)), Gray8::new(72));
+    assert_eq!(
+        map.map_color(Rgb565::new(31, 32, 0)),
+        Rgb565::new(31, 18, 0)
+    );
+
+    let mut disp = ColorLut::per_channel(
+        MockDisplay::<Rgb888>::new(),
+        [&[255, 0], &[0, 128, 255], &[0]],
+    );
+    disp.fill_solid(&rect(0, 0, 1, 1), Rgb888::new(0, 100, 200))
+        .unwrap();
+    assert_eq!(
+        disp.as_ref().get_pixel(Point::zero()),
+        Some(Rgb888::new(255, 128, 0))
+    );
+}
+
+#[test]
+fn brightness() {
+    let mut disp = Brightness::new(MockDisplay::<Rgb888>::new(), 128);
+    assert_eq!(disp.brightness(), 128);
+
+    disp.fill_solid(&rect(0, 0, 1, 1), Rgb888::new(255, 100, 0))
+        .unwrap();
+    disp.set_brightness(255);
+    disp.set_contrast(512);
+    disp.fill_solid(&rect(1, 0, 1, 1), Rgb888::new(255, 100, 160))
+        .unwrap();
+    disp.set_contrast(0);
+    disp.fill_solid(&rect(2, 0, 1, 1), Rgb888::new(255, 100, 0))
+        .unwrap();
+
+    let mock = disp.as_ref();
+    assert_eq!(
+        mock.get_pixel(Point::new(0, 0)),
+        Some(Rgb888::new(128, 50, 0))
+    );
+    assert_eq!(
+        mock.get_pixel(Point::new(1, 0)),
+        Some(Rgb888::new(255, 72, 192))
+    );
+    assert_eq!(
+        mock.get_pixel(Point::new(2, 0)),
+        Some(Rgb888::new(127, 127, 127))
+    );
+
+    // Binary colors are only on or off
+    let mut map = Scale {
+        brightness: 255,
+        contrast: 256,
+    };
+    assert_eq!(map.map_color(BinaryColor::On), BinaryColor::On);
+    map.brightness = 100;
+    assert_eq!(map.map_color(BinaryColor::On), BinaryColor::Off);
+}
+
+#[test]
+fn to_binary() {
+    assert_eq!(luminance(Rgb565::WHITE), 255);
+    assert_eq!(luminance(Gray2::new(1)), 85);
+    assert_eq!(luminance(Rgb888::GREEN), 150);
+
+    let mut disp = ToBinary::new(MockDisplay::new(), 128);
+    disp.fill_contiguous(
+        &rect(0, 0, 4, 1),
+        [
+            Rgb565::GREEN,
+            Rgb565::RED,
+            Rgb565::WHITE,
+            Rgb565::new(16, 32, 16),
+        ],
+    )
+    .unwrap();
+    disp.set_threshold(160);
+    assert_eq!(disp.threshold(), 160);
+    disp.draw_iter([Pixel(Point::new(0, 1), Rgb565::GREEN)])
+        .unwrap();
+
+    disp.as_ref().assert_pattern(&[
+        "#.##", //
+        ".   ", //
+    ]);
+}
+
+#[test]
+fn grayscale() {
+    let mut disp = Grayscale::new(MockDisplay::<Gray4>::new());
+
+    disp.fill_contiguous(
+        &rect(0, 0, 4, 1),
+        [Rgb888::WHITE, Rgb888::RED, Rgb888::GREEN, Rgb888::BLUE],
+    )
+    .unwrap();
+    disp.fill_solid(&rect(0, 1, 1, 1), Rgb888::new(128, 128, 128))
+        .unwrap();
+
+    let mock = disp.as_ref();
+    let luma = |x, y| mock.get_pixel(Point::new(x, y)).map(|c| c.luma());
+    assert_eq!(
+        [luma(0, 0), luma(1, 0), luma(2, 0), luma(3, 0), luma(0, 1)],
+        [Some(15), Some(4), Some(9), Some(2), Some(8)]
+    );
+}
+
+#[test]
+fn chroma_key() {
+    use BinaryColor::{Off, On};
+
+    let mut disp = ChromaKey::new(MockDisplay::<Rgb565>::new(), Rgb565::MAGENTA);
+    assert_eq!(disp.key(), Rgb565::MAGENTA);
+    let (k, r, g) = (Rgb565::MAGENTA, Rgb565::RED, Rgb565::GREEN);
+
+    disp.fill_contiguous(&rect(0, 0, 4, 3), [k, r, r, k, g, g, g, g, k, k, k, r])
+        .unwrap();
+    disp.fill_solid(&rect(10, 0, 2, 2), k).unwrap();
+    disp.clear(k).unwrap();
+    disp.draw_iter([Pixel(Point::new(5, 5), k)]).unwrap();
+
+    let mock = disp.into_inner();
+    let pattern = mock.map(|c| if c == r || c == g { On } else { Off });
+    pattern.assert_pattern(&[
+        " ## ", //
+        "####", //
+        "   #", //
+    ]);
+}
+
+#[test]
+fn chroma_key_runs() {
+    let mut disp = ChromaKey::new(MockDisplay::<BinaryColor>::new(), BinaryColor::Off);
+
+    // Runs longer than the buffer are split
+    disp.fill_contiguous(&rect(0, 0, 40, 2), [BinaryColor::On; 80])
+        .unwrap();
+    assert_eq!(disp.as_ref().affected_area(), rect(0, 0, 40, 2));
+}
+
+#[test]
+fn highlight() {
+    use BinaryColor::{Off, On};
+
+    let mut disp = Highlight::<_, 2>::new(MockDisplay::new());
+    disp.highlight(0, rect(1, 1, 3, 2));
+    disp.highlight(1, rect(3, 2, 2, 2));
+    disp.fill_solid(&rect(0, 0, 6, 5), Off).unwrap();
+    disp.assert_pattern(&[
+        "......", //
+        ".###..", //
+        ".####.", //
+        "...##.", //
+        "......", //
+    ]);
+
+    disp.as_mut().set_allow_overdraw(true);
+    disp.unhighlight(1);
+    disp.draw_iter([Pixel(Point::new(0, 0), On), Pixel(Point::new(2, 1), On)])
+        .unwrap();
+    disp.fill_contiguous(&rect(3, 3, 2, 1), [On, Off]).unwrap();
+    disp.fill_contiguous(&rect(2, 2, 2, 1), [On, Off]).unwrap();
+    disp.assert_pattern(&[
+        "#.....", //
+        ".#.#..", //
+        ".#.##.", //
+        "...#..", //
+        "......", //
+    ]);
+}
+
+#[test]
+fn highlight_fills() {
+    // A highlighted list row splits a full screen fill into three.
+    let mut disp = Highlight::<_, 1>::new(crate::Instrument::new(MockDisplay::new()));
+    disp.highlight(0, rect(-5, 10, 100, 8));
+    disp.clear(BinaryColor::Off).unwrap();
+    assert_eq!(disp.stats().fills, 3);
+
+    let mut expected = MockDisplay::new();
+    expected.set_allow_overdraw(true);
+    expected.clear(BinaryColor::Off).unwrap();
+    expected
+        .fill_solid(&rect(0, 10, 64, 8), BinaryColor::On)
+        .unwrap();
+    disp.into_inner().into_inner().assert_eq(&expected);
+
+    // Highlights are in the adapter's coordinates.
+    let mut disp = Highlight::<_, 1>::new(Rotate90::new(MockDisplay::new()));
+    disp.highlight(0, rect(0, 0, 2, 1));
+    disp.fill_solid(&rect(0, 0, 3, 1), BinaryColor::Off)
+        .unwrap();
+    let mut expected = MockDisplay::new();
+    expected
+        .fill_solid(&rect(63, 0, 1, 2), BinaryColor::On)
+        .unwrap();
+    expected
+        .fill_solid(&rect(63, 2, 1, 1), BinaryColor::Off)
+        .unwrap();
+    disp.into_inner().into_inner().assert_eq(&expected);
+}
+
+#[test]
+fn palette() {
+    use embedded_graphics::{
+        image::{Image, ImageRaw},
+        pixelcolor::raw::{RawU2, RawU4},
+        Drawable,
+    };
+
+    let index = PaletteIndex::<RawU2>::new;
+    let mut disp = Palette::new(
+        MockDisplay::new(),
+        [Rgb565::BLACK, Rgb565::RED, Rgb565::GREEN, Rgb565::BLUE],
+    );
+    disp.fill_solid(&rect(0, 0, 2, 1), index(1)).unwrap();
+    disp.fill_contiguous(&rect(0, 1, 4, 1), [0, 1, 2, 3].map(index))
+        .unwrap();
+    disp.draw_iter([Pixel(Point::new(3, 0), index(6))]).unwrap();
+
+    // A theme change only affects what's drawn afterwards.
+    disp.palette_mut()[1] = Rgb565::WHITE;
+    disp.draw_iter([Pixel(Point::new(2, 0), index(1))]).unwrap();
+    disp.assert_pattern(&[
+        "RRWG", //
+        "KRGB", //
+    ]);
+
+    // 4 bit images
+    let data = [0x01, 0x23, 0x45, 0x67];
+    let raw = ImageRaw::<PaletteIndex<RawU4>>::new(&data, 4);
+    let mut disp = Palette::new(MockDisplay::new(), [BinaryColor::Off, BinaryColor::On]);
+    Image::new(&raw, Point::zero()).draw(&mut disp).unwrap();
+    let old = disp.set_palette([BinaryColor::On, BinaryColor::Off]);
+    assert_eq!(old, [BinaryColor::Off, BinaryColor::On]);
+    assert_eq!(disp.palette(), &[BinaryColor::On, BinaryColor::Off]);
+    disp.assert_pattern(&[
+        ".#.#", //
+        ".#.#", //
+    ]);
+}
+
+#[test]
+fn color_temperature() {
+    assert_eq!(Tint::warm(0), Tint::NEUTRAL);
+    assert_eq!(Tint::cool(0), Tint::NEUTRAL);
+    assert_eq!(Tint::warm(255), Tint::new(255, 128, 32));
+    assert_eq!(Tint::cool(255), Tint::new(128, 192, 255));
+
+    let mut disp = ColorTemperature::new(MockDisplay::new(), Tint::new(255, 128, 0));
+    disp.fill_solid(&rect(0, 0, 1, 1), Rgb888::WHITE).unwrap();
+    disp.set_tint(Tint::new(0, 255, 255));
+    disp.fill_contiguous(&rect(1, 0, 2, 1), [Rgb888::new(200, 100, 50); 2])
+        .unwrap();
+    disp.set_enabled(false);
+    assert!(!disp.is_enabled());
+    disp.draw_iter([Pixel(Point::new(3, 0), Rgb888::new(1, 2, 3))])
+        .unwrap();
+
+    assert_eq!(
+        disp.get_pixel(Point::new(0, 0)),
+        Some(Rgb888::new(255, 128, 0))
+    );
+    assert_eq!(
+        disp.get_pixel(Point::new(2, 0)),
+        Some(Rgb888::new(0, 100, 50))
+    );
+    assert_eq!(disp.get_pixel(Point::new(3, 0)), Some(Rgb888::new(1, 2, 3)));
+    assert_eq!(disp.tint(), Tint::new(0, 255, 255));
+}
+
+#[test]
+fn quantize() {
+    let mut disp = Quantize::<_, Rgb888>::new(MockDisplay::<Rgb565>::new(), Rounding::Nearest);
+    disp.draw_iter([Pixel(Point::new(0, 0), Rgb888::new(255, 130, 6))])
+        .unwrap();
+    disp.set_rounding(Rounding::Truncate);
+    assert_eq!(disp.rounding(), Rounding::Truncate);
+    disp.draw_iter([Pixel(Point::new(1, 0), Rgb888::new(255, 130, 6))])
+        .unwrap();
+    assert_eq!(
+        disp.get_pixel(Point::new(0, 0)),
+        Some(Rgb565::new(31, 32, 1))
+    );
+    assert_eq!(
+        disp.get_pixel(Point::new(1, 0)),
+        Some(Rgb565::new(31, 32, 0))
+    );
+
+    // Rgb332 previewed on an Rgb888 display
+    let mut disp = Quantize::<_, Rgb888>::new(MockDisplay::<Rgb888>::new(), Rounding::Nearest)
+        .with_bits([3, 3, 2]);
+    assert_eq!(disp.bits(), [3, 3, 2]);
+    disp.fill_solid(&rect(0, 0, 1, 1), Rgb888::new(255, 100, 100))
+        .unwrap();
+    disp.fill_contiguous(&rect(1, 0, 1, 1), [Rgb888::new(20, 0, 42)])
+        .unwrap();
+    assert_eq!(
+        disp.get_pixel(Point::new(0, 0)),
+        Some(Rgb888::new(255, 109, 85))
+    );
+    assert_eq!(
+        disp.get_pixel(Point::new(1, 0)),
+        Some(Rgb888::new(36, 0, 0))
+    );
+
+    // Gray8 to Gray2, with the bits limited to 1 as well
+    let mut disp = Quantize::<_, Gray8>::new(MockDisplay::<Gray2>::new(), Rounding::Nearest);
+    disp.fill_solid(&rect(0, 0, 1, 1), Gray8::new(100)).unwrap();
+    disp.set_bits([1; 3]);
+    disp.fill_solid(&rect(1, 0, 1, 1), Gray8::new(100)).unwrap();
+    disp.fill_solid(&rect(2, 0, 1, 1), Gray8::new(200)).unwrap();
+    disp.assert_pattern(&["103"]);
+}
@@ -0,0 +1,113 @@
+//! Simulate color vision deficiencies, to check a UI's accessibility.
+
+use crate::{ColorChannels, ColorMap};
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+
+#[cfg(test)]
+mod tests;
+
+/// A type of color blindness, for [`ColorBlind`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Deficiency {
+    /// No red cones, so reds appear dark and are confused with greens.
+    Protanopia,
+    /// No green cones, so reds and greens are confused.
+    Deuteranopia,
+    /// No blue cones, so blues and greens, and yellows and pinks, are
+    /// confused.
+    Tritanopia,
+}
+
+impl Deficiency {
+    /// The simulation matrix in linear RGB, from Machado, Oliveira and
+    /// Fernandes (2009), at full severity.
+    #[rustfmt::skip]
+    fn matrix(self) -> [[f32; 3]; 3] {
+        match self {
+            Deficiency::Protanopia => [
+                [ 0.152286,  1.052583, -0.204868],
+                [ 0.114503,  0.786281,  0.099216],
+                [-0.003882, -0.048116,  1.051998],
+            ],
+            Deficiency::Deuteranopia => [
+                [ 0.367322,  0.860646, -0.227968],
+                [ 0.280085,  0.672501,  0.047413],
+                [-0.011820,  0.042940,  0.968881],
+            ],
+            Deficiency::Tritanopia => [
+                [ 1.255528, -0.076749, -0.178779],
+                [-0.078411,  0.930809,  0.147602],
+                [ 0.004733,  0.691367,  0.303900],
+            ],
+        }
+    }
+}
+
+/// Convert an sRGB channel from 0 to 1 to linear light.
+fn to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Convert a channel in linear light to sRGB, both from 0 to 1.
+fn from_linear(value: f32) -> f32 {
+    if value <= 0.003_130_8 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+impl<C: ColorChannels> ColorMap<C> for Deficiency {
+    type Output = C;
+
+    fn map_color(&mut self, color: C) -> C {
+        let channels = color.to_channels();
+        let linear = [0, 1, 2].map(|i| to_linear(f32::from(channels[i]) / f32::from(C::MAX[i])));
+        let matrix = self.matrix();
+
+        C::from_channels([0, 1, 2].map(|i| {
+            let value = (0..3).map(|j| matrix[i][j] * linear[j]).sum::<f32>();
+            let max = f32::from(C::MAX[i]);
+            (from_linear(value.clamp(0.0, 1.0)) * max).round() as u8
+        }))
+    }
+}
+
+/// Show colors as they appear with a color vision deficiency.
+///
+/// This is a development aid, typically wrapping a simulator display, to check
+/// that a UI can still be used by people with color blindness: for example,
+/// that red and green status indicators differ by more than their hue. The
+/// [`Deficiency`] can be changed at runtime, and affects subsequent drawing.
+/// Grayscale and binary colors are unchanged.
+pub struct ColorBlind<D> {
+    target: D,
+    map: Deficiency,
+}
+
+impl<D> ColorBlind<D> {
+    /// Simulate `deficiency` on a display.
+    pub fn new(target: D, deficiency: Deficiency) -> Self {
+        ColorBlind {
+            target,
+            map: deficiency,
+        }
+    }
+
+    /// Return the simulated deficiency.
+    pub fn deficiency(&self) -> Deficiency {
+        self.map
+    }
+
+    /// Change the simulated deficiency.
+    pub fn set_deficiency(&mut self, deficiency: Deficiency) {
+        self.map = deficiency;
+    }
+}
+
+impl_wrapper!(ColorBlind<D>);
+impl_color_map!(ColorBlind<D>, D::Color, where D::Color: ColorChannels);
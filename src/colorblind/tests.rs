@@ -0,0 +1,61 @@
+use super::*;
+use embedded_graphics::{
+    mock_display::MockDisplay,
+    pixelcolor::{BinaryColor, Rgb565, Rgb888},
+};
+
+#[test]
+fn simulate() {
+    let mut protan = Deficiency::Protanopia;
+    let mut deutan = Deficiency::Deuteranopia;
+    let mut tritan = Deficiency::Tritanopia;
+
+    // Black, white and grays are the same for everyone.
+    for color in [Rgb888::BLACK, Rgb888::WHITE, Rgb888::new(128, 128, 128)] {
+        for map in [&mut protan, &mut deutan, &mut tritan] {
+            let out: Rgb888 = map.map_color(color);
+            for (a, b) in out.to_channels().iter().zip(color.to_channels()) {
+                assert!(a.abs_diff(b) <= 1, "{color:?} became {out:?}");
+            }
+        }
+    }
+
+    // Without red or green cones, red and green are both shades of yellow,
+    // but they're still different hues without blue cones.
+    let yellowish = |color: Rgb888| color.r() >= color.g() && color.g() > color.b();
+    for map in [&mut protan, &mut deutan] {
+        assert!(yellowish(map.map_color(Rgb888::RED)));
+        assert!(yellowish(map.map_color(Rgb888::GREEN)));
+    }
+    let (red, green): (Rgb888, Rgb888) = (
+        tritan.map_color(Rgb888::RED),
+        tritan.map_color(Rgb888::GREEN),
+    );
+    assert!(red.r() > 200 && red.g() < 50);
+    assert!(green.r() < 50 && green.g() > 200);
+
+    assert_eq!(
+        protan.map_color(BinaryColor::On),
+        BinaryColor::On,
+        "binary colors are unchanged"
+    );
+}
+
+#[test]
+fn adapter() {
+    let mut disp = ColorBlind::new(MockDisplay::new(), Deficiency::Protanopia);
+    disp.fill_solid(&Rectangle::new(Point::zero(), Size::new(1, 1)), Rgb565::RED)
+        .unwrap();
+    assert_eq!(disp.deficiency(), Deficiency::Protanopia);
+    disp.set_deficiency(Deficiency::Tritanopia);
+    disp.draw_iter([Pixel(Point::new(1, 0), Rgb565::RED)])
+        .unwrap();
+
+    let protan_red = disp.get_pixel(Point::zero()).unwrap();
+    assert!(protan_red.r() < Rgb565::MAX_R / 2);
+    assert!(protan_red.g() > 0);
+    assert_eq!(
+        disp.get_pixel(Point::new(1, 0)),
+        Some(Deficiency::Tritanopia.map_color(Rgb565::RED))
+    );
+}
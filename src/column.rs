@@ -0,0 +1,80 @@
+//! Drive displays whose memory is addressed in column-major order.
+
+use crate::r#impl::Transpose;
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+
+#[cfg(test)]
+mod tests;
+
+/// Present a column-major display as row-major.
+///
+/// The wrapped display's rows are the logical display's columns, so its
+/// coordinates are transposed. Unlike a plain transpose, contiguous fills are
+/// kept contiguous: each logical row is a single column of the wrapped display,
+/// so it's sent as one [`fill_contiguous`](DrawTarget::fill_contiguous) call
+/// without needing to buffer and reorder the colors. Areas which are a single
+/// column wide are sent as a single row.
+pub struct ColumnMajor<D> {
+    target: D,
+}
+
+impl<D> ColumnMajor<D> {
+    /// Wrap a column-major display.
+    pub fn new(target: D) -> Self {
+        ColumnMajor { target }
+    }
+}
+
+impl_wrapper!(ColumnMajor<D>);
+
+impl<D: Dimensions> Dimensions for ColumnMajor<D> {
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box().transpose()
+    }
+}
+
+impl<D: DrawTarget> DrawTarget for ColumnMajor<D> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.target.draw_iter(
+            pixels
+                .into_iter()
+                .map(|Pixel(loc, col)| Pixel(loc.transpose(), col)),
+        )
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let Size { width, height } = area.size;
+        if width == 0 || height == 0 {
+            return Ok(());
+        }
+        if width == 1 {
+            // A single logical column is a single physical row
+            return self.target.fill_contiguous(&area.transpose(), colors);
+        }
+
+        let mut colors = colors.into_iter();
+        for y in 0..height as i32 {
+            let row = Rectangle::new(area.top_left + Point::new(0, y), Size::new(width, 1));
+            self.target
+                .fill_contiguous(&row.transpose(), colors.by_ref().take(width as usize))?;
+        }
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.target.fill_solid(&area.transpose(), color)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.target.clear(color)
+    }
+}
@@ -0,0 +1,81 @@
+use super::*;
+use crate::tests::rect;
+use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+/// Records the areas passed to `fill_contiguous`.
+struct Fills {
+    disp: MockDisplay<BinaryColor>,
+    areas: [Option<Rectangle>; 8],
+    count: usize,
+}
+
+impl Dimensions for Fills {
+    fn bounding_box(&self) -> Rectangle {
+        rect(0, 0, 20, 10)
+    }
+}
+
+impl DrawTarget for Fills {
+    type Color = BinaryColor;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.disp.draw_iter(pixels)
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        self.areas[self.count] = Some(*area);
+        self.count += 1;
+        self.disp.fill_contiguous(area, colors)
+    }
+}
+
+#[test]
+fn column_major() {
+    use BinaryColor::{Off, On};
+
+    let mut disp = ColumnMajor::new(Fills {
+        disp: MockDisplay::new(),
+        areas: [None; 8],
+        count: 0,
+    });
+    assert_eq!(disp.bounding_box(), rect(0, 0, 10, 20));
+
+    disp.fill_contiguous(&rect(1, 2, 3, 2), [On, On, Off, Off, On, On])
+        .unwrap();
+    disp.fill_contiguous(&rect(5, 0, 1, 3), [On, Off, On])
+        .unwrap();
+    disp.draw_iter([Pixel(Point::new(0, 4), On)]).unwrap();
+
+    let Fills { disp, areas, count } = disp.into_inner();
+    assert_eq!(count, 3);
+    assert_eq!(
+        areas[..3],
+        [
+            Some(rect(2, 1, 1, 3)),
+            Some(rect(3, 1, 1, 3)),
+            Some(rect(0, 5, 3, 1))
+        ]
+    );
+    disp.assert_pattern(&[
+        "    #", //
+        "  #. ", //
+        "  ## ", //
+        "  .# ", //
+        "     ", //
+        "#.#  ", //
+    ]);
+}
+
+#[test]
+fn column_major_solid() {
+    let mut disp = ColumnMajor::new(MockDisplay::<BinaryColor>::new());
+    disp.fill_solid(&rect(1, 2, 3, 1), BinaryColor::On).unwrap();
+    assert_eq!(disp.as_ref().affected_area(), rect(2, 1, 1, 3));
+}
@@ -0,0 +1,411 @@
+//! Adapters for debugging and profiling drawing.
+
+use crate::{util, Command};
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+
+#[cfg(test)]
+mod tests;
+
+/// Drawing statistics collected by [`Instrument`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct DrawStats {
+    /// Number of draw calls, of any kind.
+    pub calls: u32,
+    /// Number of pixels drawn, including by fills.
+    pub pixels: u32,
+    /// Number of calls to `fill_contiguous`, `fill_solid` or `clear`.
+    pub fills: u32,
+    /// The largest area filled by a single call.
+    pub largest_fill: Rectangle,
+}
+
+impl DrawStats {
+    const fn new() -> Self {
+        DrawStats {
+            calls: 0,
+            pixels: 0,
+            fills: 0,
+            largest_fill: Rectangle::zero(),
+        }
+    }
+
+    fn fill(&mut self, area: &Rectangle) {
+        let pixels = area.size.width.saturating_mul(area.size.height);
+        let largest = &self.largest_fill.size;
+
+        self.calls += 1;
+        self.fills += 1;
+        self.pixels = self.pixels.saturating_add(pixels);
+        if pixels > largest.width * largest.height {
+            self.largest_fill = *area;
+        }
+    }
+}
+
+impl Default for DrawStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Count the drawing done to a display.
+///
+/// This counts draw calls, pixels and fills as they pass through to the inner
+/// display, for tuning rendering performance. The counts accumulate until
+/// [`reset`](Self::reset), which is typically called once per frame. Pixels
+/// are counted as they're passed to the inner display, so it includes any
+/// which it then clips.
+pub struct Instrument<D> {
+    target: D,
+    stats: DrawStats,
+}
+
+impl<D> Instrument<D> {
+    /// Count drawing to a display implementing [`DrawTarget`].
+    pub const fn new(target: D) -> Self {
+        Instrument {
+            target,
+            stats: DrawStats::new(),
+        }
+    }
+
+    /// Return the statistics collected since the last reset.
+    pub fn stats(&self) -> DrawStats {
+        self.stats
+    }
+
+    /// Reset the statistics, returning their previous values.
+    pub fn reset(&mut self) -> DrawStats {
+        core::mem::take(&mut self.stats)
+    }
+}
+
+impl_wrapper!(Instrument<D>);
+
+impl<D: Dimensions> Dimensions for Instrument<D> {
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+impl<D: DrawTarget> DrawTarget for Instrument<D> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let mut count = 0u32;
+        let res = self
+            .target
+            .draw_iter(pixels.into_iter().inspect(|_| count += 1));
+
+        self.stats.calls += 1;
+        self.stats.pixels = self.stats.pixels.saturating_add(count);
+        res
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        self.stats.fill(area);
+        self.target.fill_contiguous(area, colors)
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.stats.fill(area);
+        self.target.fill_solid(area, color)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.stats.fill(&self.target.bounding_box());
+        self.target.clear(color)
+    }
+}
+
+/// Report each drawing operation to a callback.
+///
+/// When placed directly around a display, below any transforms, this shows the
+/// operations a transform stack actually sends to the hardware, in physical
+/// coordinates. Each operation is passed to the callback as a [`Command`]
+/// before being forwarded. Only the area of a `fill_contiguous` is reported,
+/// not its colors.
+///
+/// With the `defmt` feature, [`defmt_trace`] can be used as the callback to log
+/// each operation.
+pub struct Trace<D, F> {
+    target: D,
+    trace: F,
+}
+
+impl<D, F> Trace<D, F>
+where
+    D: DrawTarget,
+    F: FnMut(&Command<D::Color>),
+{
+    /// Trace drawing to a display implementing [`DrawTarget`].
+    pub fn new(target: D, trace: F) -> Self {
+        Trace { target, trace }
+    }
+}
+
+impl_wrapper!(Trace<D, F>);
+
+/// Log a [`Command`] with `defmt` at trace level.
+#[cfg(feature = "defmt")]
+pub fn defmt_trace<C>(command: &Command<C>) {
+    match command {
+        Command::Pixel(p, _) => defmt::trace!("pixel ({=i32}, {=i32})", p.x, p.y),
+        Command::FillSolid(area, _) => defmt::trace!(
+            "fill_solid ({=i32}, {=i32}) {=u32}x{=u32}",
+            area.top_left.x,
+            area.top_left.y,
+            area.size.width,
+            area.size.height
+        ),
+        Command::FillContiguous(area) => defmt::trace!(
+            "fill_contiguous ({=i32}, {=i32}) {=u32}x{=u32}",
+            area.top_left.x,
+            area.top_left.y,
+            area.size.width,
+            area.size.height
+        ),
+        Command::Color(_) => (),
+        Command::Clear(_) => defmt::trace!("clear"),
+    }
+}
+
+impl<D: Dimensions, F> Dimensions for Trace<D, F> {
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+impl<D, F> DrawTarget for Trace<D, F>
+where
+    D: DrawTarget,
+    F: FnMut(&Command<D::Color>),
+{
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let trace = &mut self.trace;
+        self.target.draw_iter(
+            pixels
+                .into_iter()
+                .inspect(|&Pixel(p, c)| trace(&Command::Pixel(p, c))),
+        )
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        (self.trace)(&Command::FillContiguous(*area));
+        self.target.fill_contiguous(area, colors)
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        (self.trace)(&Command::FillSolid(*area, color));
+        self.target.fill_solid(area, color)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        (self.trace)(&Command::Clear(color));
+        self.target.clear(color)
+    }
+}
+
+/// A problem with a draw call found by [`Validate`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Violation {
+    /// An area with no pixels.
+    EmptyArea(Rectangle),
+    /// An area which isn't entirely within the bounding box.
+    AreaOutOfBounds(Rectangle),
+    /// A pixel outside the bounding box.
+    PixelOutOfBounds(Point),
+    /// An area whose far edge overflows the coordinate range, which can only
+    /// come from a bad calculation. It's not drawn.
+    Degenerate(Rectangle),
+    /// A `fill_contiguous` with only `len` colors, fewer than its area needs.
+    ShortColors {
+        /// The area being filled.
+        area: Rectangle,
+        /// How many colors there were.
+        len: usize,
+    },
+}
+
+/// Error from drawing to a [`Validate`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ValidationError<E> {
+    /// A draw call was invalid. Only returned if the `Validate` was configured
+    /// to report errors.
+    Invalid(Violation),
+    /// The wrapped display returned an error.
+    Display(E),
+}
+
+impl<E> From<E> for ValidationError<E> {
+    fn from(err: E) -> Self {
+        ValidationError::Display(err)
+    }
+}
+
+/// Check draw calls for mistakes, and report them to a callback.
+///
+/// A bad calculation somewhere in a transform stack or a widget typically
+/// shows up as corrupted output on the display, far from its cause. Placing
+/// this at any point in the stack checks the calls passing through for empty
+/// areas, areas and pixels outside the bounding box, fills with too few colors
+/// and areas whose coordinates overflow, and passes each [`Violation`] to the
+/// callback. Optionally, the first violation in a call can also be returned
+/// as [`ValidationError::Invalid`].
+///
+/// Some of these are valid, if unusual, for embedded-graphics, which expects
+/// displays to clip anything out of bounds and ignore anything empty, so
+/// calls are still forwarded after being reported. The exception is
+/// [`Violation::Degenerate`] areas, which are dropped. A short
+/// `fill_contiguous` is found by reading any colors the display didn't, up to
+/// the size of the area.
+pub struct Validate<D, F> {
+    target: D,
+    report: F,
+    errors: bool,
+}
+
+impl<D, F> Validate<D, F>
+where
+    D: DrawTarget,
+    F: FnMut(&Violation),
+{
+    /// Check drawing to a display implementing [`DrawTarget`], passing
+    /// violations to `report`.
+    pub fn new(target: D, report: F) -> Self {
+        Validate {
+            target,
+            report,
+            errors: false,
+        }
+    }
+
+    /// Return [`ValidationError::Invalid`] from draw calls with violations.
+    pub fn with_errors(mut self) -> Self {
+        self.errors = true;
+        self
+    }
+
+    /// Check an area, returning its first violation.
+    fn check(&self, area: &Rectangle) -> Option<Violation> {
+        let end = |start: i32, len: u32| {
+            i32::try_from(len)
+                .ok()
+                .and_then(|len| start.checked_add(len))
+        };
+        if end(area.top_left.x, area.size.width).is_none()
+            || end(area.top_left.y, area.size.height).is_none()
+        {
+            Some(Violation::Degenerate(*area))
+        } else if util::is_empty(area) {
+            Some(Violation::EmptyArea(*area))
+        } else if !util::contains_rect(&self.target.bounding_box(), area) {
+            Some(Violation::AreaOutOfBounds(*area))
+        } else {
+            None
+        }
+    }
+
+    /// Report a violation, and return it as an error if configured to.
+    fn violation(&mut self, violation: Violation) -> Result<(), ValidationError<D::Error>> {
+        (self.report)(&violation);
+        self.result(Some(violation))
+    }
+
+    fn result(&self, violation: Option<Violation>) -> Result<(), ValidationError<D::Error>> {
+        match violation {
+            Some(violation) if self.errors => Err(ValidationError::Invalid(violation)),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl_wrapper!(Validate<D, F>);
+
+impl<D: Dimensions, F> Dimensions for Validate<D, F> {
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+impl<D, F> DrawTarget for Validate<D, F>
+where
+    D: DrawTarget,
+    F: FnMut(&Violation),
+{
+    type Color = D::Color;
+    type Error = ValidationError<D::Error>;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let bbox = self.target.bounding_box();
+        let report = &mut self.report;
+        let mut first = None;
+
+        self.target
+            .draw_iter(pixels.into_iter().inspect(|&Pixel(p, _)| {
+                if !bbox.contains(p) {
+                    let violation = Violation::PixelOutOfBounds(p);
+                    report(&violation);
+                    first.get_or_insert(violation);
+                }
+            }))?;
+        self.result(first)
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let violation = self.check(area);
+        if let Some(violation @ Violation::Degenerate(_)) = violation {
+            return self.violation(violation);
+        }
+
+        let needed = area.size.width as usize * area.size.height as usize;
+        let mut colors = colors.into_iter();
+        let mut len = 0;
+        self.target
+            .fill_contiguous(area, colors.by_ref().inspect(|_| len += 1))?;
+        len += colors.take(needed.saturating_sub(len)).count();
+
+        let short = (len < needed).then_some(Violation::ShortColors { area: *area, len });
+        for violation in violation.iter().chain(&short) {
+            (self.report)(violation);
+        }
+        self.result(violation.or(short))
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        match self.check(area) {
+            Some(violation @ Violation::Degenerate(_)) => self.violation(violation),
+            Some(violation) => {
+                self.target.fill_solid(area, color)?;
+                self.violation(violation)
+            }
+            None => Ok(self.target.fill_solid(area, color)?),
+        }
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        Ok(self.target.clear(color)?)
+    }
+}
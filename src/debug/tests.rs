@@ -0,0 +1,133 @@
+use super::*;
+use crate::{tests::rect, Clipped, Rotate90};
+use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+#[test]
+fn instrument() {
+    let mut disp = Instrument::new(MockDisplay::new());
+    disp.as_mut().set_allow_overdraw(true);
+
+    disp.draw_iter([
+        Pixel(Point::new(0, 0), BinaryColor::On),
+        Pixel(Point::new(1, 0), BinaryColor::On),
+    ])
+    .unwrap();
+    disp.fill_solid(&rect(0, 1, 4, 2), BinaryColor::On).unwrap();
+    disp.fill_contiguous(&rect(0, 3, 2, 1), [BinaryColor::Off; 2])
+        .unwrap();
+
+    assert_eq!(
+        disp.stats(),
+        DrawStats {
+            calls: 3,
+            pixels: 12,
+            fills: 2,
+            largest_fill: rect(0, 1, 4, 2),
+        }
+    );
+
+    disp.reset();
+    disp.clear(BinaryColor::Off).unwrap();
+    assert_eq!(disp.stats().pixels, 64 * 64);
+    assert_eq!(disp.stats().largest_fill, rect(0, 0, 64, 64));
+}
+
+#[test]
+fn trace() {
+    let mut ops = [None; 4];
+    let mut count = 0;
+    let mut disp = Rotate90::new(Trace::new(MockDisplay::new(), |op: &Command<_>| {
+        ops[count] = Some(*op);
+        count += 1;
+    }));
+
+    disp.draw_iter([Pixel(Point::new(1, 0), BinaryColor::On)])
+        .unwrap();
+    disp.fill_contiguous(&rect(0, 1, 2, 1), [BinaryColor::Off; 2])
+        .unwrap();
+
+    assert_eq!(
+        ops,
+        [
+            Some(Command::Pixel(Point::new(63, 1), BinaryColor::On)),
+            // A single row is still a single fill when rotated
+            Some(Command::FillContiguous(rect(62, 0, 1, 2))),
+            None,
+            None,
+        ]
+    );
+}
+
+#[test]
+fn validate() {
+    let mut found = [None; 4];
+    let mut count = 0;
+    {
+        let inner = Clipped::new(MockDisplay::<BinaryColor>::new(), rect(0, 0, 64, 64));
+        let mut disp = Validate::new(inner, |v: &Violation| {
+            found[count] = Some(*v);
+            count += 1;
+        });
+
+        disp.fill_solid(&rect(0, 0, 2, 2), BinaryColor::On).unwrap();
+        disp.fill_solid(&rect(4, 4, 0, 2), BinaryColor::On).unwrap();
+        disp.fill_contiguous(&rect(62, 4, 4, 1), [BinaryColor::On; 4])
+            .unwrap();
+        disp.draw_iter([
+            Pixel(Point::new(4, 0), BinaryColor::On),
+            Pixel(Point::new(-1, 0), BinaryColor::On),
+        ])
+        .unwrap();
+        // Dropped rather than drawn
+        disp.fill_solid(
+            &Rectangle::new(Point::new(i32::MAX - 1, 0), Size::new(4, 1)),
+            BinaryColor::On,
+        )
+        .unwrap();
+
+        // Valid, or forwarded anyway
+        let mock = disp.as_ref().as_ref();
+        assert_eq!(mock.affected_area(), rect(0, 0, 64, 5));
+        assert_eq!(mock.get_pixel(Point::new(4, 0)), Some(BinaryColor::On));
+    }
+    assert_eq!(
+        found,
+        [
+            Some(Violation::EmptyArea(rect(4, 4, 0, 2))),
+            Some(Violation::AreaOutOfBounds(rect(62, 4, 4, 1))),
+            Some(Violation::PixelOutOfBounds(Point::new(-1, 0))),
+            Some(Violation::Degenerate(Rectangle::new(
+                Point::new(i32::MAX - 1, 0),
+                Size::new(4, 1)
+            ))),
+        ]
+    );
+}
+
+#[test]
+fn validate_errors() {
+    let mut mock = MockDisplay::<BinaryColor>::new();
+    mock.set_allow_overdraw(true);
+    let inner = Clipped::new(Rotate90::new(mock), rect(0, 0, 64, 64));
+    let mut disp = Validate::new(inner, |_| {}).with_errors();
+
+    assert_eq!(
+        disp.fill_contiguous(&rect(0, 0, 2, 2), [BinaryColor::On; 3]),
+        Err(ValidationError::Invalid(Violation::ShortColors {
+            area: rect(0, 0, 2, 2),
+            len: 3
+        }))
+    );
+    // The colors the display didn't read are counted
+    assert_eq!(
+        disp.fill_contiguous(&rect(-2, 0, 2, 2), [BinaryColor::On; 4]),
+        Err(ValidationError::Invalid(Violation::AreaOutOfBounds(rect(
+            -2, 0, 2, 2
+        ))))
+    );
+    assert_eq!(
+        disp.fill_contiguous(&rect(2, 0, 2, 2), [BinaryColor::On; 4]),
+        Ok(())
+    );
+    assert_eq!(disp.clear(BinaryColor::Off), Ok(()));
+}
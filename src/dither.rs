@@ -0,0 +1,511 @@
+//! Dithering for displays with fewer colors than are being drawn.
+
+use crate::{util, ColorChannels};
+use core::marker::PhantomData;
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+
+#[cfg(test)]
+mod tests;
+
+#[rustfmt::skip]
+const BAYER4: [[u8; 4]; 4] = [
+    [ 0,  8,  2, 10],
+    [12,  4, 14,  6],
+    [ 3, 11,  1,  9],
+    [15,  7, 13,  5],
+];
+
+#[rustfmt::skip]
+const BAYER8: [[u8; 8]; 8] = [
+    [ 0, 32,  8, 40,  2, 34, 10, 42],
+    [48, 16, 56, 24, 50, 18, 58, 26],
+    [12, 44,  4, 36, 14, 46,  6, 38],
+    [60, 28, 52, 20, 62, 30, 54, 22],
+    [ 3, 35, 11, 43,  1, 33,  9, 41],
+    [51, 19, 59, 27, 49, 17, 57, 25],
+    [15, 47,  7, 39, 13, 45,  5, 37],
+    [63, 31, 55, 23, 61, 29, 53, 21],
+];
+
+/// Threshold matrix used for ordered dithering.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum BayerMatrix {
+    /// 4x4 matrix, giving 16 levels between each output level.
+    Bayer4,
+    /// 8x8 matrix, giving 64 levels between each output level.
+    Bayer8,
+}
+
+impl BayerMatrix {
+    /// Number of cells in the matrix.
+    fn cells(self) -> u32 {
+        match self {
+            BayerMatrix::Bayer4 => 16,
+            BayerMatrix::Bayer8 => 64,
+        }
+    }
+
+    fn threshold(self, p: Point) -> u32 {
+        let (x, y) = (p.x.rem_euclid(8) as usize, p.y.rem_euclid(8) as usize);
+        match self {
+            BayerMatrix::Bayer4 => BAYER4[y % 4][x % 4].into(),
+            BayerMatrix::Bayer8 => BAYER8[y][x].into(),
+        }
+    }
+
+    /// Dither `color` at `p`.
+    fn dither<C, O>(self, p: Point, color: C) -> O
+    where
+        C: ColorChannels,
+        O: ColorChannels,
+    {
+        let channels = color.to_channels();
+        O::from_channels([0, 1, 2].map(|i| {
+            let (level, frac) = self.quantize(channels[i], C::MAX[i], O::MAX[i]);
+            level + (frac > self.threshold(p)) as u8
+        }))
+    }
+
+    /// Whether `color` dithers to a solid color.
+    fn is_solid<C, O>(self, color: C) -> bool
+    where
+        C: ColorChannels,
+        O: ColorChannels,
+    {
+        let channels = color.to_channels();
+        (0..3).all(|i| self.quantize(channels[i], C::MAX[i], O::MAX[i]).1 == 0)
+    }
+
+    /// Scale `value` to `out_max`, returning the whole level and the fraction
+    /// of the next in units of matrix cells.
+    fn quantize(self, value: u8, in_max: u8, out_max: u8) -> (u8, u32) {
+        quantize(value, in_max, out_max, self.cells())
+    }
+}
+
+/// Scale `value` to `out_max`, returning the whole level and the fraction of
+/// the next in units of `1 / steps`.
+fn quantize(value: u8, in_max: u8, out_max: u8, steps: u32) -> (u8, u32) {
+    let scaled = u32::from(value) * u32::from(out_max) * steps / u32::from(in_max);
+
+    ((scaled / steps) as u8, scaled % steps)
+}
+
+/// Dither colors using an ordered (Bayer) threshold matrix.
+///
+/// This converts colors of type `C` to the wrapped display's color type, which
+/// may be [`BinaryColor`](embedded_graphics_core::pixelcolor::BinaryColor) or
+/// any other type with fewer levels per channel. Each pixel's level is chosen
+/// by comparing the fractional part of its value with the matrix entry for its
+/// position, so this needs no buffering. The pattern is aligned to the wrapped
+/// display's coordinates, so it works best with this applied directly to the
+/// physical display.
+pub struct OrderedDither<D, C> {
+    target: D,
+    matrix: BayerMatrix,
+    color: PhantomData<fn(C)>,
+}
+
+impl<D, C> OrderedDither<D, C> {
+    /// Dither colors drawn to a display using `matrix`.
+    pub fn new(target: D, matrix: BayerMatrix) -> Self {
+        OrderedDither {
+            target,
+            matrix,
+            color: PhantomData,
+        }
+    }
+}
+
+impl_wrapper!(OrderedDither<D, C>);
+
+impl<D: Dimensions, C> Dimensions for OrderedDither<D, C> {
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+impl<D, C> DrawTarget for OrderedDither<D, C>
+where
+    D: DrawTarget,
+    D::Color: ColorChannels,
+    C: ColorChannels,
+{
+    type Color = C;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let matrix = self.matrix;
+        self.target.draw_iter(
+            pixels
+                .into_iter()
+                .map(|Pixel(loc, col)| Pixel(loc, matrix.dither(loc, col))),
+        )
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let matrix = self.matrix;
+        self.target.fill_contiguous(
+            area,
+            util::points(area)
+                .zip(colors)
+                .map(|(loc, col)| matrix.dither(loc, col)),
+        )
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let matrix = self.matrix;
+        if matrix.is_solid::<C, D::Color>(color) {
+            self.target
+                .fill_solid(area, matrix.dither(area.top_left, color))
+        } else {
+            self.target.fill_contiguous(
+                area,
+                util::points(area).map(|loc| matrix.dither(loc, color)),
+            )
+        }
+    }
+}
+
+/// The position in a temporal dithering cycle.
+#[derive(Clone, Copy)]
+struct Cycle {
+    frames: u8,
+    frame: u8,
+}
+
+impl Cycle {
+    /// Whether `frac` of the next level is shown at `p` in this frame.
+    fn upper(self, p: Point, frac: u32) -> bool {
+        let phase = (i32::from(self.frame) + p.x + 2 * p.y).rem_euclid(self.frames.into());
+        frac > phase as u32
+    }
+
+    /// Dither `color` at `p`.
+    fn dither<C, O>(self, p: Point, color: C) -> O
+    where
+        C: ColorChannels,
+        O: ColorChannels,
+    {
+        let channels = color.to_channels();
+        O::from_channels([0, 1, 2].map(|i| {
+            let (level, frac) = quantize(channels[i], C::MAX[i], O::MAX[i], self.frames.into());
+            level + self.upper(p, frac) as u8
+        }))
+    }
+
+    /// Whether `color` is the same in every frame.
+    fn is_solid<C, O>(self, color: C) -> bool
+    where
+        C: ColorChannels,
+        O: ColorChannels,
+    {
+        let channels = color.to_channels();
+        (0..3).all(|i| quantize(channels[i], C::MAX[i], O::MAX[i], self.frames.into()).1 == 0)
+    }
+}
+
+/// Show intermediate levels by alternating between colors over frames.
+///
+/// This is frame rate control, as used by passive LCDs: a color between two of
+/// the wrapped display's levels is drawn as the upper level in a proportion of
+/// every `frames` frames, and the lower level in the rest, so a display which
+/// is refreshed quickly enough appears to show `frames - 1` extra levels
+/// between each pair. For example, with 3 frames a binary display shows 4
+/// levels, enough for [`Gray2`](embedded_graphics_core::pixelcolor::Gray2)
+/// drawing.
+///
+/// The application calls [`tick`](Self::tick) once per frame and then redraws
+/// everything with intermediate colors, typically from a framebuffer or a
+/// [`Recorder`](crate::Recorder). The frame each pixel is on in is offset by
+/// its position, so the pattern moves across the display rather than the whole
+/// display flickering together. Like [`OrderedDither`], this works best
+/// applied directly to the physical display.
+pub struct TemporalDither<D, C> {
+    target: D,
+    cycle: Cycle,
+    color: PhantomData<fn(C)>,
+}
+
+impl<D, C> TemporalDither<D, C> {
+    /// Dither colors drawn to a display over `frames` frames.
+    ///
+    /// Useful values are 2 to 4, as more frames flicker visibly on most
+    /// displays. A value of 0 is treated as 1, which doesn't dither at all.
+    pub fn new(target: D, frames: u8) -> Self {
+        TemporalDither {
+            target,
+            cycle: Cycle {
+                frames: frames.max(1),
+                frame: 0,
+            },
+            color: PhantomData,
+        }
+    }
+
+    /// Return the number of frames in the cycle.
+    pub fn frames(&self) -> u8 {
+        self.cycle.frames
+    }
+
+    /// Return the current frame, from 0 to `frames - 1`.
+    pub fn frame(&self) -> u8 {
+        self.cycle.frame
+    }
+
+    /// Advance to the next frame.
+    pub fn tick(&mut self) {
+        self.cycle.frame = (self.cycle.frame + 1) % self.cycle.frames;
+    }
+}
+
+impl_wrapper!(TemporalDither<D, C>);
+
+impl<D: Dimensions, C> Dimensions for TemporalDither<D, C> {
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+impl<D, C> DrawTarget for TemporalDither<D, C>
+where
+    D: DrawTarget,
+    D::Color: ColorChannels,
+    C: ColorChannels,
+{
+    type Color = C;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let cycle = self.cycle;
+        self.target.draw_iter(
+            pixels
+                .into_iter()
+                .map(|Pixel(loc, col)| Pixel(loc, cycle.dither(loc, col))),
+        )
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let cycle = self.cycle;
+        self.target.fill_contiguous(
+            area,
+            util::points(area)
+                .zip(colors)
+                .map(|(loc, col)| cycle.dither(loc, col)),
+        )
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let cycle = self.cycle;
+        if cycle.is_solid::<C, D::Color>(color) {
+            self.target
+                .fill_solid(area, cycle.dither(area.top_left, color))
+        } else {
+            self.target
+                .fill_contiguous(area, util::points(area).map(|loc| cycle.dither(loc, color)))
+        }
+    }
+}
+
+/// Quantize a channel value in the range 0 to 255 to `max` levels, returning
+/// the level and the quantization error.
+fn quantize_nearest(value: i32, max: u8) -> (u8, i32) {
+    let max = i32::from(max);
+    let level = ((value * max + 127) / 255).clamp(0, max);
+
+    (level as u8, value - level * 255 / max)
+}
+
+/// Scale each channel of `color` to the range 0 to 255.
+fn channels8<C: ColorChannels>(color: C) -> [i32; 3] {
+    let channels = color.to_channels();
+    [0, 1, 2].map(|i| i32::from(channels[i]) * 255 / i32::from(C::MAX[i]))
+}
+
+/// Dither colors using Floyd-Steinberg error diffusion.
+///
+/// This converts colors of type `C` to the wrapped display's color type, like
+/// [`OrderedDither`], but gives much better results for photographic images.
+/// The quantization error of each pixel is spread to its neighbors to the right
+/// and below, using a buffer of one row of errors for displays up to `W` pixels
+/// wide.
+///
+/// Error diffusion relies on drawing in raster order, which is the case for
+/// [`fill_contiguous`](DrawTarget::fill_contiguous) (used for images) and
+/// [`fill_solid`](DrawTarget::fill_solid). The errors from the previous row are
+/// used if an area starts on the row after the last one drawn, and otherwise
+/// they're discarded. Pixels drawn with [`draw_iter`](DrawTarget::draw_iter)
+/// are set to the nearest color without dithering.
+pub struct ErrorDiffusion<D, C, const W: usize> {
+    target: D,
+    errors: [[i16; 3]; W],
+    next_row: Option<i32>,
+    color: PhantomData<fn(C)>,
+}
+
+impl<D, C, const W: usize> ErrorDiffusion<D, C, W> {
+    /// Dither colors drawn to a display.
+    pub fn new(target: D) -> Self {
+        ErrorDiffusion {
+            target,
+            errors: [[0; 3]; W],
+            next_row: None,
+            color: PhantomData,
+        }
+    }
+
+    /// Discard the accumulated errors, for example at the start of a frame.
+    pub fn reset(&mut self) {
+        self.errors = [[0; 3]; W];
+        self.next_row = None;
+    }
+
+    /// Recover the inner display instance.
+    pub fn into_inner(self) -> D {
+        self.target
+    }
+}
+
+impl<D, C, const W: usize> AsRef<D> for ErrorDiffusion<D, C, W> {
+    fn as_ref(&self) -> &D {
+        &self.target
+    }
+}
+
+impl<D, C, const W: usize> AsMut<D> for ErrorDiffusion<D, C, W> {
+    fn as_mut(&mut self) -> &mut D {
+        &mut self.target
+    }
+}
+
+impl<D, C, const W: usize> core::ops::Deref for ErrorDiffusion<D, C, W> {
+    type Target = D;
+
+    fn deref(&self) -> &D {
+        &self.target
+    }
+}
+
+impl<D, C, const W: usize> core::ops::DerefMut for ErrorDiffusion<D, C, W> {
+    fn deref_mut(&mut self) -> &mut D {
+        &mut self.target
+    }
+}
+
+impl_by_mut_ref!([D, C, const W: usize] ErrorDiffusion<D, C, W>);
+
+impl<D: Dimensions, C, const W: usize> Dimensions for ErrorDiffusion<D, C, W> {
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+impl<D, C, const W: usize> ErrorDiffusion<D, C, W>
+where
+    D: DrawTarget,
+    D::Color: ColorChannels,
+    C: ColorChannels,
+{
+    /// Dither one row of colors, starting at `x`.
+    fn dither_row<'a>(
+        errors: &'a mut [[i16; 3]; W],
+        x: i32,
+        colors: impl Iterator<Item = C> + 'a,
+    ) -> impl Iterator<Item = D::Color> + 'a {
+        let mut right = [0i32; 3];
+        let mut below_right = [0i32; 3];
+
+        colors.enumerate().map(move |(i, color)| {
+            let idx = usize::try_from(x + i as i32).ok().filter(|&idx| idx < W);
+            let value = channels8(color);
+            let mut out = [0u8; 3];
+
+            for c in 0..3 {
+                let max = <D::Color as ColorChannels>::MAX[c];
+                let above = idx.map_or(0, |idx| i32::from(errors[idx][c]));
+                let (level, err) = quantize_nearest(value[c] + right[c] + above, max);
+                out[c] = level;
+
+                // Spread the error as 7/16 right, and 3/16, 5/16 and 1/16 below
+                // left, below and below right, with any rounding in the last.
+                let (r, bl, b) = (err * 7 / 16, err * 3 / 16, err * 5 / 16);
+                right[c] = r;
+                if let Some(idx) = idx {
+                    if idx > 0 {
+                        errors[idx - 1][c] += bl as i16;
+                    }
+                    errors[idx][c] = (below_right[c] + b) as i16;
+                }
+                below_right[c] = err - r - bl - b;
+            }
+
+            D::Color::from_channels(out)
+        })
+    }
+}
+
+impl<D, C, const W: usize> DrawTarget for ErrorDiffusion<D, C, W>
+where
+    D: DrawTarget,
+    D::Color: ColorChannels,
+    C: ColorChannels,
+{
+    type Color = C;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.target
+            .draw_iter(pixels.into_iter().map(|Pixel(loc, col)| {
+                let value = channels8(col);
+                let out = [0, 1, 2]
+                    .map(|c| quantize_nearest(value[c], <D::Color as ColorChannels>::MAX[c]).0);
+                Pixel(loc, D::Color::from_channels(out))
+            }))
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let width = area.size.width as usize;
+        if width == 0 || area.size.height == 0 {
+            return Ok(());
+        }
+
+        let mut colors = colors.into_iter();
+        for y in 0..area.size.height as i32 {
+            let row = Rectangle::new(
+                area.top_left + Point::new(0, y),
+                Size::new(area.size.width, 1),
+            );
+            if self.next_row != Some(row.top_left.y) {
+                self.errors = [[0; 3]; W];
+            }
+            self.next_row = Some(row.top_left.y + 1);
+
+            let left = row.top_left.x - self.target.bounding_box().top_left.x;
+            let dithered = Self::dither_row(&mut self.errors, left, colors.by_ref().take(width));
+            self.target.fill_contiguous(&row, dithered)?;
+        }
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.fill_contiguous(area, util::points(area).map(|_| color))
+    }
+}
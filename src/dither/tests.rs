@@ -0,0 +1,131 @@
+use super::*;
+use crate::tests::rect;
+use embedded_graphics::{
+    mock_display::MockDisplay,
+    pixelcolor::{BinaryColor, Gray2, Gray8},
+};
+
+#[test]
+fn bayer_levels() {
+    let matrix = BayerMatrix::Bayer4;
+    let count = |luma| {
+        util::points(&rect(0, 0, 4, 4))
+            .filter(|&p| matrix.dither::<_, BinaryColor>(p, Gray8::new(luma)).is_on())
+            .count()
+    };
+
+    assert_eq!(count(0), 0);
+    assert_eq!(count(64), 4);
+    assert_eq!(count(128), 8);
+    assert_eq!(count(255), 16);
+
+    // Gray2 has three steps between black and white
+    assert_eq!(
+        matrix.dither::<_, Gray2>(Point::zero(), Gray8::new(255)),
+        Gray2::new(3)
+    );
+    assert!(matrix.is_solid::<_, Gray2>(Gray8::new(170)));
+    assert!(!matrix.is_solid::<_, Gray2>(Gray8::new(128)));
+}
+
+#[test]
+fn ordered_dither() {
+    let mut disp = OrderedDither::new(MockDisplay::<BinaryColor>::new(), BayerMatrix::Bayer4);
+
+    disp.fill_solid(&rect(0, 0, 4, 4), Gray8::new(128)).unwrap();
+    disp.fill_solid(&rect(4, 0, 2, 1), Gray8::new(255)).unwrap();
+    disp.draw_iter([Pixel(Point::new(4, 1), Gray8::new(200))])
+        .unwrap();
+
+    disp.as_ref().assert_pattern(&[
+        "#.#.##", //
+        ".#.#. ", //
+        "#.#.  ", //
+        ".#.#  ", //
+    ]);
+}
+
+#[test]
+fn quantize() {
+    assert_eq!(quantize_nearest(0, 1), (0, 0));
+    assert_eq!(quantize_nearest(100, 1), (0, 100));
+    assert_eq!(quantize_nearest(200, 1), (1, -55));
+    assert_eq!(quantize_nearest(300, 1), (1, 45));
+    assert_eq!(quantize_nearest(-20, 1), (0, -20));
+    assert_eq!(quantize_nearest(90, 3), (1, 5));
+}
+
+#[test]
+fn error_diffusion() {
+    let mut disp = ErrorDiffusion::<_, Gray8, 64>::new(MockDisplay::<BinaryColor>::new());
+
+    // 50% gray becomes a checkerboard
+    disp.fill_solid(&rect(0, 0, 6, 4), Gray8::new(128)).unwrap();
+    disp.as_ref().assert_pattern(&[
+        "#.#.#.", //
+        ".#.#.#", //
+        "#.#.#.", //
+        ".#.#.#", //
+    ]);
+
+    // Overall brightness is preserved
+    let mut disp = ErrorDiffusion::<_, Gray8, 64>::new(MockDisplay::<BinaryColor>::new());
+    disp.fill_solid(&rect(0, 0, 64, 64), Gray8::new(64))
+        .unwrap();
+    let on = util::points(&rect(0, 0, 64, 64))
+        .filter(|&p| disp.as_ref().get_pixel(p) == Some(BinaryColor::On))
+        .count();
+    assert!((1000..1050).contains(&on), "{on} pixels on");
+}
+
+#[test]
+fn error_diffusion_rows() {
+    let mut disp = ErrorDiffusion::<_, Gray8, 8>::new(MockDisplay::<BinaryColor>::new());
+
+    // Drawing row by row is the same as drawing all at once
+    for y in 0..4 {
+        disp.fill_solid(&rect(0, y, 6, 1), Gray8::new(128)).unwrap();
+    }
+    disp.draw_iter([Pixel(Point::new(7, 0), Gray8::new(200))])
+        .unwrap();
+    disp.as_ref().assert_pattern(&[
+        "#.#.#. #", //
+        ".#.#.#  ", //
+        "#.#.#.  ", //
+        ".#.#.#  ", //
+    ]);
+}
+
+#[test]
+fn temporal() {
+    let mut disp = TemporalDither::<_, Gray2>::new(MockDisplay::<BinaryColor>::new(), 3);
+    assert_eq!(disp.frames(), 3);
+
+    // Each gray level is on for that many of the 3 frames at every pixel.
+    let mut on = [[0; 4]; 4];
+    for frame in 0..3 {
+        assert_eq!(disp.frame(), frame);
+        *disp.as_mut() = MockDisplay::new();
+        for (i, level) in (0..4).enumerate() {
+            disp.fill_solid(&rect(i as i32 * 4, 0, 4, 1), Gray2::new(level))
+                .unwrap();
+        }
+        for p in util::points(&rect(0, 0, 16, 1)) {
+            on[(p.x / 4) as usize][(p.x % 4) as usize] +=
+                (disp.get_pixel(p) == Some(BinaryColor::On)) as u32;
+        }
+        disp.tick();
+    }
+    assert_eq!(disp.frame(), 0);
+    assert_eq!(on, [[0; 4], [1; 4], [2; 4], [3; 4]]);
+
+    // Neighbouring pixels are in different phases, so a level isn't all on or
+    // off at once.
+    *disp.as_mut() = MockDisplay::new();
+    disp.fill_solid(&rect(0, 0, 3, 1), Gray2::new(1)).unwrap();
+    disp.assert_pattern(&["#.."]);
+    disp.tick();
+    *disp.as_mut() = MockDisplay::new();
+    disp.fill_solid(&rect(0, 0, 3, 1), Gray2::new(1)).unwrap();
+    disp.assert_pattern(&["..#"]);
+}
@@ -0,0 +1,162 @@
+//! Pack transformed fills into buffers for DMA.
+
+use crate::{geometry, util, Orientation};
+use core::marker::PhantomData;
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+
+#[cfg(test)]
+mod tests;
+
+/// A rectangle of packed pixel data, in physical display coordinates.
+///
+/// The data is in row-major order, ready to be sent to a controller whose
+/// address window has been set to `area`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct DmaChunk<'a> {
+    /// The area of the physical display covered by `data`.
+    pub area: Rectangle,
+    /// The packed pixels.
+    pub data: &'a [u8],
+}
+
+impl<'a> DmaChunk<'a> {
+    /// Iterate over the rows of the chunk, as the physical position of the
+    /// first pixel and its packed pixels. This is convenient for writing to a
+    /// framebuffer at `(y * stride + x) * bytes_per_pixel`.
+    pub fn rows(&self) -> impl Iterator<Item = (Point, &'a [u8])> + 'a {
+        let Rectangle { top_left, size } = self.area;
+        let len = self.data.len() / size.height.max(1) as usize;
+
+        self.data
+            .chunks(len.max(1))
+            .take(size.height as usize)
+            .enumerate()
+            .map(move |(y, row)| (top_left + Point::new(0, y as i32), row))
+    }
+}
+
+/// Convert logical fills into packed physical chunks, ready for DMA.
+///
+/// Rather than drawing a pixel at a time through a [`DrawTarget`], the colors
+/// of a fill are mapped by the [`Orientation`] while they're packed into a
+/// caller-supplied buffer, with `pack` writing each color as
+/// `bytes_per_pixel` bytes. Each time the buffer is full, its contents are
+/// handed to a sink as a [`DmaChunk`]; chunks are whole rows of the logical
+/// fill when the buffer is large enough, so it should hold at least one row of
+/// the largest fill.
+///
+/// Parts of a fill outside the display are skipped.
+pub struct ScanlineEmitter<'a, C, F> {
+    buf: &'a mut [u8],
+    bytes_per_pixel: usize,
+    orientation: Orientation,
+    size: Size,
+    pack: F,
+    _color: PhantomData<C>,
+}
+
+impl<'a, C, F> ScanlineEmitter<'a, C, F>
+where
+    C: PixelColor,
+    F: FnMut(C, &mut [u8]),
+{
+    /// Create an emitter for a physical display of `size` in `orientation`.
+    ///
+    /// # Panics
+    ///
+    /// If `bytes_per_pixel` is zero.
+    pub fn new(
+        buf: &'a mut [u8],
+        bytes_per_pixel: usize,
+        orientation: Orientation,
+        size: Size,
+        pack: F,
+    ) -> Self {
+        assert!(bytes_per_pixel > 0, "pixels must have at least one byte");
+
+        ScanlineEmitter {
+            buf,
+            bytes_per_pixel,
+            orientation,
+            size,
+            pack,
+            _color: PhantomData,
+        }
+    }
+
+    /// Return the bounding box of the logical display.
+    pub fn bounding_box(&self) -> Rectangle {
+        Rectangle::new(
+            Point::zero(),
+            geometry::transform_size(self.orientation, self.size),
+        )
+    }
+
+    /// Pack a fill of logical `area` with `colors`, in row-major order, and
+    /// pass each chunk to `sink`.
+    ///
+    /// If there are too few colors, the last pixels are passed as single pixel
+    /// chunks.
+    pub fn fill<I, E>(
+        &mut self,
+        area: &Rectangle,
+        colors: I,
+        mut sink: impl FnMut(DmaChunk<'_>) -> Result<(), E>,
+    ) -> Result<(), E>
+    where
+        I: IntoIterator<Item = C>,
+    {
+        let clipped = area.intersection(&self.bounding_box());
+        let mut colors = util::points(area)
+            .zip(colors)
+            .filter(|(p, _)| clipped.contains(*p))
+            .map(|(_, c)| c);
+
+        let bpp = self.bytes_per_pixel;
+        let bounds = Rectangle::new(Point::zero(), self.size);
+        let orientation = self.orientation;
+
+        for logical in util::chunks(&clipped, self.buf.len() / bpp) {
+            let physical = geometry::transform_rect(orientation, bounds, logical);
+            let index = |p: Point| {
+                let p = geometry::transform_point(orientation, bounds, p) - physical.top_left;
+                (p.y as usize * physical.size.width as usize + p.x as usize) * bpp
+            };
+
+            let mut read = 0;
+            for (p, color) in util::points(&logical).zip(&mut colors) {
+                let i = index(p);
+                (self.pack)(color, &mut self.buf[i..i + bpp]);
+                read += 1;
+            }
+
+            let len = (logical.size.width * logical.size.height) as usize;
+            if read < len {
+                // Ran out of colors, so emit what there is.
+                for p in util::points(&logical).take(read) {
+                    let i = index(p);
+                    sink(DmaChunk {
+                        area: Rectangle::new(
+                            geometry::transform_point(orientation, bounds, p),
+                            Size::new(1, 1),
+                        ),
+                        data: &self.buf[i..i + bpp],
+                    })?;
+                }
+                return Ok(());
+            }
+
+            sink(DmaChunk {
+                area: physical,
+                data: &self.buf[..len * bpp],
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Recover the buffer and packing function.
+    pub fn into_inner(self) -> (&'a mut [u8], F) {
+        (self.buf, self.pack)
+    }
+}
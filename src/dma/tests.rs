@@ -0,0 +1,104 @@
+use super::*;
+use crate::tests::{color, rect};
+use embedded_graphics_core::pixelcolor::{raw::RawU16, Rgb565};
+
+fn pack(color: Rgb565, out: &mut [u8]) {
+    out.copy_from_slice(&RawU16::from(color).into_inner().to_be_bytes());
+}
+
+/// A 12x8 physical framebuffer, written through the chunks' rows.
+struct Framebuffer([[u8; 2]; 96]);
+
+impl Framebuffer {
+    fn write(&mut self, chunk: DmaChunk<'_>) -> Result<(), ()> {
+        for (start, row) in chunk.rows() {
+            for (i, px) in row.chunks(2).enumerate() {
+                let x = start.x as usize + i;
+                self.0[start.y as usize * 12 + x].copy_from_slice(px);
+            }
+        }
+        Ok(())
+    }
+
+    fn get(&self, p: Point) -> [u8; 2] {
+        self.0[p.y as usize * 12 + p.x as usize]
+    }
+}
+
+#[test]
+fn emit() {
+    let size = Size::new(12, 8);
+    let bounds = rect(0, 0, 12, 8);
+
+    for orientation in Orientation::ALL {
+        for len in [2, 6, 14, 64, 192] {
+            let mut buf = [0; 192];
+            let mut emitter = ScanlineEmitter::new(&mut buf[..len], 2, orientation, size, pack);
+            let area = rect(1, 2, 5, 4);
+            let mut fb = Framebuffer([[0xff; 2]; 96]);
+
+            emitter
+                .fill(&area, util::points(&area).map(color), |c| fb.write(c))
+                .unwrap();
+
+            for p in util::points(&emitter.bounding_box()) {
+                let expected = if area.contains(p) {
+                    RawU16::from(color(p)).into_inner().to_be_bytes()
+                } else {
+                    [0xff; 2]
+                };
+                let physical = geometry::transform_point(orientation, bounds, p);
+                assert_eq!(
+                    fb.get(physical),
+                    expected,
+                    "{} {} {:?}",
+                    orientation,
+                    len,
+                    p
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn emit_chunks() {
+    let mut buf = [0; 32];
+    let mut emitter = ScanlineEmitter::new(
+        &mut buf,
+        2,
+        Orientation::IDENTITY.rotate90(),
+        Size::new(12, 8),
+        pack,
+    );
+    assert_eq!(emitter.bounding_box(), rect(0, 0, 8, 12));
+
+    // Two rows at a time become two columns, and the part off the display is
+    // skipped
+    let mut areas = [Rectangle::zero(); 4];
+    let mut count = 0;
+    emitter
+        .fill(&rect(2, 6, 6, 8), [Rgb565::RED; 48], |c| {
+            areas[count] = c.area;
+            count += 1;
+            Ok::<_, ()>(())
+        })
+        .unwrap();
+    assert_eq!(count, 3);
+    assert_eq!(
+        areas[..3],
+        [rect(4, 2, 2, 6), rect(2, 2, 2, 6), rect(0, 2, 2, 6)]
+    );
+
+    // Running out of colors emits single pixels
+    count = 0;
+    emitter
+        .fill(&rect(0, 0, 8, 1), [Rgb565::RED; 3], |c| {
+            assert_eq!(c.area.size, Size::new(1, 1));
+            assert_eq!(c.data, [0xf8, 0]);
+            count += 1;
+            Ok::<_, ()>(())
+        })
+        .unwrap();
+    assert_eq!(count, 3);
+}
@@ -0,0 +1,166 @@
+//! Type-erased displays.
+
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+
+#[cfg(test)]
+mod tests;
+
+/// An object-safe form of [`DrawTarget`].
+///
+/// `DrawTarget` can't be used as a trait object because its methods are
+/// generic over the iterator types. This trait passes the iterators as trait
+/// objects instead, and is implemented for every `DrawTarget`, so that any
+/// display or stack of transforms can be type-erased as
+/// `dyn DynTransform<Color = C, Error = E>`. [`MapError`](crate::MapError) can
+/// give stacks over different displays a common error type.
+///
+/// `&mut dyn DynTransform` implements `DrawTarget`, so drawing code such as a
+/// UI library can take any display as a `&mut dyn DynTransform<Color = C,
+/// Error = E>`, without the concrete types of the transforms appearing in its
+/// signatures. With the `alloc` feature, a [`BoxedTransform`] implements
+/// `DrawTarget` in the same way, so it can be stored without generic parameters.
+/// Both can be wrapped in further transforms.
+pub trait DynTransform {
+    /// The color type of the display.
+    type Color: PixelColor;
+    /// The error type of the display.
+    type Error;
+
+    /// Return the bounding box of the display.
+    fn dyn_bounding_box(&self) -> Rectangle;
+
+    /// Draw pixels, as [`DrawTarget::draw_iter`].
+    fn dyn_draw_iter(
+        &mut self,
+        pixels: &mut dyn Iterator<Item = Pixel<Self::Color>>,
+    ) -> Result<(), Self::Error>;
+
+    /// Fill an area with colors, as [`DrawTarget::fill_contiguous`].
+    fn dyn_fill_contiguous(
+        &mut self,
+        area: &Rectangle,
+        colors: &mut dyn Iterator<Item = Self::Color>,
+    ) -> Result<(), Self::Error>;
+
+    /// Fill an area with a solid color, as [`DrawTarget::fill_solid`].
+    fn dyn_fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error>;
+
+    /// Clear the display, as [`DrawTarget::clear`].
+    fn dyn_clear(&mut self, color: Self::Color) -> Result<(), Self::Error>;
+}
+
+impl<D: DrawTarget> DynTransform for D {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn dyn_bounding_box(&self) -> Rectangle {
+        self.bounding_box()
+    }
+
+    fn dyn_draw_iter(
+        &mut self,
+        pixels: &mut dyn Iterator<Item = Pixel<Self::Color>>,
+    ) -> Result<(), Self::Error> {
+        self.draw_iter(pixels)
+    }
+
+    fn dyn_fill_contiguous(
+        &mut self,
+        area: &Rectangle,
+        colors: &mut dyn Iterator<Item = Self::Color>,
+    ) -> Result<(), Self::Error> {
+        self.fill_contiguous(area, colors)
+    }
+
+    fn dyn_fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.fill_solid(area, color)
+    }
+
+    fn dyn_clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.clear(color)
+    }
+}
+
+impl<C: PixelColor, E> Dimensions for &mut (dyn DynTransform<Color = C, Error = E> + '_) {
+    fn bounding_box(&self) -> Rectangle {
+        (**self).dyn_bounding_box()
+    }
+}
+
+impl<C: PixelColor, E> DrawTarget for &mut (dyn DynTransform<Color = C, Error = E> + '_) {
+    type Color = C;
+    type Error = E;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        (**self).dyn_draw_iter(&mut pixels.into_iter())
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        (**self).dyn_fill_contiguous(area, &mut colors.into_iter())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        (**self).dyn_fill_solid(area, color)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        (**self).dyn_clear(color)
+    }
+}
+
+/// A boxed, type-erased display, with the `alloc` feature.
+#[cfg(feature = "alloc")]
+pub type BoxedTransform<'a, C, E> = Box<dyn DynTransform<Color = C, Error = E> + 'a>;
+
+/// Box a display or stack of transforms as a [`BoxedTransform`].
+#[cfg(feature = "alloc")]
+pub fn boxed<'a, D>(target: D) -> BoxedTransform<'a, D::Color, D::Error>
+where
+    D: DrawTarget + 'a,
+{
+    Box::new(target)
+}
+
+#[cfg(feature = "alloc")]
+impl<C: PixelColor, E> Dimensions for BoxedTransform<'_, C, E> {
+    fn bounding_box(&self) -> Rectangle {
+        (**self).dyn_bounding_box()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C: PixelColor, E> DrawTarget for BoxedTransform<'_, C, E> {
+    type Color = C;
+    type Error = E;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        (**self).dyn_draw_iter(&mut pixels.into_iter())
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        (**self).dyn_fill_contiguous(area, &mut colors.into_iter())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        (**self).dyn_fill_solid(area, color)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        (**self).dyn_clear(color)
+    }
+}
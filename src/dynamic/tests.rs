@@ -0,0 +1,73 @@
+use super::*;
+use crate::{tests::rect, FlipY, Rotate180};
+use core::convert::Infallible;
+use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+/// Drawing code which doesn't know which transforms are in use.
+fn draw_ui(mut display: &mut dyn DynTransform<Color = BinaryColor, Error = Infallible>) {
+    display
+        .fill_contiguous(&rect(0, 0, 2, 1), [BinaryColor::On, BinaryColor::Off])
+        .unwrap();
+    display
+        .fill_solid(&rect(0, 1, 1, 2), BinaryColor::On)
+        .unwrap();
+
+    // It can also be transformed further
+    Rotate180::new(display)
+        .draw_iter([Pixel(Point::new(0, 0), BinaryColor::On)])
+        .unwrap();
+}
+
+#[test]
+fn dyn_ref() {
+    let mut disp = MockDisplay::new();
+    draw_ui(&mut disp);
+    assert_eq!(disp.affected_area(), rect(0, 0, 64, 64));
+    assert_eq!(disp.get_pixel(Point::new(1, 0)), Some(BinaryColor::Off));
+
+    let mut disp = FlipY::new(MockDisplay::new());
+    draw_ui(&mut disp);
+    assert_eq!(
+        disp.as_ref().get_pixel(Point::new(0, 63)),
+        Some(BinaryColor::On)
+    );
+    assert_eq!(
+        disp.as_ref().get_pixel(Point::new(63, 0)),
+        Some(BinaryColor::On)
+    );
+}
+
+/// Holds a display without knowing which transforms it has.
+#[cfg(feature = "alloc")]
+struct Screen<'a> {
+    display: BoxedTransform<'a, BinaryColor, Infallible>,
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn boxed_stack() {
+    let mut screens = [
+        Screen {
+            display: boxed(crate::Rotate90::new(MockDisplay::new())),
+        },
+        Screen {
+            display: boxed(MockDisplay::new()),
+        },
+    ];
+
+    for screen in &mut screens {
+        screen
+            .display
+            .draw_iter([Pixel(Point::new(0, 0), BinaryColor::On)])
+            .unwrap();
+        assert_eq!(screen.display.bounding_box().size, Size::new(64, 64));
+    }
+
+    // Transforms can wrap a boxed display
+    let mut disp = Rotate180::new(boxed(FlipY::new(MockDisplay::new())));
+    disp.fill_contiguous(
+        &Rectangle::new(Point::new(0, 0), Size::new(2, 1)),
+        [BinaryColor::On, BinaryColor::Off],
+    )
+    .unwrap();
+}
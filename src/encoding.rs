@@ -0,0 +1,343 @@
+//! Stable encodings of orientations and rotations.
+
+use crate::{Orientation, Rotation};
+use core::{fmt, str::FromStr};
+
+#[cfg(test)]
+mod tests;
+
+impl Orientation {
+    /// Encode the orientation as a single byte.
+    ///
+    /// The encoding is stable, so it can be stored or sent over a wire
+    /// protocol: bits 0 and 1 are the number of quarter turns to the right, and
+    /// bit 2 is set if the display is mirrored around the X axis first. This
+    /// is the orientation's index in [`Orientation::ALL`], and a plain rotation
+    /// has the same encoding as the [`Rotation`].
+    pub const fn to_byte(self) -> u8 {
+        let mut i = 0;
+        while i < Orientation::ALL.len() {
+            let o = Orientation::ALL[i];
+            if o.transpose == self.transpose && o.flip_x == self.flip_x && o.flip_y == self.flip_y {
+                break;
+            }
+            i += 1;
+        }
+        i as u8
+    }
+
+    /// Decode an orientation encoded by [`to_byte`](Self::to_byte), returning
+    /// `None` if the byte isn't a valid encoding.
+    pub const fn from_byte(byte: u8) -> Option<Self> {
+        if (byte as usize) < Orientation::ALL.len() {
+            Some(Orientation::ALL[byte as usize])
+        } else {
+            None
+        }
+    }
+}
+
+impl Rotation {
+    /// Encode the rotation as a single byte.
+    ///
+    /// The encoding is stable: it's the number of quarter turns to the right,
+    /// the same as the equivalent [`Orientation::to_byte`].
+    pub const fn to_byte(self) -> u8 {
+        match self {
+            Rotation::Rotate0 => 0,
+            Rotation::Rotate90 => 1,
+            Rotation::Rotate180 => 2,
+            Rotation::Rotate270 => 3,
+        }
+    }
+
+    /// Decode a rotation encoded by [`to_byte`](Self::to_byte), returning
+    /// `None` if the byte isn't a valid encoding.
+    pub const fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(Rotation::Rotate0),
+            1 => Some(Rotation::Rotate90),
+            2 => Some(Rotation::Rotate180),
+            3 => Some(Rotation::Rotate270),
+            _ => None,
+        }
+    }
+}
+
+/// MADCTL row address order bit.
+const MADCTL_MY: u8 = 0x80;
+/// MADCTL column address order bit.
+const MADCTL_MX: u8 = 0x40;
+/// MADCTL row/column exchange bit.
+const MADCTL_MV: u8 = 0x20;
+/// MADCTL BGR color order bit.
+const MADCTL_BGR: u8 = 0x08;
+
+impl Orientation {
+    /// Return the MADCTL (memory access control) register value which makes a
+    /// MIPI-style display controller apply this orientation itself.
+    ///
+    /// The controller exchanges rows and columns if MV is set, and then
+    /// reverses the column order if MX is set and the row order if MY is set.
+    /// Setting the register to this value and drawing directly draws the same
+    /// as drawing through a [`Reorient`](crate::Reorient) in this orientation,
+    /// assuming the panel isn't itself mounted mirrored. The BGR bit is set if
+    /// `bgr`, and the other bits are clear.
+    pub const fn to_madctl(self, bgr: bool) -> u8 {
+        // Mirroring happens after the exchange, so it applies to the other
+        // axis if transposed
+        let (mx, my) = if self.transpose {
+            (self.flip_y, self.flip_x)
+        } else {
+            (self.flip_x, self.flip_y)
+        };
+
+        let mut madctl = 0;
+        if my {
+            madctl |= MADCTL_MY;
+        }
+        if mx {
+            madctl |= MADCTL_MX;
+        }
+        if self.transpose {
+            madctl |= MADCTL_MV;
+        }
+        if bgr {
+            madctl |= MADCTL_BGR;
+        }
+        madctl
+    }
+
+    /// Return the orientation applied by a controller with this MADCTL
+    /// register value, as described for [`to_madctl`](Self::to_madctl). Only
+    /// the MY, MX and MV bits are used.
+    pub const fn from_madctl(madctl: u8) -> Self {
+        let transpose = madctl & MADCTL_MV != 0;
+        let mx = madctl & MADCTL_MX != 0;
+        let my = madctl & MADCTL_MY != 0;
+
+        if transpose {
+            Orientation::new(true, my, mx)
+        } else {
+            Orientation::new(false, mx, my)
+        }
+    }
+}
+
+/// Linux fbdev `FB_ROTATE_*` values, in quarter turns clockwise.
+const FB_ROTATE: [u32; 4] = [0, 1, 2, 3];
+
+/// Linux DRM plane rotation bits for 0, 90, 180 and 270 degrees counter
+/// clockwise.
+const DRM_MODE_ROTATE: [u32; 4] = [1 << 0, 1 << 1, 1 << 2, 1 << 3];
+/// Linux DRM plane reflection bits.
+const DRM_MODE_REFLECT_X: u32 = 1 << 4;
+const DRM_MODE_REFLECT_Y: u32 = 1 << 5;
+
+impl Rotation {
+    /// Return the Linux fbdev `FB_ROTATE_*` value for this rotation, as used
+    /// for the framebuffer console and the `rotate` field of
+    /// `fb_var_screeninfo`.
+    ///
+    /// `FB_ROTATE_CW` is [`Rotation::Rotate90`], and `FB_ROTATE_CCW` is
+    /// [`Rotation::Rotate270`].
+    pub const fn to_fb_rotate(self) -> u32 {
+        FB_ROTATE[self.to_byte() as usize]
+    }
+
+    /// Return the rotation for a Linux fbdev `FB_ROTATE_*` value, or `None` if
+    /// it isn't one.
+    pub const fn from_fb_rotate(value: u32) -> Option<Self> {
+        if value < 4 {
+            Rotation::from_byte(value as u8)
+        } else {
+            None
+        }
+    }
+}
+
+impl Orientation {
+    /// Return the Linux fbdev `FB_ROTATE_*` value for this orientation, and
+    /// the orientation left to apply in software.
+    ///
+    /// fbdev can only rotate, so a mirrored orientation is split into its
+    /// rotation, done by the kernel, and mirroring around the X axis, which is
+    /// done first. Drawing through a [`Reorient`](crate::Reorient) in the
+    /// returned orientation to a framebuffer rotated by the returned value
+    /// draws the same as this orientation; for a plain rotation, the returned
+    /// orientation is the identity.
+    pub const fn to_fb_rotate(self) -> (u32, Orientation) {
+        let byte = self.to_byte();
+        let residual = if byte >= 4 {
+            Orientation::IDENTITY.flip_x()
+        } else {
+            Orientation::IDENTITY
+        };
+        (FB_ROTATE[(byte % 4) as usize], residual)
+    }
+
+    /// Return the Linux DRM plane `rotation` property value for this
+    /// orientation.
+    ///
+    /// DRM rotations are counter clockwise, so `DRM_MODE_ROTATE_90` is a
+    /// [`Rotate270`](crate::Rotate270), and are applied after reflection. Every
+    /// orientation has a value, using `DRM_MODE_REFLECT_X` for mirroring,
+    /// although a plane may only support some of them.
+    pub const fn to_drm_rotation(self) -> u32 {
+        let byte = self.to_byte();
+        let mut value = DRM_MODE_ROTATE[((4 - byte % 4) % 4) as usize];
+        if byte >= 4 {
+            value |= DRM_MODE_REFLECT_X;
+        }
+        value
+    }
+
+    /// Return the orientation for a Linux DRM plane `rotation` property value,
+    /// or `None` if it doesn't have exactly one rotation bit.
+    ///
+    /// Both reflections are accepted, although
+    /// [`to_drm_rotation`](Self::to_drm_rotation) only uses
+    /// `DRM_MODE_REFLECT_X`. Other bits are ignored.
+    pub const fn from_drm_rotation(value: u32) -> Option<Self> {
+        let mut found = None;
+        let mut i = 0;
+        while i < DRM_MODE_ROTATE.len() {
+            if value & DRM_MODE_ROTATE[i] != 0 {
+                if found.is_some() {
+                    return None;
+                }
+                found = Some(i);
+            }
+            i += 1;
+        }
+        let Some(ccw) = found else {
+            return None;
+        };
+
+        // Reflection comes first, so it's the outermost wrapper
+        let mut orientation = Orientation::ALL[(4 - ccw) % 4];
+        if value & DRM_MODE_REFLECT_X != 0 {
+            orientation = orientation.flip_x();
+        }
+        if value & DRM_MODE_REFLECT_Y != 0 {
+            orientation = orientation.flip_y();
+        }
+        Some(orientation)
+    }
+}
+
+/// Error returned when parsing an unrecognized [`Rotation`] or
+/// [`Orientation`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ParseOrientationError;
+
+impl fmt::Display for ParseOrientationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("unrecognized orientation")
+    }
+}
+
+fn strip_prefix<'a>(s: &'a str, prefix: &str) -> Option<&'a str> {
+    let (head, tail) = (s.get(..prefix.len())?, s.get(prefix.len()..)?);
+    head.eq_ignore_ascii_case(prefix).then_some(tail)
+}
+
+fn strip_suffix<'a>(s: &'a str, suffix: &str) -> Option<&'a str> {
+    let split = s.len().checked_sub(suffix.len())?;
+    let (head, tail) = (s.get(..split)?, s.get(split..)?);
+    tail.eq_ignore_ascii_case(suffix).then_some(head)
+}
+
+/// Parse a rotation from its number of degrees or a name.
+///
+/// This accepts, ignoring case and surrounding whitespace:
+/// - the angle to the right: `"0"`, `"90"`, `"180"` or `"270"`, optionally
+///   prefixed by `"rotate"`, as in `"Rotate90"`
+/// - `"cw"` for `Rotate90` and `"ccw"` for `Rotate270`
+/// - `"landscape"` for `Rotate0` and `"portrait"` for `Rotate90`, which assumes
+///   the display is natively landscape
+impl FromStr for Rotation {
+    type Err = ParseOrientationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let angle = strip_prefix(s, "rotate").unwrap_or(s);
+
+        let rotation = match angle {
+            "0" => Rotation::Rotate0,
+            "90" => Rotation::Rotate90,
+            "180" => Rotation::Rotate180,
+            "270" => Rotation::Rotate270,
+            _ if s.eq_ignore_ascii_case("cw") => Rotation::Rotate90,
+            _ if s.eq_ignore_ascii_case("ccw") => Rotation::Rotate270,
+            _ if s.eq_ignore_ascii_case("landscape") => Rotation::Rotate0,
+            _ if s.eq_ignore_ascii_case("portrait") => Rotation::Rotate90,
+            _ => return Err(ParseOrientationError),
+        };
+        Ok(rotation)
+    }
+}
+
+/// Parse an orientation as a rotation, which may be mirrored.
+///
+/// This accepts anything which parses as a [`Rotation`]. Mirrored orientations,
+/// which are mirrored around the X axis before being rotated, are written with
+/// a `"flipped-"` or `"mirrored-"` prefix, as in `"flipped-90"`, or with a
+/// `" mirrored"` suffix, as they're displayed. `"flipped"` or `"mirrored"` on
+/// its own is mirrored without rotation.
+impl FromStr for Orientation {
+    type Err = ParseOrientationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("flipped") || s.eq_ignore_ascii_case("mirrored") {
+            return Ok(Orientation::IDENTITY.flip_x());
+        }
+
+        let mirrored = strip_prefix(s, "flipped-")
+            .or_else(|| strip_prefix(s, "mirrored-"))
+            .or_else(|| strip_suffix(s, " mirrored"));
+
+        match mirrored {
+            Some(rotation) => Ok(Orientation::from(rotation.parse::<Rotation>()?).flip_x()),
+            None => Ok(s.parse::<Rotation>()?.into()),
+        }
+    }
+}
+
+/// Serialize orientations and rotations as their byte encodings, which is a
+/// single byte with formats such as postcard.
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use super::*;
+    use serde::{
+        de::{Error, Unexpected},
+        Deserialize, Deserializer, Serialize, Serializer,
+    };
+
+    macro_rules! impl_serde {
+        ($($name:ident, $expected:literal;)*) => {
+            $(
+                impl Serialize for $name {
+                    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                        serializer.serialize_u8(self.to_byte())
+                    }
+                }
+
+                impl<'de> Deserialize<'de> for $name {
+                    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                        let byte = u8::deserialize(deserializer)?;
+                        $name::from_byte(byte).ok_or_else(|| {
+                            D::Error::invalid_value(Unexpected::Unsigned(byte.into()), &$expected)
+                        })
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_serde! {
+        Orientation, "an orientation from 0 to 7";
+        Rotation, "a rotation from 0 to 3";
+    }
+}
@@ -0,0 +1,245 @@
+use super::*;
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+
+#[test]
+fn orientation_bytes() {
+    for (i, o) in Orientation::ALL.into_iter().enumerate() {
+        assert_eq!(o.to_byte(), i as u8);
+        assert_eq!(Orientation::from_byte(i as u8), Some(o));
+    }
+    assert_eq!(Orientation::from_byte(8), None);
+    assert_eq!(Orientation::from_byte(0xff), None);
+
+    // The encoding is stable
+    assert_eq!(Orientation::IDENTITY.rotate270().to_byte(), 3);
+    assert_eq!(Orientation::IDENTITY.rotate90().flip_x().to_byte(), 5);
+    assert_eq!(Orientation::IDENTITY.flip_y().to_byte(), 6);
+}
+
+#[test]
+fn rotation_bytes() {
+    for rotation in [
+        Rotation::Rotate0,
+        Rotation::Rotate90,
+        Rotation::Rotate180,
+        Rotation::Rotate270,
+    ] {
+        let byte = rotation.to_byte();
+        assert_eq!(Rotation::from_byte(byte), Some(rotation));
+        assert_eq!(Orientation::from(rotation).to_byte(), byte);
+    }
+    assert_eq!(Rotation::from_byte(4), None);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn deserialize() {
+    use serde::{de::value::U8Deserializer, Deserialize};
+
+    type Error = serde::de::value::Error;
+
+    assert_eq!(
+        Orientation::deserialize(U8Deserializer::<Error>::new(5)),
+        Ok(Orientation::ALL[5])
+    );
+    assert!(Orientation::deserialize(U8Deserializer::<Error>::new(8)).is_err());
+    assert_eq!(
+        Rotation::deserialize(U8Deserializer::<Error>::new(2)),
+        Ok(Rotation::Rotate180)
+    );
+    assert!(Rotation::deserialize(U8Deserializer::<Error>::new(4)).is_err());
+}
+
+#[test]
+fn parse_rotation() {
+    use Rotation::*;
+
+    for (s, rotation) in [
+        ("0", Rotate0),
+        ("90", Rotate90),
+        (" 180\n", Rotate180),
+        ("270", Rotate270),
+        ("Rotate90", Rotate90),
+        ("rotate270", Rotate270),
+        ("cw", Rotate90),
+        ("CCW", Rotate270),
+        ("landscape", Rotate0),
+        ("Portrait", Rotate90),
+    ] {
+        assert_eq!(s.parse(), Ok(rotation), "{:?}", s);
+    }
+
+    for s in ["", "45", "-90", "rotate", "cwx", "flipped-90"] {
+        assert_eq!(s.parse::<Rotation>(), Err(ParseOrientationError), "{:?}", s);
+    }
+}
+
+#[test]
+fn parse_orientation() {
+    use std::string::ToString;
+
+    let r90 = Orientation::IDENTITY.rotate90();
+
+    assert_eq!("90".parse(), Ok(r90));
+    assert_eq!("flipped-90".parse(), Ok(r90.flip_x()));
+    assert_eq!("Mirrored-cw".parse(), Ok(r90.flip_x()));
+    assert_eq!("flipped".parse(), Ok(Orientation::IDENTITY.flip_x()));
+    assert_eq!("flipped-180".parse(), Ok(Orientation::IDENTITY.flip_y()));
+    assert_eq!(
+        "flipped-".parse::<Orientation>(),
+        Err(ParseOrientationError)
+    );
+    assert_eq!(
+        "sideways".parse::<Orientation>(),
+        Err(ParseOrientationError)
+    );
+
+    // Displayed orientations parse back
+    for o in Orientation::ALL {
+        assert_eq!(o.to_string().parse(), Ok(o), "{}", o);
+    }
+}
+
+/// A controller which applies MADCTL itself, as described by `to_madctl`.
+struct Controller<D> {
+    target: D,
+    madctl: u8,
+}
+
+impl<D: Dimensions> Dimensions for Controller<D> {
+    fn bounding_box(&self) -> Rectangle {
+        let Size { width, height } = self.target.bounding_box().size;
+        let size = if self.madctl & 0x20 != 0 {
+            Size::new(height, width)
+        } else {
+            Size::new(width, height)
+        };
+        Rectangle::new(Point::zero(), size)
+    }
+}
+
+impl<D: DrawTarget> DrawTarget for Controller<D> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let Size { width, height } = self.target.bounding_box().size;
+        let madctl = self.madctl;
+
+        self.target
+            .draw_iter(pixels.into_iter().map(|Pixel(Point { x, y }, col)| {
+                let (x, y) = if madctl & 0x20 != 0 { (y, x) } else { (x, y) };
+                let x = if madctl & 0x40 != 0 {
+                    width as i32 - 1 - x
+                } else {
+                    x
+                };
+                let y = if madctl & 0x80 != 0 {
+                    height as i32 - 1 - y
+                } else {
+                    y
+                };
+                Pixel(Point::new(x, y), col)
+            }))
+    }
+}
+
+#[test]
+fn madctl() {
+    use crate::{testing::TransformTester, Reorient, WithSize};
+
+    // As used by common drivers for panels in their native orientation
+    let r = |rotation| Orientation::from(rotation);
+    assert_eq!(r(Rotation::Rotate0).to_madctl(false), 0x00);
+    assert_eq!(r(Rotation::Rotate90).to_madctl(false), 0x60);
+    assert_eq!(r(Rotation::Rotate180).to_madctl(true), 0xc8);
+    assert_eq!(r(Rotation::Rotate270).to_madctl(false), 0xa0);
+
+    // Other bits are ignored
+    assert_eq!(
+        Orientation::from_madctl(0x60 | 0x08 | 0x10 | 0x04),
+        r(Rotation::Rotate90)
+    );
+
+    for o in Orientation::ALL {
+        let madctl = o.to_madctl(false);
+        assert_eq!(Orientation::from_madctl(madctl), o);
+        assert_eq!(o.to_madctl(true), madctl | 0x08);
+
+        let sized = |disp| WithSize::new(disp, Size::new(40, 24));
+        TransformTester::new(
+            |disp| Reorient::new(sized(disp), o),
+            |target| target.into_inner().into_inner(),
+        )
+        .assert_equivalent(&mut TransformTester::new(
+            |disp| Controller {
+                target: sized(disp),
+                madctl,
+            },
+            |target| target.target.into_inner(),
+        ));
+    }
+}
+
+#[test]
+fn fb_rotate() {
+    use crate::{testing::TransformTester, Reorient, Rotate, WithSize};
+
+    assert_eq!(Rotation::Rotate90.to_fb_rotate(), 1);
+    assert_eq!(Rotation::Rotate270.to_fb_rotate(), 3);
+    assert_eq!(Rotation::from_fb_rotate(2), Some(Rotation::Rotate180));
+    assert_eq!(Rotation::from_fb_rotate(4), None);
+
+    for o in Orientation::ALL {
+        let (value, residual) = o.to_fb_rotate();
+        let rotation = Rotation::from_fb_rotate(value).unwrap();
+        if o.to_byte() < 4 {
+            assert_eq!(residual, Orientation::IDENTITY);
+        }
+
+        // The residual orientation drawn to the rotated framebuffer
+        let sized = |disp| WithSize::new(disp, Size::new(40, 24));
+        TransformTester::new(
+            |disp| Reorient::new(Rotate::new(rotation, sized(disp)), residual),
+            |target| target.into_inner().into_inner().into_inner(),
+        )
+        .assert_equivalent(&mut TransformTester::new(
+            |disp| Reorient::new(sized(disp), o),
+            |target| target.into_inner().into_inner(),
+        ));
+    }
+}
+
+#[test]
+fn drm_rotation() {
+    // Rotations are counter clockwise
+    let r = |rotation| Orientation::from(rotation);
+    assert_eq!(r(Rotation::Rotate0).to_drm_rotation(), 0x01);
+    assert_eq!(r(Rotation::Rotate90).to_drm_rotation(), 0x08);
+    assert_eq!(r(Rotation::Rotate270).to_drm_rotation(), 0x02);
+    assert_eq!(Orientation::IDENTITY.flip_x().to_drm_rotation(), 0x11);
+
+    for o in Orientation::ALL {
+        assert_eq!(Orientation::from_drm_rotation(o.to_drm_rotation()), Some(o));
+    }
+
+    // Reflection is applied first
+    assert_eq!(
+        Orientation::from_drm_rotation(0x02 | 0x10),
+        Some(Orientation::IDENTITY.rotate270().flip_x())
+    );
+    assert_eq!(
+        Orientation::from_drm_rotation(0x01 | 0x20),
+        Some(Orientation::IDENTITY.flip_y())
+    );
+    assert_eq!(
+        Orientation::from_drm_rotation(0x04 | 0x30 | 0x100),
+        Some(Orientation::IDENTITY)
+    );
+
+    assert_eq!(Orientation::from_drm_rotation(0), None);
+    assert_eq!(Orientation::from_drm_rotation(0x03), None);
+}
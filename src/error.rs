@@ -0,0 +1,261 @@
+//! Convert and recover from display errors.
+
+use crate::util;
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+
+#[cfg(test)]
+mod tests;
+
+/// Convert the errors returned by a display.
+///
+/// Every error from the inner display is passed through `map`, so drawing code
+/// can use the application's own error type without calling `map_err` on every
+/// draw. [`from_error`](MapError::from_error) uses an existing [`From`]
+/// conversion.
+pub struct MapError<D, F> {
+    target: D,
+    map: F,
+}
+
+impl<D, F, E> MapError<D, F>
+where
+    D: DrawTarget,
+    F: FnMut(D::Error) -> E,
+{
+    /// Convert the errors from a display implementing [`DrawTarget`] with
+    /// `map`.
+    pub fn new(target: D, map: F) -> Self {
+        MapError { target, map }
+    }
+}
+
+impl<D, E> MapError<D, fn(D::Error) -> E>
+where
+    D: DrawTarget,
+    E: From<D::Error>,
+{
+    /// Convert the errors from a display with [`From`].
+    pub fn from_error(target: D) -> Self {
+        MapError {
+            target,
+            map: E::from,
+        }
+    }
+}
+
+impl_wrapper!(MapError<D, F>);
+
+impl<D: Dimensions, F> Dimensions for MapError<D, F> {
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+impl<D, F, E> DrawTarget for MapError<D, F>
+where
+    D: DrawTarget,
+    F: FnMut(D::Error) -> E,
+{
+    type Color = D::Color;
+    type Error = E;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.target.draw_iter(pixels).map_err(&mut self.map)
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        self.target
+            .fill_contiguous(area, colors)
+            .map_err(&mut self.map)
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.target.fill_solid(area, color).map_err(&mut self.map)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.target.clear(color).map_err(&mut self.map)
+    }
+}
+
+/// Retry drawing which fails, for displays on buses with transient errors.
+///
+/// Each operation which returns an error is tried again, up to `retries` more
+/// times, before the error is returned. A reset callback, set with
+/// [`with_reset`](Self::with_reset), can be run on the display before each
+/// retry, for example to recover a stuck I2C bus.
+///
+/// [`draw_iter`](DrawTarget::draw_iter) and
+/// [`fill_contiguous`](DrawTarget::fill_contiguous) consume their pixels, so
+/// they're sent to the display in chunks of up to `N` pixels, each of which is
+/// kept so that it can be sent again. Fills are chunked as bands of whole
+/// rows, unless a row is longer than `N`, in which case they're drawn as
+/// pixels. A retry redraws the whole of the operation or chunk which failed,
+/// which is harmless since drawing the same pixels twice has the same result.
+pub struct Retry<D, R, const N: usize> {
+    target: D,
+    reset: R,
+    retries: usize,
+}
+
+impl<D, const N: usize> Retry<D, fn(&mut D), N> {
+    /// Wrap `target`, retrying failed operations up to `retries` times.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is zero.
+    pub fn new(target: D, retries: usize) -> Self {
+        assert!(N > 0, "chunks must have at least one pixel");
+        Retry {
+            target,
+            reset: |_| {},
+            retries,
+        }
+    }
+}
+
+impl<D, R, const N: usize> Retry<D, R, N> {
+    /// Call `reset` with the display after each failure, before it's retried.
+    pub fn with_reset<F: FnMut(&mut D)>(self, reset: F) -> Retry<D, F, N> {
+        Retry {
+            target: self.target,
+            reset,
+            retries: self.retries,
+        }
+    }
+
+    /// Return how many times failed operations are retried.
+    pub fn retries(&self) -> usize {
+        self.retries
+    }
+
+    /// Change how many times failed operations are retried.
+    pub fn set_retries(&mut self, retries: usize) {
+        self.retries = retries;
+    }
+}
+
+impl<D, R, const N: usize> Retry<D, R, N>
+where
+    D: DrawTarget,
+    R: FnMut(&mut D),
+{
+    /// Run `op`, retrying it up to `retries` times if it fails.
+    fn retry(
+        &mut self,
+        mut op: impl FnMut(&mut D) -> Result<(), D::Error>,
+    ) -> Result<(), D::Error> {
+        let mut retries = self.retries;
+        loop {
+            match op(&mut self.target) {
+                Err(_) if retries > 0 => {
+                    retries -= 1;
+                    (self.reset)(&mut self.target);
+                }
+                res => return res,
+            }
+        }
+    }
+}
+
+impl_wrapper!(Retry<D, R; const N: usize>);
+
+impl<D: Dimensions, R, const N: usize> Dimensions for Retry<D, R, N> {
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+impl<D, R, const N: usize> DrawTarget for Retry<D, R, N>
+where
+    D: DrawTarget,
+    R: FnMut(&mut D),
+{
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let mut pixels = pixels.into_iter().peekable();
+        let mut buf = match pixels.peek() {
+            Some(&first) => [first; N],
+            None => return Ok(()),
+        };
+
+        loop {
+            let len = buf
+                .iter_mut()
+                .zip(pixels.by_ref())
+                .map(|(slot, pixel)| *slot = pixel)
+                .count();
+            if len > 0 {
+                self.retry(|target| target.draw_iter(buf[..len].iter().copied()))?;
+            }
+            if len < N {
+                return Ok(());
+            }
+        }
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let width = area.size.width as usize;
+        if util::is_empty(area) {
+            return Ok(());
+        }
+        if width > N {
+            return self.draw_iter(
+                util::points(area)
+                    .zip(colors)
+                    .map(|(p, color)| Pixel(p, color)),
+            );
+        }
+
+        let mut colors = colors.into_iter().peekable();
+        let mut buf = match colors.peek() {
+            Some(&first) => [first; N],
+            None => return Ok(()),
+        };
+
+        let rows = (N / width) as u32;
+        let mut y = 0;
+        while y < area.size.height {
+            let band = Rectangle::new(
+                area.top_left + Point::new(0, y as i32),
+                Size::new(area.size.width, rows.min(area.size.height - y)),
+            );
+            let full = width * band.size.height as usize;
+            let len = buf[..full]
+                .iter_mut()
+                .zip(colors.by_ref())
+                .map(|(slot, color)| *slot = color)
+                .count();
+            if len > 0 {
+                self.retry(|target| target.fill_contiguous(&band, buf[..len].iter().copied()))?;
+            }
+            if len < full {
+                break;
+            }
+            y += band.size.height;
+        }
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.retry(|target| target.fill_solid(area, color))
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.retry(|target| target.clear(color))
+    }
+}
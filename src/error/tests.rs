@@ -0,0 +1,163 @@
+use super::*;
+use crate::{tests::rect, Lockout, LockoutError, Rotate90};
+use core::cell::Cell;
+use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+#[derive(Debug, PartialEq)]
+enum AppError {
+    Display,
+}
+
+impl From<LockoutError<core::convert::Infallible>> for AppError {
+    fn from(_: LockoutError<core::convert::Infallible>) -> Self {
+        AppError::Display
+    }
+}
+
+#[test]
+fn map_error() {
+    let mut lockout = Lockout::<_, 1>::new(MockDisplay::<BinaryColor>::new()).with_errors();
+    lockout.protect(0, rect(0, 0, 4, 4));
+
+    let mut disp = MapError::new(lockout, |_| AppError::Display);
+    assert_eq!(
+        disp.fill_solid(&rect(2, 2, 2, 2), BinaryColor::On),
+        Err(AppError::Display)
+    );
+    assert_eq!(disp.fill_solid(&rect(4, 4, 2, 2), BinaryColor::On), Ok(()));
+
+    let mut disp = Rotate90::new(MapError::from_error(disp.into_inner()));
+    let res: Result<(), AppError> = disp.draw_iter([Pixel(Point::new(0, 0), BinaryColor::On)]);
+    assert_eq!(res, Ok(()));
+    assert_eq!(
+        disp.draw_iter([Pixel(Point::new(0, 63), BinaryColor::On)]),
+        Err(AppError::Display)
+    );
+}
+
+/// A display which fails its next `failures` calls, and counts all calls.
+struct Flaky {
+    disp: MockDisplay<BinaryColor>,
+    failures: usize,
+    calls: usize,
+}
+
+impl Flaky {
+    fn new(failures: usize) -> Self {
+        let mut disp = MockDisplay::new();
+        disp.set_allow_overdraw(true);
+        Flaky {
+            disp,
+            failures,
+            calls: 0,
+        }
+    }
+
+    fn call(&mut self) -> Result<(), AppError> {
+        self.calls += 1;
+        if self.failures > 0 {
+            self.failures -= 1;
+            return Err(AppError::Display);
+        }
+        Ok(())
+    }
+}
+
+impl Dimensions for Flaky {
+    fn bounding_box(&self) -> Rectangle {
+        self.disp.bounding_box()
+    }
+}
+
+impl DrawTarget for Flaky {
+    type Color = BinaryColor;
+    type Error = AppError;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.call()?;
+        self.disp.draw_iter(pixels).unwrap();
+        Ok(())
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        self.call()?;
+        self.disp.fill_contiguous(area, colors).unwrap();
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.call()?;
+        self.disp.fill_solid(area, color).unwrap();
+        Ok(())
+    }
+}
+
+#[test]
+fn retry() {
+    let resets = Cell::new(0);
+    let mut disp =
+        Retry::<_, _, 4>::new(Flaky::new(2), 2).with_reset(|_| resets.set(resets.get() + 1));
+    assert_eq!(disp.fill_solid(&rect(0, 0, 2, 2), BinaryColor::On), Ok(()));
+    assert_eq!((disp.calls, resets.get()), (3, 2));
+
+    // Out of retries
+    disp.failures = 3;
+    assert_eq!(
+        disp.fill_solid(&rect(2, 2, 2, 2), BinaryColor::On),
+        Err(AppError::Display)
+    );
+    assert_eq!((disp.calls, resets.get()), (6, 4));
+
+    disp.set_retries(0);
+    disp.failures = 1;
+    assert_eq!(disp.clear(BinaryColor::Off), Err(AppError::Display));
+    assert_eq!(disp.as_ref().disp.affected_area(), rect(0, 0, 2, 2));
+}
+
+#[test]
+fn retry_chunks() {
+    let pattern = [
+        "#.#.#", //
+        ".#.#.", //
+        "#.#.#", //
+    ];
+    let area = rect(0, 0, 5, 3);
+    let colors = || util::points(&area).map(|p| BinaryColor::from((p.x + p.y) % 2 == 0));
+
+    // Each chunk is retried on its own, so a failure doesn't redraw earlier
+    // chunks.
+    let mut disp = Retry::<_, _, 4>::new(Flaky::new(0), 1);
+    disp.draw_iter(util::points(&area).zip(colors()).map(|(p, c)| Pixel(p, c)))
+        .unwrap();
+    assert_eq!(disp.calls, 4);
+    disp.calls = 0;
+    disp.failures = 1;
+    disp.draw_iter(util::points(&area).zip(colors()).map(|(p, c)| Pixel(p, c)))
+        .unwrap();
+    assert_eq!(disp.calls, 5);
+    disp.as_ref().disp.assert_pattern(&pattern);
+
+    // Fills are sent as bands of whole rows
+    let mut disp = Retry::<_, _, 12>::new(Flaky::new(1), 1);
+    disp.fill_contiguous(&area, colors()).unwrap();
+    assert_eq!(disp.calls, 3);
+    disp.as_ref().disp.assert_pattern(&pattern);
+
+    // or as pixels if the rows are too long
+    let mut disp = Retry::<_, _, 4>::new(Flaky::new(0), 1);
+    disp.fill_contiguous(&area, colors()).unwrap();
+    assert_eq!(disp.calls, 4);
+    disp.as_ref().disp.assert_pattern(&pattern);
+
+    // Running out of colors draws what there are
+    let mut disp = Retry::<_, _, 10>::new(Flaky::new(0), 1);
+    disp.fill_contiguous(&area, colors().take(7)).unwrap();
+    assert_eq!(disp.calls, 1);
+    disp.as_ref().disp.assert_pattern(&["#.#.#", ".#   "]);
+}
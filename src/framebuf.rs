@@ -0,0 +1,197 @@
+//! Direct drawing to an embedded-graphics-framebuf frame buffer in any
+//! orientation.
+
+use crate::{geometry, util, Orientation};
+use core::convert::Infallible;
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+use embedded_graphics_framebuf::{backends::FrameBufferBackend, FrameBuf};
+
+#[cfg(test)]
+mod tests;
+
+/// A [`FrameBuf`] drawn to in an [`Orientation`], with the orientation folded
+/// into the index of each pixel in its backing store.
+///
+/// Wrapping a `FrameBuf` in a transform such as [`Rotate90`](crate::Rotate90)
+/// maps every pixel through an iterator adapter, and then has the frame
+/// buffer check its bounds and compute its index again. This instead checks
+/// each point once against the logical bounds and writes straight to the
+/// backend at the transformed index. Within a row of a fill, consecutive points
+/// are a fixed distance apart in the backend, so the index is only computed at
+/// the start of each row.
+///
+/// This draws the same as a [`Reorient`](crate::Reorient) in the same
+/// orientation, wrapping the frame buffer. It's a type of its own rather than
+/// a special case of `Reorient`, since `Reorient` implements [`DrawTarget`]
+/// for any display, and stable Rust can't specialize that for `FrameBuf`. The
+/// frame buffer's origin is ignored, so its top left is the origin of the
+/// logical display.
+pub struct OrientedFrameBuf<C: PixelColor, B: FrameBufferBackend<Color = C>> {
+    target: FrameBuf<C, B>,
+    orientation: Orientation,
+}
+
+impl<C: PixelColor, B: FrameBufferBackend<Color = C>> OrientedFrameBuf<C, B> {
+    /// Draw to `target` in `orientation`.
+    pub fn new(target: FrameBuf<C, B>, orientation: impl Into<Orientation>) -> Self {
+        OrientedFrameBuf {
+            target,
+            orientation: orientation.into(),
+        }
+    }
+
+    /// Return the current orientation.
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    /// Change the orientation.
+    ///
+    /// This doesn't change the frame buffer's contents.
+    pub fn set_orientation(&mut self, orientation: impl Into<Orientation>) {
+        self.orientation = orientation.into();
+    }
+
+    /// Recover the frame buffer.
+    pub fn into_inner(self) -> FrameBuf<C, B> {
+        self.target
+    }
+
+    /// The frame buffer's area, in its own coordinates.
+    fn physical(&self) -> Rectangle {
+        let size = Size::new(self.target.width() as u32, self.target.height() as u32);
+        Rectangle::new(Point::zero(), size)
+    }
+
+    /// Return the index of a logical point in the backend, and how far the
+    /// index moves for each step along a logical row. The point must be
+    /// within the bounding box.
+    fn index(&self, p: Point) -> (usize, isize) {
+        let physical = self.physical();
+        let width = physical.size.width as isize;
+        let at = geometry::transform_point(self.orientation, physical, p);
+        let next = geometry::transform_point(self.orientation, physical, p + Point::new(1, 0));
+        let step = (next.y - at.y) as isize * width + (next.x - at.x) as isize;
+        (at.y as usize * width as usize + at.x as usize, step)
+    }
+
+    /// Write `colors` to a run of `len` points along a logical row, starting
+    /// at `start`, all of which are within the bounding box.
+    fn write_run(&mut self, start: Point, len: usize, colors: impl Iterator<Item = C>) {
+        let (mut index, step) = self.index(start);
+        for (i, color) in colors.take(len).enumerate() {
+            if i > 0 {
+                index = index.wrapping_add_signed(step);
+            }
+            self.target.data.set(index, color);
+        }
+    }
+}
+
+impl<C: PixelColor, B: FrameBufferBackend<Color = C>> core::ops::Deref for OrientedFrameBuf<C, B> {
+    type Target = FrameBuf<C, B>;
+
+    fn deref(&self) -> &FrameBuf<C, B> {
+        &self.target
+    }
+}
+
+impl<C: PixelColor, B: FrameBufferBackend<Color = C>> core::ops::DerefMut
+    for OrientedFrameBuf<C, B>
+{
+    fn deref_mut(&mut self) -> &mut FrameBuf<C, B> {
+        &mut self.target
+    }
+}
+
+impl<C: PixelColor, B: FrameBufferBackend<Color = C>> AsRef<FrameBuf<C, B>>
+    for OrientedFrameBuf<C, B>
+{
+    fn as_ref(&self) -> &FrameBuf<C, B> {
+        &self.target
+    }
+}
+
+impl<C: PixelColor, B: FrameBufferBackend<Color = C>> AsMut<FrameBuf<C, B>>
+    for OrientedFrameBuf<C, B>
+{
+    fn as_mut(&mut self) -> &mut FrameBuf<C, B> {
+        &mut self.target
+    }
+}
+
+impl_by_mut_ref!([C: PixelColor, B: FrameBufferBackend<Color = C>] OrientedFrameBuf<C, B>);
+
+impl<C: PixelColor, B: FrameBufferBackend<Color = C>> Dimensions for OrientedFrameBuf<C, B> {
+    fn bounding_box(&self) -> Rectangle {
+        let size = geometry::transform_size(self.orientation, self.physical().size);
+        Rectangle::new(Point::zero(), size)
+    }
+}
+
+impl<C: PixelColor, B: FrameBufferBackend<Color = C>> DrawTarget for OrientedFrameBuf<C, B> {
+    type Color = C;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let bbox = self.bounding_box();
+        let physical = self.physical();
+        let width = physical.size.width as usize;
+
+        for Pixel(p, color) in pixels {
+            if bbox.contains(p) {
+                let p = geometry::transform_point(self.orientation, physical, p);
+                self.target
+                    .data
+                    .set(p.y as usize * width + p.x as usize, color);
+            }
+        }
+        Ok(())
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let clip = area.intersection(&self.bounding_box());
+        if util::is_empty(&clip) {
+            return Ok(());
+        }
+        if clip != *area {
+            return self.draw_iter(
+                util::points(area)
+                    .zip(colors)
+                    .map(|(p, color)| Pixel(p, color)),
+            );
+        }
+
+        let width = area.size.width as usize;
+        let mut colors = colors.into_iter();
+        for y in area.rows() {
+            self.write_run(Point::new(area.top_left.x, y), width, colors.by_ref());
+        }
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = area.intersection(&self.bounding_box());
+        if util::is_empty(&area) {
+            return Ok(());
+        }
+
+        let width = area.size.width as usize;
+        for y in area.rows() {
+            let start = Point::new(area.top_left.x, y);
+            self.write_run(start, width, core::iter::repeat(color));
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        let area = self.bounding_box();
+        self.fill_solid(&area, color)
+    }
+}
@@ -0,0 +1,76 @@
+use super::*;
+use crate::{
+    tests::{color, rect},
+    Reorient,
+};
+use embedded_graphics::pixelcolor::Rgb565;
+
+const WIDTH: usize = 7;
+const HEIGHT: usize = 5;
+
+fn framebuf() -> FrameBuf<Rgb565, [Rgb565; WIDTH * HEIGHT]> {
+    FrameBuf::new([Rgb565::BLACK; WIDTH * HEIGHT], WIDTH, HEIGHT)
+}
+
+/// Draw a bit of everything, inside and overlapping the edges of a 7x5 or
+/// 5x7 display.
+fn draw<D: DrawTarget<Color = Rgb565, Error = Infallible>>(disp: &mut D) {
+    disp.draw_iter(
+        [
+            Point::new(0, 0),
+            Point::new(4, 1),
+            Point::new(-1, 2),
+            Point::new(3, 7),
+        ]
+        .into_iter()
+        .map(|p| Pixel(p, color(p))),
+    )
+    .unwrap();
+
+    for area in [
+        rect(1, 1, 3, 2),
+        rect(-2, 3, 4, 3),
+        rect(3, -1, 6, 2),
+        rect(4, 5, 3, 3),
+    ] {
+        disp.fill_contiguous(&area, util::points(&area).map(color))
+            .unwrap();
+    }
+
+    disp.fill_solid(&rect(2, 3, 2, 2), Rgb565::RED).unwrap();
+    disp.fill_solid(&rect(-1, -1, 2, 9), Rgb565::GREEN).unwrap();
+    disp.fill_solid(&rect(4, 6, 4, 4), Rgb565::BLUE).unwrap();
+    disp.fill_solid(&rect(10, 0, 2, 2), Rgb565::WHITE).unwrap();
+}
+
+#[test]
+fn same_as_reorient() {
+    for orientation in Orientation::ALL {
+        let mut disp = OrientedFrameBuf::new(framebuf(), orientation);
+        let mut expected = Reorient::new(framebuf(), orientation);
+        assert_eq!(
+            disp.bounding_box(),
+            expected.bounding_box(),
+            "{:?}",
+            orientation
+        );
+
+        draw(&mut disp);
+        draw(&mut expected);
+        assert_eq!(
+            disp.into_inner().data,
+            expected.into_inner().data,
+            "{:?}",
+            orientation
+        );
+    }
+}
+
+#[test]
+fn clear() {
+    for orientation in Orientation::ALL {
+        let mut disp = OrientedFrameBuf::new(framebuf(), orientation);
+        disp.clear(Rgb565::CYAN).unwrap();
+        assert_eq!(disp.into_inner().data, [Rgb565::CYAN; WIDTH * HEIGHT]);
+    }
+}
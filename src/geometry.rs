@@ -0,0 +1,509 @@
+//! The coordinate mappings applied by the transforms, as plain functions.
+//!
+//! These map between logical coordinates, as seen by drawing code, and the
+//! physical coordinates of a display with bounding box `bounds`, for an
+//! [`Orientation`]. They're the same mappings as [`Reorient`](crate::Reorient)
+//! and the other orientation wrappers use, and can be used without a
+//! [`DrawTarget`](embedded_graphics_core::draw_target::DrawTarget), for example
+//! to map touch input back to logical coordinates or for layout. They're all
+//! `const`.
+//!
+//! The logical display always has its top left corner at the origin.
+//!
+//! [`Anchor`] maps the corners and edges used for alignment in the same way,
+//! and [`strips`] splits a logical area into the physical rows it covers.
+
+use crate::Orientation;
+use embedded_graphics_core::{
+    geometry::{Point, Size},
+    primitives::Rectangle,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Return the logical size of a display of physical `size`.
+pub const fn transform_size(orientation: Orientation, size: Size) -> Size {
+    if orientation.transpose {
+        Size {
+            width: size.height,
+            height: size.width,
+        }
+    } else {
+        size
+    }
+}
+
+/// Return the physical size of a display of logical `size`.
+pub const fn inverse_transform_size(orientation: Orientation, size: Size) -> Size {
+    // Transposing is its own inverse.
+    transform_size(orientation, size)
+}
+
+/// Map a logical point to the physical display.
+pub const fn transform_point(orientation: Orientation, bounds: Rectangle, p: Point) -> Point {
+    let size = transform_size(orientation, bounds.size);
+    let x = if orientation.flip_x {
+        size.width as i32 - 1 - p.x
+    } else {
+        p.x
+    };
+    let y = if orientation.flip_y {
+        size.height as i32 - 1 - p.y
+    } else {
+        p.y
+    };
+    let (x, y) = if orientation.transpose {
+        (y, x)
+    } else {
+        (x, y)
+    };
+
+    Point {
+        x: bounds.top_left.x + x,
+        y: bounds.top_left.y + y,
+    }
+}
+
+/// Map a point on the physical display to logical coordinates.
+pub const fn inverse_transform_point(
+    orientation: Orientation,
+    bounds: Rectangle,
+    p: Point,
+) -> Point {
+    let size = transform_size(orientation, bounds.size);
+    let (x, y) = (p.x - bounds.top_left.x, p.y - bounds.top_left.y);
+    let (x, y) = if orientation.transpose {
+        (y, x)
+    } else {
+        (x, y)
+    };
+
+    Point {
+        x: if orientation.flip_x {
+            size.width as i32 - 1 - x
+        } else {
+            x
+        },
+        y: if orientation.flip_y {
+            size.height as i32 - 1 - y
+        } else {
+            y
+        },
+    }
+}
+
+/// Map a logical rectangle to the physical display.
+pub const fn transform_rect(
+    orientation: Orientation,
+    bounds: Rectangle,
+    area: Rectangle,
+) -> Rectangle {
+    let size = transform_size(orientation, bounds.size);
+    let x = if orientation.flip_x {
+        size.width as i32 - area.top_left.x - area.size.width as i32
+    } else {
+        area.top_left.x
+    };
+    let y = if orientation.flip_y {
+        size.height as i32 - area.top_left.y - area.size.height as i32
+    } else {
+        area.top_left.y
+    };
+    let (x, y) = if orientation.transpose {
+        (y, x)
+    } else {
+        (x, y)
+    };
+
+    Rectangle {
+        top_left: Point {
+            x: bounds.top_left.x + x,
+            y: bounds.top_left.y + y,
+        },
+        size: inverse_transform_size(orientation, area.size),
+    }
+}
+
+/// Map a rectangle on the physical display to logical coordinates.
+pub const fn inverse_transform_rect(
+    orientation: Orientation,
+    bounds: Rectangle,
+    area: Rectangle,
+) -> Rectangle {
+    let size = transform_size(orientation, bounds.size);
+    let area_size = transform_size(orientation, area.size);
+    let (x, y) = (
+        area.top_left.x - bounds.top_left.x,
+        area.top_left.y - bounds.top_left.y,
+    );
+    let (x, y) = if orientation.transpose {
+        (y, x)
+    } else {
+        (x, y)
+    };
+
+    Rectangle {
+        top_left: Point {
+            x: if orientation.flip_x {
+                size.width as i32 - x - area_size.width as i32
+            } else {
+                x
+            },
+            y: if orientation.flip_y {
+                size.height as i32 - y - area_size.height as i32
+            } else {
+                y
+            },
+        },
+        size: area_size,
+    }
+}
+
+/// Shapes which can be mapped between logical and physical coordinates.
+///
+/// Sometimes it's cheaper to map a few shapes before drawing them directly on
+/// the physical display than to map every pixel they rasterize to. The
+/// `embedded-graphics` feature adds implementations for its [`Line`],
+/// [`Triangle`] and [`Circle`] primitives.
+///
+/// [`Line`]: embedded_graphics::primitives::Line
+/// [`Triangle`]: embedded_graphics::primitives::Triangle
+/// [`Circle`]: embedded_graphics::primitives::Circle
+pub trait TransformPrimitive: Sized {
+    /// Map a logical shape onto a physical display with bounding box `bounds`.
+    fn transform(&self, orientation: impl Into<Orientation>, bounds: Rectangle) -> Self;
+
+    /// Map a shape on a physical display with bounding box `bounds` to logical
+    /// coordinates.
+    fn inverse_transform(&self, orientation: impl Into<Orientation>, bounds: Rectangle) -> Self;
+}
+
+impl TransformPrimitive for Point {
+    fn transform(&self, orientation: impl Into<Orientation>, bounds: Rectangle) -> Self {
+        transform_point(orientation.into(), bounds, *self)
+    }
+
+    fn inverse_transform(&self, orientation: impl Into<Orientation>, bounds: Rectangle) -> Self {
+        inverse_transform_point(orientation.into(), bounds, *self)
+    }
+}
+
+impl TransformPrimitive for Rectangle {
+    fn transform(&self, orientation: impl Into<Orientation>, bounds: Rectangle) -> Self {
+        transform_rect(orientation.into(), bounds, *self)
+    }
+
+    fn inverse_transform(&self, orientation: impl Into<Orientation>, bounds: Rectangle) -> Self {
+        inverse_transform_rect(orientation.into(), bounds, *self)
+    }
+}
+
+#[cfg(any(test, feature = "embedded-graphics"))]
+mod primitives {
+    use super::*;
+    use embedded_graphics::primitives::{Circle, Line, Triangle};
+
+    impl TransformPrimitive for Line {
+        fn transform(&self, orientation: impl Into<Orientation>, bounds: Rectangle) -> Self {
+            let orientation = orientation.into();
+            Line::new(
+                transform_point(orientation, bounds, self.start),
+                transform_point(orientation, bounds, self.end),
+            )
+        }
+
+        fn inverse_transform(
+            &self,
+            orientation: impl Into<Orientation>,
+            bounds: Rectangle,
+        ) -> Self {
+            let orientation = orientation.into();
+            Line::new(
+                inverse_transform_point(orientation, bounds, self.start),
+                inverse_transform_point(orientation, bounds, self.end),
+            )
+        }
+    }
+
+    impl TransformPrimitive for Triangle {
+        fn transform(&self, orientation: impl Into<Orientation>, bounds: Rectangle) -> Self {
+            let orientation = orientation.into();
+            let [a, b, c] = self
+                .vertices
+                .map(|p| transform_point(orientation, bounds, p));
+            Triangle::new(a, b, c)
+        }
+
+        fn inverse_transform(
+            &self,
+            orientation: impl Into<Orientation>,
+            bounds: Rectangle,
+        ) -> Self {
+            let orientation = orientation.into();
+            let [a, b, c] = self
+                .vertices
+                .map(|p| inverse_transform_point(orientation, bounds, p));
+            Triangle::new(a, b, c)
+        }
+    }
+
+    impl TransformPrimitive for Circle {
+        fn transform(&self, orientation: impl Into<Orientation>, bounds: Rectangle) -> Self {
+            let area = Rectangle::new(self.top_left, Size::new_equal(self.diameter));
+            Circle::new(
+                transform_rect(orientation.into(), bounds, area).top_left,
+                self.diameter,
+            )
+        }
+
+        fn inverse_transform(
+            &self,
+            orientation: impl Into<Orientation>,
+            bounds: Rectangle,
+        ) -> Self {
+            let area = Rectangle::new(self.top_left, Size::new_equal(self.diameter));
+            Circle::new(
+                inverse_transform_rect(orientation.into(), bounds, area).top_left,
+                self.diameter,
+            )
+        }
+    }
+}
+
+/// Map the logical points of a polyline onto a physical display with bounding
+/// box `bounds`, in place.
+pub fn transform_points(
+    orientation: impl Into<Orientation>,
+    bounds: Rectangle,
+    points: &mut [Point],
+) {
+    let orientation = orientation.into();
+    for p in points {
+        *p = transform_point(orientation, bounds, *p);
+    }
+}
+
+/// Map the points of a polyline on a physical display with bounding box
+/// `bounds` to logical coordinates, in place.
+pub fn inverse_transform_points(
+    orientation: impl Into<Orientation>,
+    bounds: Rectangle,
+    points: &mut [Point],
+) {
+    let orientation = orientation.into();
+    for p in points {
+        *p = inverse_transform_point(orientation, bounds, *p);
+    }
+}
+
+/// A run of pixels in one physical row, and where they come from on the
+/// logical display.
+///
+/// The logical pixels form a row or column run: the first is at `start`, and
+/// each following one is `step` further on, which is one pixel in one of the
+/// four directions.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Strip {
+    /// The physical pixels, one row high.
+    pub area: Rectangle,
+    /// The logical point of the strip's leftmost pixel.
+    pub start: Point,
+    /// The logical offset from each of the strip's pixels to the next.
+    pub step: Point,
+}
+
+impl Strip {
+    /// Return the logical point of the strip's `i`th pixel from the left.
+    pub const fn logical(&self, i: u32) -> Point {
+        Point::new(
+            self.start.x + self.step.x * i as i32,
+            self.start.y + self.step.y * i as i32,
+        )
+    }
+}
+
+/// Split a logical rectangle into the physical rows it covers.
+///
+/// This is the order a row-major display is written in, so it's what's needed
+/// to build DMA scatter lists from a logical framebuffer: each [`Strip`] is a
+/// contiguous run on the display, and says where its pixels are in the
+/// logical framebuffer. Depending on the orientation, that's a logical row or
+/// column, forwards or backwards. Strips are returned from the top of the
+/// display down.
+pub fn strips(orientation: impl Into<Orientation>, bounds: Rectangle, area: Rectangle) -> Strips {
+    let orientation = orientation.into();
+    let physical = transform_rect(orientation, bounds, area);
+    let start = inverse_transform_point(orientation, bounds, physical.top_left);
+    let next = inverse_transform_point(
+        orientation,
+        bounds,
+        Point::new(physical.top_left.x + 1, physical.top_left.y),
+    );
+    let down = inverse_transform_point(
+        orientation,
+        bounds,
+        Point::new(physical.top_left.x, physical.top_left.y + 1),
+    );
+
+    Strips {
+        physical,
+        start,
+        step: Point::new(next.x - start.x, next.y - start.y),
+        down: Point::new(down.x - start.x, down.y - start.y),
+        row: 0,
+    }
+}
+
+/// Iterator over the physical rows of a logical rectangle, returned by
+/// [`strips`].
+#[derive(Debug, Clone)]
+pub struct Strips {
+    physical: Rectangle,
+    start: Point,
+    step: Point,
+    down: Point,
+    row: u32,
+}
+
+impl Iterator for Strips {
+    type Item = Strip;
+
+    fn next(&mut self) -> Option<Strip> {
+        if self.row >= self.physical.size.height || self.physical.size.width == 0 {
+            return None;
+        }
+        let row = self.row as i32;
+        self.row += 1;
+
+        Some(Strip {
+            area: Rectangle::new(
+                Point::new(self.physical.top_left.x, self.physical.top_left.y + row),
+                Size::new(self.physical.size.width, 1),
+            ),
+            start: self.start + self.down * row,
+            step: self.step,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = if self.physical.size.width == 0 {
+            0
+        } else {
+            (self.physical.size.height - self.row) as usize
+        };
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Strips {}
+
+/// A point on the edge or center of a rectangle, for aligning things.
+///
+/// Because rotating or mirroring a display moves its corners and edges, an
+/// anchor on the logical display is a different anchor on the physical one,
+/// which is what [`transform`](Anchor::transform) returns. For example, the
+/// top left of a display rotated 90 degrees is the top right of the physical
+/// display.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Anchor {
+    /// The top left corner.
+    TopLeft,
+    /// The center of the top edge.
+    TopCenter,
+    /// The top right corner.
+    TopRight,
+    /// The center of the left edge.
+    CenterLeft,
+    /// The center.
+    Center,
+    /// The center of the right edge.
+    CenterRight,
+    /// The bottom left corner.
+    BottomLeft,
+    /// The center of the bottom edge.
+    BottomCenter,
+    /// The bottom right corner.
+    BottomRight,
+}
+
+impl Anchor {
+    /// The horizontal and vertical positions: -1 for the left or top, 0 for
+    /// the center and 1 for the right or bottom.
+    const fn parts(self) -> (i8, i8) {
+        match self {
+            Anchor::TopLeft => (-1, -1),
+            Anchor::TopCenter => (0, -1),
+            Anchor::TopRight => (1, -1),
+            Anchor::CenterLeft => (-1, 0),
+            Anchor::Center => (0, 0),
+            Anchor::CenterRight => (1, 0),
+            Anchor::BottomLeft => (-1, 1),
+            Anchor::BottomCenter => (0, 1),
+            Anchor::BottomRight => (1, 1),
+        }
+    }
+
+    const fn from_parts(h: i8, v: i8) -> Self {
+        match (h, v) {
+            (-1, -1) => Anchor::TopLeft,
+            (0, -1) => Anchor::TopCenter,
+            (1, -1) => Anchor::TopRight,
+            (-1, 0) => Anchor::CenterLeft,
+            (1, 0) => Anchor::CenterRight,
+            (-1, 1) => Anchor::BottomLeft,
+            (0, 1) => Anchor::BottomCenter,
+            (1, 1) => Anchor::BottomRight,
+            _ => Anchor::Center,
+        }
+    }
+
+    /// Return the point of `area` at this anchor.
+    ///
+    /// Centers are rounded towards the top left.
+    pub const fn point(self, area: &Rectangle) -> Point {
+        const fn offset(part: i8, len: u32) -> i32 {
+            match part {
+                -1 => 0,
+                0 => (len.saturating_sub(1) / 2) as i32,
+                _ => len.saturating_sub(1) as i32,
+            }
+        }
+
+        let (h, v) = self.parts();
+        Point {
+            x: area.top_left.x + offset(h, area.size.width),
+            y: area.top_left.y + offset(v, area.size.height),
+        }
+    }
+
+    /// Return the anchor of the physical display which this anchor of the
+    /// logical display is mapped to.
+    pub const fn transform(self, orientation: Orientation) -> Self {
+        let (h, v) = self.parts();
+        let h = if orientation.flip_x { -h } else { h };
+        let v = if orientation.flip_y { -v } else { v };
+        if orientation.transpose {
+            Anchor::from_parts(v, h)
+        } else {
+            Anchor::from_parts(h, v)
+        }
+    }
+
+    /// Return the anchor of the logical display which this anchor of the
+    /// physical display is mapped from.
+    pub const fn inverse_transform(self, orientation: Orientation) -> Self {
+        self.transform(orientation.inverse())
+    }
+
+    /// Return the translation which aligns `object`'s anchor point with
+    /// `reference`'s, for example to center `object` within `reference` with
+    /// [`Center`](Anchor::Center).
+    pub const fn align(self, object: &Rectangle, reference: &Rectangle) -> Point {
+        let (from, to) = (self.point(object), self.point(reference));
+        Point {
+            x: to.x - from.x,
+            y: to.y - from.y,
+        }
+    }
+}
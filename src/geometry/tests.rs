@@ -0,0 +1,256 @@
+use super::*;
+use crate::{testing::TransformTester, tests::rect, Reorient, Rotation, WithSize};
+
+const BOUNDS: Rectangle = Rectangle {
+    top_left: Point { x: 0, y: 0 },
+    size: Size {
+        width: 40,
+        height: 24,
+    },
+};
+
+#[test]
+fn logical_size() {
+    for orientation in Orientation::ALL {
+        let expected = if orientation.swaps_axes() {
+            Size::new(24, 40)
+        } else {
+            BOUNDS.size
+        };
+        assert_eq!(transform_size(orientation, BOUNDS.size), expected);
+    }
+    assert_eq!(
+        transform_size(Rotation::Rotate90.into(), Size::new(3, 1)),
+        Size::new(1, 3)
+    );
+}
+
+#[test]
+fn round_trip() {
+    let bounds = rect(5, -3, 40, 24);
+
+    for orientation in Orientation::ALL {
+        let logical = transform_size(orientation, bounds.size);
+        assert_eq!(inverse_transform_size(orientation, logical), bounds.size);
+
+        for p in [
+            Point::new(0, 0),
+            Point::new(7, 3),
+            Point::new(logical.width as i32 - 1, 2),
+        ] {
+            let physical = transform_point(orientation, bounds, p);
+            assert!(bounds.contains(physical), "{:?} {:?}", orientation, p);
+            assert_eq!(inverse_transform_point(orientation, bounds, physical), p);
+        }
+
+        let area = rect(2, 1, 5, 3);
+        let physical = transform_rect(orientation, bounds, area);
+        assert!(bounds.contains(physical.top_left));
+        assert_eq!(inverse_transform_rect(orientation, bounds, physical), area);
+    }
+}
+
+#[test]
+fn matches_reorient() {
+    for orientation in Orientation::ALL {
+        let mut tester = TransformTester::new(
+            |disp| Reorient::new(WithSize::new(disp, BOUNDS.size), orientation),
+            |target| target.into_inner().into_inner(),
+        );
+
+        for p in [Point::new(0, 0), Point::new(3, 11)] {
+            assert_eq!(
+                tester.map_point(p),
+                Some(transform_point(orientation, BOUNDS, p))
+            );
+        }
+    }
+}
+
+#[test]
+fn rect_corners() {
+    let r90 = Orientation::IDENTITY.rotate90();
+    let area = rect(1, 2, 3, 4);
+    let mapped = transform_rect(r90, BOUNDS, area);
+
+    // The mapped rectangle has the mapped corners
+    let corners = [
+        transform_point(r90, BOUNDS, area.top_left),
+        transform_point(r90, BOUNDS, Point::new(3, 5)),
+    ];
+    assert_eq!(mapped, Rectangle::with_corners(corners[0], corners[1]));
+}
+
+#[test]
+fn const_eval() {
+    const P: Point = transform_point(Orientation::IDENTITY.rotate90(), BOUNDS, Point::new(0, 0));
+    assert_eq!(P, Point::new(39, 0));
+}
+
+#[test]
+fn primitives() {
+    use crate::{Rotate90, Rotation};
+    use embedded_graphics::{
+        mock_display::MockDisplay,
+        pixelcolor::BinaryColor,
+        primitives::{Circle, Line, Primitive, PrimitiveStyle, Triangle},
+        Drawable,
+    };
+
+    let style = PrimitiveStyle::with_stroke(BinaryColor::On, 1);
+    let fill = PrimitiveStyle::with_fill(BinaryColor::On);
+    let bounds = rect(0, 0, 64, 64);
+
+    let line = Line::new(Point::new(1, 2), Point::new(20, 9));
+    let triangle = Triangle::new(Point::new(30, 1), Point::new(40, 12), Point::new(33, 20));
+    let circle = Circle::new(Point::new(5, 30), 11);
+    let area = rect(50, 40, 6, 3);
+
+    // Drawing the mapped shapes directly is the same as drawing through the
+    // transform
+    let mut expected = Rotate90::new(MockDisplay::new());
+    line.into_styled(style).draw(&mut expected).unwrap();
+    circle.into_styled(fill).draw(&mut expected).unwrap();
+    area.into_styled(fill).draw(&mut expected).unwrap();
+
+    let mut disp = MockDisplay::new();
+    let r = Rotation::Rotate90;
+    line.transform(r, bounds)
+        .into_styled(style)
+        .draw(&mut disp)
+        .unwrap();
+    circle
+        .transform(r, bounds)
+        .into_styled(fill)
+        .draw(&mut disp)
+        .unwrap();
+    area.transform(r, bounds)
+        .into_styled(fill)
+        .draw(&mut disp)
+        .unwrap();
+    disp.assert_eq(expected.as_ref());
+
+    // Triangles aren't necessarily rasterized identically when rotated, but
+    // their vertices are mapped
+    assert_eq!(
+        triangle.transform(r, bounds),
+        Triangle::new(Point::new(62, 30), Point::new(51, 40), Point::new(43, 33))
+    );
+
+    assert_eq!(line.transform(r, bounds).inverse_transform(r, bounds), line);
+    assert_eq!(
+        circle.transform(r, bounds).inverse_transform(r, bounds),
+        circle
+    );
+}
+
+#[test]
+fn polyline() {
+    let orientation = Orientation::IDENTITY.rotate180();
+    let mut points = [Point::new(0, 0), Point::new(5, 2), Point::new(39, 23)];
+    transform_points(orientation, BOUNDS, &mut points);
+    assert_eq!(
+        points,
+        [Point::new(39, 23), Point::new(34, 21), Point::new(0, 0)]
+    );
+    inverse_transform_points(orientation, BOUNDS, &mut points);
+    assert_eq!(
+        points,
+        [Point::new(0, 0), Point::new(5, 2), Point::new(39, 23)]
+    );
+}
+
+#[test]
+fn anchors() {
+    use Anchor::*;
+
+    const ALL: [Anchor; 9] = [
+        TopLeft,
+        TopCenter,
+        TopRight,
+        CenterLeft,
+        Center,
+        CenterRight,
+        BottomLeft,
+        BottomCenter,
+        BottomRight,
+    ];
+
+    // Odd sizes, so that centers are exact
+    let bounds = rect(2, 3, 41, 25);
+
+    for orientation in Orientation::ALL {
+        let logical = Rectangle::new(Point::zero(), transform_size(orientation, bounds.size));
+
+        for anchor in ALL {
+            let physical = anchor.transform(orientation);
+            assert_eq!(
+                transform_point(orientation, bounds, anchor.point(&logical)),
+                physical.point(&bounds),
+                "{} {:?}",
+                orientation,
+                anchor
+            );
+            assert_eq!(physical.inverse_transform(orientation), anchor);
+        }
+    }
+
+    assert_eq!(
+        TopLeft.transform(Orientation::IDENTITY.rotate90()),
+        TopRight
+    );
+    assert_eq!(
+        CenterLeft.transform(Orientation::IDENTITY.rotate270()),
+        BottomCenter
+    );
+
+    let reference = rect(0, 0, 64, 32);
+    assert_eq!(
+        Center.align(&rect(5, 5, 10, 4), &reference),
+        Point::new(22, 9)
+    );
+    assert_eq!(
+        BottomRight.align(&rect(0, 0, 10, 4), &reference),
+        Point::new(54, 28)
+    );
+}
+
+#[test]
+fn physical_strips() {
+    let bounds = rect(5, -3, 40, 24);
+    let area = rect(2, 1, 5, 3);
+
+    for orientation in Orientation::ALL {
+        let physical = transform_rect(orientation, bounds, area);
+        let strips = strips(orientation, bounds, area);
+        assert_eq!(strips.len(), physical.size.height as usize);
+
+        let mut y = physical.top_left.y;
+        for strip in strips {
+            assert_eq!(strip.area.top_left, Point::new(physical.top_left.x, y));
+            assert_eq!(strip.area.size, Size::new(physical.size.width, 1));
+            assert_eq!(strip.step.x.abs() + strip.step.y.abs(), 1);
+            for i in 0..strip.area.size.width {
+                let p = strip.area.top_left + Point::new(i as i32, 0);
+                assert_eq!(
+                    strip.logical(i),
+                    inverse_transform_point(orientation, bounds, p),
+                    "{} {:?}",
+                    orientation,
+                    p
+                );
+            }
+            y += 1;
+        }
+        assert_eq!(y, physical.top_left.y + physical.size.height as i32);
+    }
+
+    // A quarter turn makes each physical row a logical column, read upwards
+    let strip = strips(Rotation::Rotate90, BOUNDS, area).next().unwrap();
+    assert_eq!(strip.step, Point::new(0, -1));
+
+    assert_eq!(
+        strips(Orientation::IDENTITY, BOUNDS, rect(1, 1, 0, 3)).count(),
+        0
+    );
+}
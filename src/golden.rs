@@ -0,0 +1,97 @@
+//! Compare display contents against golden images.
+
+use crate::{util, ReadPixel};
+use embedded_graphics_core::{pixelcolor::Rgb888, prelude::*};
+use std::{env, fs, path::Path, vec::Vec};
+
+#[cfg(test)]
+mod tests;
+
+/// Size of the BMP file header and info header.
+const HEADER_SIZE: u32 = 14 + 40;
+
+/// Encode the contents of a display as an uncompressed 24-bit BMP image.
+///
+/// Pixels for which [`read_pixel`](ReadPixel::read_pixel) returns `None` are
+/// black.
+pub fn encode_bmp<D>(display: &D) -> Vec<u8>
+where
+    D: ReadPixel,
+    D::Color: Into<Rgb888>,
+{
+    let area = display.bounding_box();
+    let Size { width, height } = area.size;
+    // Rows are padded to a multiple of 4 bytes.
+    let stride = (width * 3 + 3) & !3;
+    let image_size = stride * height;
+
+    let mut bmp = Vec::with_capacity((HEADER_SIZE + image_size) as usize);
+    bmp.extend_from_slice(b"BM");
+    bmp.extend_from_slice(&(HEADER_SIZE + image_size).to_le_bytes());
+    bmp.extend_from_slice(&[0; 4]);
+    bmp.extend_from_slice(&HEADER_SIZE.to_le_bytes());
+
+    bmp.extend_from_slice(&40u32.to_le_bytes());
+    bmp.extend_from_slice(&width.to_le_bytes());
+    // Negative height for rows stored top to bottom.
+    bmp.extend_from_slice(&(-(height as i32)).to_le_bytes());
+    bmp.extend_from_slice(&1u16.to_le_bytes());
+    bmp.extend_from_slice(&24u16.to_le_bytes());
+    bmp.extend_from_slice(&[0; 4]); // uncompressed
+    bmp.extend_from_slice(&image_size.to_le_bytes());
+    bmp.extend_from_slice(&2835u32.to_le_bytes()); // 72 dpi
+    bmp.extend_from_slice(&2835u32.to_le_bytes());
+    bmp.extend_from_slice(&[0; 8]);
+
+    let padding = (stride - width * 3) as usize;
+    for (i, p) in util::points(&area).enumerate() {
+        let color = display.read_pixel(p).map_or(Rgb888::BLACK, Into::into);
+        bmp.extend_from_slice(&[color.b(), color.g(), color.r()]);
+        if (i + 1) % width as usize == 0 {
+            bmp.resize(bmp.len() + padding, 0);
+        }
+    }
+    bmp
+}
+
+/// Assert that the contents of a display match a golden BMP image.
+///
+/// If the image at `path` doesn't exist yet, or the `UPDATE_GOLDEN`
+/// environment variable is set, it's written from the display's contents
+/// instead, so it can be reviewed and committed. Otherwise, on mismatch the
+/// display's contents are written next to it with an `.actual.bmp` extension
+/// for comparison.
+///
+/// This is available with the `std` feature.
+///
+/// # Panics
+///
+/// Panics if the display doesn't match the golden image, or if the image can't
+/// be read or written.
+#[track_caller]
+pub fn assert_golden<D>(display: &D, path: impl AsRef<Path>)
+where
+    D: ReadPixel,
+    D::Color: Into<Rgb888>,
+{
+    let path = path.as_ref();
+    let actual = encode_bmp(display);
+
+    if env::var_os("UPDATE_GOLDEN").is_some() || !path.exists() {
+        fs::write(path, &actual)
+            .unwrap_or_else(|err| panic!("writing {}: {}", path.display(), err));
+        return;
+    }
+
+    let golden = fs::read(path).unwrap_or_else(|err| panic!("reading {}: {}", path.display(), err));
+    if golden != actual {
+        let actual_path = path.with_extension("actual.bmp");
+        fs::write(&actual_path, &actual)
+            .unwrap_or_else(|err| panic!("writing {}: {}", actual_path.display(), err));
+        panic!(
+            "display doesn't match {}, see {}",
+            path.display(),
+            actual_path.display()
+        );
+    }
+}
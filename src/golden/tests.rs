@@ -0,0 +1,52 @@
+use super::*;
+use crate::Rotate90;
+use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+use std::{format, path::PathBuf};
+
+fn golden_path(name: &str) -> PathBuf {
+    let path = env::temp_dir().join(format!("eg-transform-{}-{}.bmp", name, std::process::id()));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+#[test]
+fn bmp() {
+    let mut disp = MockDisplay::<Rgb888>::new();
+    disp.draw_iter([
+        Pixel(Point::new(0, 0), Rgb888::RED),
+        Pixel(Point::new(63, 63), Rgb888::new(1, 2, 3)),
+    ])
+    .unwrap();
+
+    let bmp = encode_bmp(&disp);
+    assert_eq!(bmp.len(), 54 + 64 * 64 * 3);
+    assert_eq!(&bmp[..2], b"BM");
+    assert_eq!(&bmp[54..57], [0, 0, 255]);
+    assert_eq!(&bmp[bmp.len() - 3..], [3, 2, 1]);
+}
+
+#[test]
+fn golden() {
+    let path = golden_path("golden");
+    let draw = |p| {
+        let mut disp = Rotate90::new(MockDisplay::<BinaryColor>::new());
+        disp.draw_iter([Pixel(p, BinaryColor::On)]).unwrap();
+        disp.into_inner()
+    };
+
+    // Created on first use
+    assert_golden(&draw(Point::new(1, 2)), &path);
+    assert!(path.exists());
+    assert_golden(&draw(Point::new(1, 2)), &path);
+
+    let res = std::panic::catch_unwind(|| assert_golden(&draw(Point::new(2, 1)), &path));
+    assert!(res.is_err());
+    let actual = path.with_extension("actual.bmp");
+    assert_eq!(
+        fs::read(&actual).unwrap(),
+        encode_bmp(&draw(Point::new(2, 1)))
+    );
+
+    fs::remove_file(&path).unwrap();
+    fs::remove_file(&actual).unwrap();
+}
@@ -0,0 +1,226 @@
+//! Dim or blank a display when it's not being used.
+
+use crate::Brightness;
+use core::ops::{Deref, DerefMut};
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+
+#[cfg(test)]
+mod tests;
+
+/// A source of the time, for [`Idle`] and [`Budget`](crate::Budget).
+///
+/// This is typically backed by a hardware timer or a system tick counter. It's
+/// implemented for closures returning milliseconds as a `u32`.
+pub trait Clock {
+    /// Return the time in milliseconds since some fixed point, wrapping around
+    /// on overflow.
+    fn millis(&mut self) -> u32;
+
+    /// Return the time in microseconds since some fixed point, wrapping around
+    /// on overflow.
+    ///
+    /// By default this is [`millis`](Self::millis) scaled, so it only has
+    /// millisecond resolution; clocks with a finer one should override it.
+    fn micros(&mut self) -> u32 {
+        self.millis().wrapping_mul(1000)
+    }
+}
+
+impl<F: FnMut() -> u32> Clock for F {
+    fn millis(&mut self) -> u32 {
+        self()
+    }
+}
+
+/// The state of an [`Idle`] display.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum IdleState {
+    /// In use, at full brightness.
+    Active,
+    /// Dimmed after a period without activity.
+    Dimmed,
+    /// Blanked after a longer period without activity.
+    Blanked,
+}
+
+/// Dim and then blank a display after a period of inactivity.
+///
+/// The application reports input, such as a button press or touch, with
+/// [`activity`](Self::activity), and calls [`update`](Self::update)
+/// periodically, such as once per frame, to check the [`Clock`]. When nothing
+/// has happened for the dimming period, colors are scaled by a dimmed
+/// brightness, as with [`Brightness`], and optionally after a longer period
+/// they're all drawn black. Activity restores full brightness.
+///
+/// Like [`AutoRotate`](crate::AutoRotate), the state only changes in
+/// [`update`](Self::update) and [`activity`](Self::activity), and a change
+/// sets a flag, which the application checks with
+/// [`take_changed`](Self::take_changed) to know that it needs to redraw
+/// everything at the new brightness.
+pub struct Idle<D, K> {
+    target: Brightness<D>,
+    clock: K,
+    last_activity: u32,
+    dim_after: u32,
+    dimmed: u8,
+    blank_after: Option<u32>,
+    state: IdleState,
+    changed: bool,
+}
+
+impl<D, K: Clock> Idle<D, K> {
+    /// Dim a display to `dimmed` brightness, from 0 to 255, after `dim_after`
+    /// milliseconds without activity.
+    ///
+    /// The display starts active, as if there had just been activity.
+    pub fn new(target: D, mut clock: K, dim_after: u32, dimmed: u8) -> Self {
+        Idle {
+            target: Brightness::new(target, 255),
+            last_activity: clock.millis(),
+            clock,
+            dim_after,
+            dimmed,
+            blank_after: None,
+            state: IdleState::Active,
+            changed: false,
+        }
+    }
+
+    /// Also blank the display after `blank_after` milliseconds without
+    /// activity.
+    pub fn with_blank_after(mut self, blank_after: u32) -> Self {
+        self.blank_after = Some(blank_after);
+        self
+    }
+
+    /// Report activity, restoring full brightness. Returns true if the
+    /// display was dimmed or blanked, for example so that the input which
+    /// woke it can be ignored.
+    pub fn activity(&mut self) -> bool {
+        self.last_activity = self.clock.millis();
+        self.set_state(IdleState::Active)
+    }
+
+    /// Check the clock and dim or blank the display if it's been inactive for
+    /// long enough, returning true if the state changed.
+    pub fn update(&mut self) -> bool {
+        let idle = self.clock.millis().wrapping_sub(self.last_activity);
+        let state = if self.blank_after.is_some_and(|blank| idle >= blank) {
+            IdleState::Blanked
+        } else if idle >= self.dim_after {
+            IdleState::Dimmed
+        } else {
+            IdleState::Active
+        };
+
+        // Only ever move towards blank, so that the clock wrapping around
+        // doesn't wake the display.
+        state > self.state && self.set_state(state)
+    }
+
+    fn set_state(&mut self, state: IdleState) -> bool {
+        if state == self.state {
+            return false;
+        }
+
+        self.state = state;
+        self.target.set_brightness(match state {
+            IdleState::Active => 255,
+            IdleState::Dimmed => self.dimmed,
+            IdleState::Blanked => 0,
+        });
+        self.changed = true;
+        true
+    }
+}
+
+impl<D, K> Idle<D, K> {
+    /// Return the current state.
+    pub fn state(&self) -> IdleState {
+        self.state
+    }
+
+    /// Return true if the state has changed since this was last called.
+    pub fn take_changed(&mut self) -> bool {
+        core::mem::take(&mut self.changed)
+    }
+
+    /// Get a reference to the clock.
+    pub fn clock(&self) -> &K {
+        &self.clock
+    }
+
+    /// Get a mutable reference to the clock.
+    pub fn clock_mut(&mut self) -> &mut K {
+        &mut self.clock
+    }
+
+    /// Recover the inner display instance.
+    pub fn into_inner(self) -> D {
+        self.target.into_inner()
+    }
+}
+
+impl<D, K> Deref for Idle<D, K> {
+    type Target = D;
+
+    fn deref(&self) -> &D {
+        self.as_ref()
+    }
+}
+
+impl<D, K> DerefMut for Idle<D, K> {
+    fn deref_mut(&mut self) -> &mut D {
+        self.as_mut()
+    }
+}
+
+impl<D, K> AsRef<D> for Idle<D, K> {
+    fn as_ref(&self) -> &D {
+        self.target.as_ref()
+    }
+}
+
+impl<D, K> AsMut<D> for Idle<D, K> {
+    fn as_mut(&mut self) -> &mut D {
+        self.target.as_mut()
+    }
+}
+
+impl_by_mut_ref!([D, K] Idle<D, K>);
+
+impl<D: Dimensions, K> Dimensions for Idle<D, K> {
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+impl<D: DrawTarget, K> DrawTarget for Idle<D, K>
+where
+    D::Color: crate::ColorChannels,
+{
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.target.draw_iter(pixels)
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        self.target.fill_contiguous(area, colors)
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.target.fill_solid(area, color)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.target.clear(color)
+    }
+}
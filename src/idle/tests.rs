@@ -0,0 +1,58 @@
+use super::*;
+use core::cell::Cell;
+use embedded_graphics::{mock_display::MockDisplay, pixelcolor::Gray8};
+
+#[test]
+fn dim_and_blank() {
+    let now = Cell::new(1000u32);
+    let mut disp = Idle::new(MockDisplay::new(), || now.get(), 500, 64).with_blank_after(2000);
+    disp.as_mut().set_allow_overdraw(true);
+    let pixel = |disp: &mut Idle<MockDisplay<Gray8>, _>| {
+        disp.draw_iter([Pixel(Point::zero(), Gray8::WHITE)])
+            .unwrap();
+        disp.get_pixel(Point::zero()).unwrap().luma()
+    };
+
+    assert_eq!(disp.state(), IdleState::Active);
+    now.set(1499);
+    assert!(!disp.update());
+    assert_eq!(pixel(&mut disp), 255);
+
+    now.set(1500);
+    assert!(disp.update());
+    assert_eq!(disp.state(), IdleState::Dimmed);
+    assert!(disp.take_changed());
+    assert!(!disp.take_changed());
+    assert_eq!(pixel(&mut disp), 64);
+
+    now.set(3000);
+    assert!(disp.update());
+    assert_eq!(disp.state(), IdleState::Blanked);
+    assert_eq!(pixel(&mut disp), 0);
+
+    // Activity wakes it, and restarts the timer.
+    assert!(disp.activity());
+    assert!(!disp.activity());
+    assert!(disp.take_changed());
+    assert_eq!(disp.state(), IdleState::Active);
+    assert_eq!(pixel(&mut disp), 255);
+    now.set(3400);
+    assert!(!disp.update());
+}
+
+#[test]
+fn clock_wraps() {
+    let now = Cell::new(u32::MAX - 100);
+    let mut disp = Idle::new(MockDisplay::<Gray8>::new(), || now.get(), 500, 64);
+
+    now.set(300);
+    assert!(!disp.update());
+    now.set(400);
+    assert!(disp.update());
+    assert_eq!(disp.state(), IdleState::Dimmed);
+
+    // Without blanking, it stays dimmed even when the idle time wraps around.
+    now.set(200);
+    assert!(!disp.update());
+    assert_eq!(disp.state(), IdleState::Dimmed);
+}
@@ -0,0 +1,53 @@
+//! Draw images through a transform in the display's own order.
+
+use crate::{geometry, scoped::ByRef, util, Offset, Orientation};
+use embedded_graphics_core::{image::ImageDrawable, prelude::*, primitives::Rectangle};
+
+#[cfg(test)]
+mod tests;
+
+/// Draw `image` with its top left at `top_left` on `target`, a display in
+/// `orientation`, one physical row at a time.
+///
+/// Each physical row is a logical row or column of the image, which is drawn
+/// as a sub-image, so it reaches `target`'s `fill_contiguous` as a single row
+/// or column which it can forward as a contiguous run. Only the part of the
+/// image on the display is drawn.
+pub(crate) fn draw_image<D, I>(
+    target: &mut D,
+    orientation: Orientation,
+    image: &I,
+    top_left: Point,
+) -> Result<(), D::Error>
+where
+    D: DrawTarget,
+    I: ImageDrawable<Color = D::Color>,
+{
+    let logical = target.bounding_box();
+    let area = Rectangle::new(top_left, image.size()).intersection(&logical);
+    if util::is_empty(&area) {
+        return Ok(());
+    }
+
+    if orientation == Orientation::IDENTITY {
+        return image.draw_sub_image(
+            &mut Offset::new(ByRef(target), area.top_left),
+            &util::translate(&area, -top_left),
+        );
+    }
+
+    let bounds = Rectangle::new(
+        Point::zero(),
+        geometry::inverse_transform_size(orientation, logical.size),
+    );
+    for strip in geometry::strips(orientation, bounds, area) {
+        let end = strip.logical(strip.area.size.width - 1);
+        let run = Rectangle::with_corners(strip.start, end);
+        image.draw_sub_image(
+            &mut Offset::new(ByRef(target), run.top_left),
+            &util::translate(&run, -top_left),
+        )?;
+    }
+
+    Ok(())
+}
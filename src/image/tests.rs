@@ -0,0 +1,51 @@
+use crate::{debug::Instrument, Orientation, Reorient, Rotate90};
+use embedded_graphics::{
+    image::{Image, ImageRaw},
+    mock_display::MockDisplay,
+    pixelcolor::{raw::BigEndian, Rgb565},
+    prelude::*,
+};
+
+/// A 16x10 image with a different color for each pixel.
+fn data() -> [u8; 320] {
+    let mut data = [0; 320];
+    for (i, px) in data.chunks_mut(2).enumerate() {
+        px.copy_from_slice(&(i as u16 * 397).to_be_bytes());
+    }
+    data
+}
+
+#[test]
+fn same_as_image() {
+    let data = data();
+    let raw = ImageRaw::<Rgb565, BigEndian>::new(&data, 16);
+
+    for orientation in Orientation::ALL {
+        for top_left in [Point::new(3, 5), Point::new(-4, 58), Point::new(60, -2)] {
+            let mut expected = Reorient::new(MockDisplay::new(), orientation);
+            expected.set_allow_out_of_bounds_drawing(true);
+            Image::new(&raw, top_left).draw(&mut expected).unwrap();
+
+            let mut disp = Reorient::new(MockDisplay::new(), orientation);
+            disp.set_allow_out_of_bounds_drawing(true);
+            disp.draw_image(&raw, top_left).unwrap();
+
+            disp.assert_eq_with_message(&expected, |f| {
+                write!(f, "{} at {:?}", orientation, top_left)
+            });
+        }
+    }
+}
+
+#[test]
+fn contiguous_rows() {
+    let data = data();
+    let raw = ImageRaw::<Rgb565, BigEndian>::new(&data, 16);
+
+    // Each of the image's columns is a single fill of a display row
+    let mut disp = Rotate90::new(Instrument::new(MockDisplay::new()));
+    disp.draw_image(&raw, Point::new(3, 5)).unwrap();
+    assert_eq!(disp.stats().calls, 16);
+    assert_eq!(disp.stats().fills, 16);
+    assert_eq!(disp.stats().pixels, 160);
+}
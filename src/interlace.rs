@@ -0,0 +1,128 @@
+//! Draw only alternate rows of a display.
+
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+
+#[cfg(test)]
+mod tests;
+
+/// Which set of rows an [`Interlace`] draws.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Field {
+    /// Even rows (0, 2, 4, ...)
+    Even,
+    /// Odd rows (1, 3, 5, ...)
+    Odd,
+}
+
+impl Field {
+    /// The other field.
+    pub fn other(self) -> Self {
+        match self {
+            Field::Even => Field::Odd,
+            Field::Odd => Field::Even,
+        }
+    }
+
+    fn contains(self, y: i32) -> bool {
+        (y.rem_euclid(2) == 0) == (self == Field::Even)
+    }
+}
+
+/// Only forward drawing to even or odd rows.
+///
+/// This is useful for interlaced panels, or to halve the amount of data sent
+/// over a slow bus by updating alternate fields on each frame. Rows are
+/// numbered in the wrapped display's coordinates, and anything drawn to the
+/// other field is discarded. Use [`next_field`](Self::next_field) to switch
+/// fields between frames.
+pub struct Interlace<D> {
+    target: D,
+    field: Field,
+}
+
+impl<D> Interlace<D> {
+    /// Draw only the rows in `field`.
+    pub fn new(target: D, field: Field) -> Self {
+        Interlace { target, field }
+    }
+
+    /// Return the field currently being drawn.
+    pub fn field(&self) -> Field {
+        self.field
+    }
+
+    /// Set the field to draw.
+    pub fn set_field(&mut self, field: Field) {
+        self.field = field;
+    }
+
+    /// Switch to the other field, typically at the start of a frame.
+    pub fn next_field(&mut self) {
+        self.field = self.field.other();
+    }
+}
+
+impl_wrapper!(Interlace<D>);
+
+impl<D: Dimensions> Dimensions for Interlace<D> {
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+impl<D: DrawTarget> DrawTarget for Interlace<D> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let field = self.field;
+        self.target.draw_iter(
+            pixels
+                .into_iter()
+                .filter(|Pixel(loc, _)| field.contains(loc.y)),
+        )
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let width = area.size.width as usize;
+        if width == 0 {
+            return Ok(());
+        }
+
+        let mut colors = colors.into_iter();
+        for y in 0..area.size.height as i32 {
+            let row = Rectangle::new(
+                area.top_left + Point::new(0, y),
+                Size::new(area.size.width, 1),
+            );
+
+            if self.field.contains(row.top_left.y) {
+                self.target
+                    .fill_contiguous(&row, colors.by_ref().take(width))?;
+            } else if colors.nth(width - 1).is_none() {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        for y in 0..area.size.height as i32 {
+            let row = Rectangle::new(
+                area.top_left + Point::new(0, y),
+                Size::new(area.size.width, 1),
+            );
+
+            if self.field.contains(row.top_left.y) {
+                self.target.fill_solid(&row, color)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use super::*;
+use crate::tests::rect;
+use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+#[test]
+fn interlace_fill_solid() {
+    let mut disp = Interlace::new(MockDisplay::<BinaryColor>::new(), Field::Even);
+    assert_eq!(disp.bounding_box(), rect(0, 0, 64, 64));
+
+    disp.fill_solid(&rect(1, 1, 3, 4), BinaryColor::On).unwrap();
+    disp.next_field();
+    assert_eq!(disp.field(), Field::Odd);
+    disp.fill_solid(&rect(5, 1, 2, 3), BinaryColor::On).unwrap();
+
+    disp.as_ref()
+        .assert_pattern(&["       ", "     ##", " ###   ", "     ##", " ###   "]);
+}
+
+#[test]
+fn interlace_fill_contiguous() {
+    use BinaryColor::{Off, On};
+
+    let mut disp = Interlace::new(MockDisplay::<BinaryColor>::new(), Field::Odd);
+    disp.fill_contiguous(
+        &rect(0, 0, 2, 4),
+        [On, On, On, Off, Off, On, Off, Off, On, On],
+    )
+    .unwrap();
+    disp.draw_iter([Pixel(Point::new(3, 0), On), Pixel(Point::new(3, 1), On)])
+        .unwrap();
+
+    disp.as_ref().assert_pattern(&[
+        "    ", //
+        "#. #", "    ", "..  ",
+    ]);
+}
@@ -0,0 +1,151 @@
+//! Replicate drawing symmetrically across the display.
+
+use crate::util;
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+
+#[cfg(test)]
+mod tests;
+
+/// Kind of symmetry applied by a [`Kaleidoscope`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Symmetry {
+    /// Mirror the top left quadrant horizontally and vertically into the other
+    /// three quadrants.
+    FourFold,
+    /// As well as mirroring into each quadrant, mirror the top right octant of
+    /// the quadrant along its diagonal. This requires a square display.
+    EightFold,
+}
+
+/// Replicate the top left quadrant of the display into the other quadrants.
+///
+/// The logical display is the top left quadrant (including the center row and
+/// column, if the display has an odd size), and everything drawn in it is
+/// mirrored into the other three quadrants. With [`Symmetry::EightFold`] each
+/// pixel is also mirrored along the diagonal, so the octant on and above the
+/// diagonal (where x ≥ y) defines the whole display, and anything drawn below
+/// the diagonal is discarded.
+///
+/// Pixels on the lines of symmetry are only drawn once.
+pub struct Kaleidoscope<D> {
+    target: D,
+    symmetry: Symmetry,
+}
+
+impl<D> Kaleidoscope<D> {
+    /// Replicate drawing with the given symmetry.
+    pub fn new(target: D, symmetry: Symmetry) -> Self {
+        Kaleidoscope { target, symmetry }
+    }
+}
+
+/// All the distinct images of `p` on a display of `size`.
+fn images(symmetry: Symmetry, size: Size, p: Point) -> impl Iterator<Item = Point> {
+    let (w, h) = (size.width as i32 - 1, size.height as i32 - 1);
+
+    let transposed = Point::new(p.y, p.x);
+    let bases = [
+        Some(p),
+        Some(transposed).filter(|t| symmetry == Symmetry::EightFold && *t != p),
+    ];
+
+    bases.into_iter().flatten().flat_map(move |q| {
+        let mx = Point::new(w - q.x, q.y);
+        let my = Point::new(q.x, h - q.y);
+        let mxy = Point::new(w - q.x, h - q.y);
+        [
+            Some(q),
+            Some(mx).filter(|_| mx.x != q.x),
+            Some(my).filter(|_| my.y != q.y),
+            Some(mxy).filter(|_| mxy.x != q.x && mxy.y != q.y),
+        ]
+        .into_iter()
+        .flatten()
+    })
+}
+
+impl_wrapper!(Kaleidoscope<D>);
+
+impl<D: Dimensions> Dimensions for Kaleidoscope<D> {
+    fn bounding_box(&self) -> Rectangle {
+        let size = self.target.bounding_box().size;
+        Rectangle::new(
+            Point::zero(),
+            Size::new(size.width.div_ceil(2), size.height.div_ceil(2)),
+        )
+    }
+}
+
+impl<D: DrawTarget> DrawTarget for Kaleidoscope<D> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let quadrant = self.bounding_box();
+        let size = self.target.bounding_box().size;
+        let symmetry = self.symmetry;
+
+        self.target.draw_iter(
+            pixels
+                .into_iter()
+                .filter(|Pixel(loc, _)| {
+                    quadrant.contains(*loc) && (symmetry == Symmetry::FourFold || loc.x >= loc.y)
+                })
+                .flat_map(|Pixel(loc, col)| {
+                    images(symmetry, size, loc).map(move |loc| Pixel(loc, col))
+                }),
+        )
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = area.intersection(&self.bounding_box());
+
+        if self.symmetry == Symmetry::EightFold {
+            return self.draw_iter(util::points(&area).map(|loc| Pixel(loc, color)));
+        }
+
+        let size = self.target.bounding_box().size;
+        let (w, h) = (size.width as i32 - 1, size.height as i32 - 1);
+
+        // Only the columns and rows which aren't on the center line are
+        // mirrored.
+        let mirrorable = |width: u32, height: u32| {
+            area.intersection(&Rectangle::new(Point::zero(), Size::new(width, height)))
+        };
+        let mirror = |area: Rectangle, mx: bool, my: bool| {
+            let x = if mx {
+                w - area.top_left.x - area.size.width as i32 + 1
+            } else {
+                area.top_left.x
+            };
+            let y = if my {
+                h - area.top_left.y - area.size.height as i32 + 1
+            } else {
+                area.top_left.y
+            };
+            Rectangle::new(Point::new(x, y), area.size)
+        };
+
+        let (half_w, half_h) = (size.width / 2, size.height / 2);
+        let parts = [
+            area,
+            mirror(mirrorable(half_w, size.height), true, false),
+            mirror(mirrorable(size.width, half_h), false, true),
+            mirror(mirrorable(half_w, half_h), true, true),
+        ];
+
+        for part in parts.iter().filter(|part| !util::is_empty(part)) {
+            self.target.fill_solid(part, color)?;
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.target.clear(color)
+    }
+}
@@ -0,0 +1,53 @@
+use super::*;
+use crate::tests::rect;
+use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+#[test]
+fn images_dedup() {
+    let size = Size::new(5, 5);
+    let count = |symmetry, p| images(symmetry, size, p).count();
+
+    assert_eq!(count(Symmetry::FourFold, Point::new(0, 1)), 4);
+    assert_eq!(count(Symmetry::FourFold, Point::new(2, 1)), 2);
+    assert_eq!(count(Symmetry::FourFold, Point::new(2, 2)), 1);
+    assert_eq!(count(Symmetry::EightFold, Point::new(0, 1)), 8);
+    assert_eq!(count(Symmetry::EightFold, Point::new(1, 1)), 4);
+    assert_eq!(count(Symmetry::EightFold, Point::new(2, 0)), 4);
+}
+
+#[test]
+fn four_fold() {
+    let mut disp = Kaleidoscope::new(MockDisplay::<BinaryColor>::new(), Symmetry::FourFold);
+    assert_eq!(disp.bounding_box(), rect(0, 0, 32, 32));
+
+    disp.draw_iter([
+        Pixel(Point::new(1, 2), BinaryColor::On),
+        // Outside the quadrant
+        Pixel(Point::new(40, 2), BinaryColor::On),
+    ])
+    .unwrap();
+    let mut expected = MockDisplay::new();
+    for p in [(1, 2), (62, 2), (1, 61), (62, 61)] {
+        expected.set_pixel(Point::new(p.0, p.1), Some(BinaryColor::On));
+    }
+    disp.assert_eq(&expected);
+
+    let mut disp = Kaleidoscope::new(MockDisplay::<BinaryColor>::new(), Symmetry::FourFold);
+    disp.fill_solid(&rect(30, 0, 10, 1), BinaryColor::On)
+        .unwrap();
+    assert_eq!(disp.affected_area(), rect(30, 0, 4, 64));
+    for x in 30..34 {
+        assert_eq!(disp.get_pixel(Point::new(x, 0)), Some(BinaryColor::On));
+        assert_eq!(disp.get_pixel(Point::new(x, 63)), Some(BinaryColor::On));
+    }
+}
+
+#[test]
+fn eight_fold() {
+    let mut disp = Kaleidoscope::new(MockDisplay::<BinaryColor>::new(), Symmetry::EightFold);
+
+    // Overlaps the diagonal, which must only be drawn once
+    disp.fill_solid(&rect(0, 0, 2, 2), BinaryColor::On).unwrap();
+    assert_eq!(disp.get_pixel(Point::new(63, 63)), Some(BinaryColor::On));
+    assert_eq!(disp.get_pixel(Point::new(62, 0)), Some(BinaryColor::On));
+}
@@ -0,0 +1,50 @@
+//! Alignment helpers for embedded-layout on transformed displays.
+
+use crate::{
+    geometry::{self, Anchor},
+    Orientation,
+};
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+use embedded_layout::{
+    align::{Align, HorizontalAlignment, VerticalAlignment},
+    View,
+};
+
+/// Move `object` so that its `anchor` point is at `reference`'s.
+///
+/// This is the equivalent of [`Align::align_to`] for an [`Anchor`], which can
+/// be chosen at runtime. Combined with [`Anchor::inverse_transform`], it places
+/// a view at a corner or edge of the physical display, whatever the
+/// orientation: for example, aligning to `Anchor::TopRight.inverse_transform(o)`
+/// of the logical display's bounding box keeps a status icon in the physical
+/// top right corner as the display is rotated.
+pub fn align_to_anchor<T: View>(mut object: T, reference: &impl View, anchor: Anchor) -> T {
+    let by = anchor.align(&object.bounds(), &reference.bounds());
+    object.translate_impl(by);
+    object
+}
+
+/// Align `object` to an area of the physical display, like
+/// [`Align::align_to`].
+///
+/// `object` is in the logical coordinates of a display in `orientation`, while
+/// `reference` is an area of the physical display, whose bounding box is
+/// `display`. This saves swapping widths and heights by hand, for example to
+/// center a widget in a region which is fixed on the physical display, such as
+/// the part of a panel not covered by a bezel.
+pub fn align_to_physical<T, H, V>(
+    object: T,
+    reference: &Rectangle,
+    display: &Rectangle,
+    orientation: Orientation,
+    horizontal: H,
+    vertical: V,
+) -> T
+where
+    T: View,
+    H: HorizontalAlignment,
+    V: VerticalAlignment,
+{
+    let reference = geometry::inverse_transform_rect(orientation, *display, *reference);
+    object.align_to(&reference, horizontal, vertical)
+}
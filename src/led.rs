@@ -0,0 +1,713 @@
+//! Coordinate remapping for LED matrices and other unusually wired displays.
+
+use crate::{util, ColorChannels, Rotation};
+use core::{convert::Infallible, marker::PhantomData};
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+
+#[cfg(test)]
+mod tests;
+
+/// Remap a serpentine (zig-zag) wired LED matrix.
+///
+/// Many LED matrices are a single strip folded back and forth, so every other
+/// row runs in the opposite direction. This maps logical coordinates so that
+/// all rows run left to right, by mirroring the reversed rows. By default the
+/// odd rows are reversed (the strip starts at the top left); use
+/// [`reverse_even_rows`](Self::reverse_even_rows) if the strip starts at the
+/// top right.
+///
+/// The inner display is expected to lay out the strip in row-major order, one
+/// row per fold. For matrices folded in columns, wrap this in a
+/// [`Transpose`](crate::Transpose); it can also be combined with any of the
+/// other transforms to account for how the matrix is mounted.
+pub struct Serpentine<D> {
+    target: D,
+    reversed: i32,
+}
+
+impl<D> Serpentine<D> {
+    /// Remap a display whose odd rows are reversed.
+    pub fn new(target: D) -> Self {
+        Serpentine {
+            target,
+            reversed: 1,
+        }
+    }
+
+    /// Reverse the even rows instead of the odd ones.
+    pub fn reverse_even_rows(mut self) -> Self {
+        self.reversed = 0;
+        self
+    }
+
+    #[inline]
+    fn is_reversed(&self, y: i32) -> bool {
+        y.rem_euclid(2) == self.reversed
+    }
+}
+
+impl_wrapper!(Serpentine<D>);
+
+impl<D: Dimensions> Dimensions for Serpentine<D> {
+    #[inline]
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+impl<D: DrawTarget> DrawTarget for Serpentine<D> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let width = self.bounding_box().size.width as i32 - 1;
+        let reversed = self.reversed;
+
+        self.target
+            .draw_iter(pixels.into_iter().map(|Pixel(Point { x, y }, col)| {
+                if y.rem_euclid(2) == reversed {
+                    Pixel(Point { x: width - x, y }, col)
+                } else {
+                    Pixel(Point { x, y }, col)
+                }
+            }))
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let width = self.bounding_box().size.width as i32;
+
+        // Full-width fills cover the same pixels in either direction.
+        if area.top_left.x == 0 && area.size.width as i32 == width {
+            return self.target.fill_solid(area, color);
+        }
+
+        for y in area.top_left.y..area.top_left.y + area.size.height as i32 {
+            let x = if self.is_reversed(y) {
+                width - area.top_left.x - area.size.width as i32
+            } else {
+                area.top_left.x
+            };
+            let row = Rectangle {
+                top_left: Point { x, y },
+                size: Size::new(area.size.width, 1),
+            };
+            self.target.fill_solid(&row, color)?;
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.target.clear(color)
+    }
+}
+
+/// Order in which the modules of a [`ModuleChain`] are connected.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ChainOrder {
+    /// The first module is at the top left, and each row of modules runs left
+    /// to right.
+    LeftToRight,
+    /// The first module is at the top right, and each row of modules runs
+    /// right to left.
+    RightToLeft,
+    /// The first module is at the top left, and rows alternate between
+    /// running left to right and right to left.
+    Serpentine,
+}
+
+/// Present a chain of 8x8 LED modules as one display.
+///
+/// Daisy-chained modules such as MAX7219 matrices are typically driven as one
+/// long strip of modules, with the first module in the chain at the left. This
+/// maps a logical canvas of `cols`×`rows` modules onto such a chain, according
+/// to the physical [`ChainOrder`] of the modules and the rotation with which
+/// each module is mounted.
+///
+/// The inner display must be at least `8 * cols * rows` pixels wide and 8
+/// pixels high, with module *n* in the chain at x = `8 * n`. Drawing outside
+/// the logical canvas is discarded.
+pub struct ModuleChain<D> {
+    target: D,
+    layout: ChainLayout,
+}
+
+#[derive(Clone, Copy)]
+struct ChainLayout {
+    cols: u32,
+    rows: u32,
+    order: ChainOrder,
+    rotation: Rotation,
+}
+
+/// Width and height of a single module.
+const MODULE_SIZE: Size = Size::new(8, 8);
+
+impl ChainLayout {
+    /// Position of module `(col, row)` in the chain.
+    fn chain_index(&self, col: u32, row: u32) -> u32 {
+        let col = match self.order {
+            ChainOrder::LeftToRight => col,
+            ChainOrder::RightToLeft => self.cols - 1 - col,
+            ChainOrder::Serpentine if row % 2 == 1 => self.cols - 1 - col,
+            ChainOrder::Serpentine => col,
+        };
+        row * self.cols + col
+    }
+
+    /// Area of the module at `(col, row)` in logical coordinates.
+    fn module_area(&self, col: u32, row: u32) -> Rectangle {
+        Rectangle::new(
+            Point::new(
+                (col * MODULE_SIZE.width) as i32,
+                (row * MODULE_SIZE.height) as i32,
+            ),
+            MODULE_SIZE,
+        )
+    }
+
+    /// Offset of the module at `(col, row)` on the inner display.
+    fn chain_offset(&self, col: u32, row: u32) -> Point {
+        Point::new((self.chain_index(col, row) * MODULE_SIZE.width) as i32, 0)
+    }
+
+    /// Map a logical point to the chain, or `None` if it's outside the canvas.
+    fn map_point(&self, p: Point) -> Option<Point> {
+        let size = MODULE_SIZE.width as i32;
+        if p.x < 0 || p.y < 0 {
+            return None;
+        }
+        let (col, row) = ((p.x / size) as u32, (p.y / size) as u32);
+        if col >= self.cols || row >= self.rows {
+            return None;
+        }
+
+        let local = Point::new(p.x % size, p.y % size);
+        let local = util::rotate_point(self.rotation, MODULE_SIZE, local);
+
+        Some(self.chain_offset(col, row) + local)
+    }
+}
+
+impl<D> ModuleChain<D> {
+    /// Arrange a chain of modules as a grid of `cols`×`rows` modules.
+    ///
+    /// The modules are initially in [`ChainOrder::LeftToRight`] order and
+    /// unrotated.
+    pub fn new(target: D, cols: u32, rows: u32) -> Self {
+        ModuleChain {
+            target,
+            layout: ChainLayout {
+                cols,
+                rows,
+                order: ChainOrder::LeftToRight,
+                rotation: Rotation::Rotate0,
+            },
+        }
+    }
+
+    /// Set the order in which the modules are chained.
+    pub fn with_order(mut self, order: ChainOrder) -> Self {
+        self.layout.order = order;
+        self
+    }
+
+    /// Set the rotation applied within each module.
+    pub fn with_module_rotation(mut self, rotation: Rotation) -> Self {
+        self.layout.rotation = rotation;
+        self
+    }
+}
+
+impl_wrapper!(ModuleChain<D>);
+
+impl<D> Dimensions for ModuleChain<D> {
+    fn bounding_box(&self) -> Rectangle {
+        Rectangle::new(
+            Point::zero(),
+            Size::new(
+                self.layout.cols * MODULE_SIZE.width,
+                self.layout.rows * MODULE_SIZE.height,
+            ),
+        )
+    }
+}
+
+impl<D: DrawTarget> DrawTarget for ModuleChain<D> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let layout = self.layout;
+
+        self.target.draw_iter(
+            pixels
+                .into_iter()
+                .filter_map(|Pixel(loc, col)| Some(Pixel(layout.map_point(loc)?, col))),
+        )
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let layout = self.layout;
+
+        for row in 0..layout.rows {
+            for col in 0..layout.cols {
+                let module = layout.module_area(col, row);
+                let part = area.intersection(&module);
+                if util::is_empty(&part) {
+                    continue;
+                }
+
+                let local = util::translate(&part, -module.top_left);
+                let local = util::rotate_rect(layout.rotation, MODULE_SIZE, &local);
+                let physical = util::translate(&local, layout.chain_offset(col, row));
+                self.target.fill_solid(&physical, color)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.fill_solid(&self.bounding_box(), color)
+    }
+}
+
+/// Address layout of a multiplexed HUB75 panel, for use with [`Hub75Scan`].
+///
+/// On a 1/*n* scan panel each half of the panel is driven as `scan` rows at a
+/// time. When the panel's half-height is more than `scan` rows, the remaining
+/// rows are "folded" into the shift register chain: each half is divided into
+/// bands of `scan` rows, and the chain alternates between the bands in blocks
+/// of `block_width` pixels. The physical buffer is therefore wider and shorter
+/// than the panel by the number of bands.
+///
+/// For example a 32x16 1/4 scan panel has two bands per half, and is driven
+/// as a 64x8 buffer. If the chain visits the bands bottom first, use
+/// [`with_reversed_bands`](Self::with_reversed_bands).
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ScanPattern {
+    scan: u32,
+    block_width: u32,
+    reverse_bands: bool,
+}
+
+impl ScanPattern {
+    /// A pattern for a 1/`scan` panel whose bands are interleaved in blocks of
+    /// `block_width` pixels.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scan` or `block_width` is zero.
+    pub const fn new(scan: u32, block_width: u32) -> Self {
+        assert!(scan > 0, "scan must be at least 1");
+        assert!(block_width > 0, "block width must be at least 1");
+        ScanPattern {
+            scan,
+            block_width,
+            reverse_bands: false,
+        }
+    }
+
+    /// Visit the bands in reverse order within each block.
+    pub const fn with_reversed_bands(mut self) -> Self {
+        self.reverse_bands = true;
+        self
+    }
+
+    /// Map a point on a panel of logical `size` to the physical buffer, or
+    /// `None` if it's outside the panel.
+    fn map_point(&self, size: Size, p: Point) -> Option<Point> {
+        if p.x < 0 || p.y < 0 || p.x >= size.width as i32 || p.y >= size.height as i32 {
+            return None;
+        }
+
+        let (x, y) = (p.x as u32, p.y as u32);
+        let half_height = size.height / 2;
+        let bands = (half_height / self.scan).max(1);
+
+        let (half, y) = (y / half_height, y % half_height);
+        let (band, line) = (y / self.scan, y % self.scan);
+        let slot = if self.reverse_bands {
+            bands - 1 - band
+        } else {
+            band
+        };
+        let (block, col) = (x / self.block_width, x % self.block_width);
+
+        Some(Point::new(
+            (block * self.block_width * bands + slot * self.block_width + col) as i32,
+            (half * self.scan + line) as i32,
+        ))
+    }
+}
+
+/// Remap a HUB75 panel with a folded scan pattern.
+///
+/// This presents a panel of the given logical size, mapping each pixel to the
+/// position in the physical buffer described by the [`ScanPattern`]. Panels
+/// chained horizontally are handled by using their combined width. Drawing
+/// outside the logical size is discarded.
+pub struct Hub75Scan<D> {
+    target: D,
+    size: Size,
+    pattern: ScanPattern,
+}
+
+impl<D> Hub75Scan<D> {
+    /// Remap a panel of logical `size` with the scan `pattern`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the panel is less than 2 pixels high, since it's driven as
+    /// two halves.
+    pub fn new(target: D, size: Size, pattern: ScanPattern) -> Self {
+        assert!(size.height >= 2, "panel must be at least 2 pixels high");
+        Hub75Scan {
+            target,
+            size,
+            pattern,
+        }
+    }
+}
+
+impl_wrapper!(Hub75Scan<D>);
+
+impl<D> Dimensions for Hub75Scan<D> {
+    fn bounding_box(&self) -> Rectangle {
+        Rectangle::new(Point::zero(), self.size)
+    }
+}
+
+impl<D: DrawTarget> DrawTarget for Hub75Scan<D> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let (size, pattern) = (self.size, self.pattern);
+
+        self.target.draw_iter(
+            pixels
+                .into_iter()
+                .filter_map(|Pixel(loc, col)| Some(Pixel(pattern.map_point(size, loc)?, col))),
+        )
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = area.intersection(&self.bounding_box());
+        let block_width = self.pattern.block_width as i32;
+
+        // Each row segment within a block is contiguous in the physical buffer.
+        for y in area.top_left.y..area.top_left.y + area.size.height as i32 {
+            let end = area.top_left.x + area.size.width as i32;
+            let mut x = area.top_left.x;
+
+            while x < end {
+                let seg_end = ((x / block_width + 1) * block_width).min(end);
+                if let Some(start) = self.pattern.map_point(self.size, Point::new(x, y)) {
+                    let seg = Rectangle::new(start, Size::new((seg_end - x) as u32, 1));
+                    self.target.fill_solid(&seg, color)?;
+                }
+                x = seg_end;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.target.clear(color)
+    }
+}
+
+/// One bitplane of a [`Bitplanes`] buffer.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Bitplane<'a> {
+    /// The bit of each channel in this plane, where 0 is the least
+    /// significant.
+    pub bit: u8,
+    /// How long this plane should be displayed for relative to the others,
+    /// which is `1 << bit` for binary code modulation.
+    pub weight: u32,
+    /// The plane's data, one byte per column for each scan line, as described
+    /// for [`Bitplanes`].
+    pub data: &'a [u8],
+    width: usize,
+}
+
+impl<'a> Bitplane<'a> {
+    /// Iterate over the scan lines of the plane, each one byte per column.
+    pub fn lines(&self) -> impl Iterator<Item = &'a [u8]> + 'a {
+        self.data.chunks(self.width.max(1))
+    }
+}
+
+/// A framebuffer which stores colors as bitplanes, for binary code modulation.
+///
+/// HUB75 and similar LED matrices only switch each LED on or off, so drivers
+/// show other colors by displaying a series of binary planes, one for each bit
+/// of the channels, each for a time proportional to the bit's weight. This
+/// splits colors into `planes` planes as they're drawn, scaling each channel
+/// to that many bits, so the most significant bits are kept; 8 planes keeps all
+/// of an `Rgb888` and 6 all of an `Rgb565`.
+///
+/// Each plane is stored as the bytes a HUB75 driver shifts out: the panel is
+/// split into top and bottom halves which are driven together, and each byte
+/// holds the bits of one column of a scan line, with the top half's red, green
+/// and blue in bits 0 to 2 and the bottom half's in bits 3 to 5. Planes are
+/// stored least significant first, each `width * height / 2` bytes, and can be
+/// read with [`planes`](Self::planes).
+///
+/// For panels with a folded scan pattern, draw to this through a
+/// [`Hub75Scan`], with this buffer the size of the physical buffer.
+pub struct Bitplanes<'a, C> {
+    buf: &'a mut [u8],
+    size: Size,
+    planes: u8,
+    _color: PhantomData<C>,
+}
+
+impl<'a, C> Bitplanes<'a, C> {
+    /// Return the length of buffer needed for a panel of `size` with `planes`
+    /// planes.
+    pub const fn buffer_len(size: Size, planes: u8) -> usize {
+        planes as usize * size.width as usize * (size.height / 2) as usize
+    }
+
+    /// Store a panel of `size` as `planes` bitplanes in `buf`, which is
+    /// cleared.
+    ///
+    /// # Panics
+    ///
+    /// If the panel's height is odd, `planes` is not from 1 to 8, or `buf` is
+    /// shorter than [`buffer_len`](Self::buffer_len).
+    pub fn new(buf: &'a mut [u8], size: Size, planes: u8) -> Self {
+        assert_eq!(size.height % 2, 0, "panel height must be even");
+        assert!((1..=8).contains(&planes), "must have 1 to 8 planes");
+        let buf = &mut buf[..Self::buffer_len(size, planes)];
+        buf.fill(0);
+
+        Bitplanes {
+            buf,
+            size,
+            planes,
+            _color: PhantomData,
+        }
+    }
+
+    /// Return the number of planes.
+    pub fn plane_count(&self) -> u8 {
+        self.planes
+    }
+
+    /// Iterate over the planes, least significant first.
+    pub fn planes(&self) -> impl Iterator<Item = Bitplane<'_>> + '_ {
+        let width = self.size.width as usize;
+        let len = width * (self.size.height / 2) as usize;
+
+        (0..self.planes).map(move |bit| Bitplane {
+            bit,
+            weight: 1 << bit,
+            data: &self.buf[usize::from(bit) * len..][..len],
+            width,
+        })
+    }
+
+    /// Return the buffer.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.buf
+    }
+
+    /// Set the bits of one pixel, with `levels` each scaled to `planes` bits.
+    fn set(&mut self, p: Point, levels: [u8; 3]) {
+        let Size { width, height } = self.size;
+        if p.x < 0 || p.y < 0 || p.x as u32 >= width || p.y as u32 >= height {
+            return;
+        }
+
+        let (x, y) = (p.x as usize, p.y as u32);
+        let half = height / 2;
+        let (line, shift) = if y < half { (y, 0) } else { (y - half, 3) };
+        let len = width as usize * half as usize;
+        let index = line as usize * width as usize + x;
+
+        for bit in 0..self.planes {
+            let byte = &mut self.buf[usize::from(bit) * len + index];
+            let rgb = (0..3).fold(0, |rgb, c| rgb | ((levels[c] >> bit) & 1) << c);
+            *byte = (*byte & !(0b111 << shift)) | rgb << shift;
+        }
+    }
+}
+
+impl<C: ColorChannels> Bitplanes<'_, C> {
+    /// Scale a color's channels to the number of planes.
+    fn levels(&self, color: C) -> [u8; 3] {
+        let channels = color.to_channels();
+        let max = (1u32 << self.planes) - 1;
+        [0, 1, 2].map(|c| {
+            let value = u32::from(channels[c]) * max;
+            let in_max = u32::from(C::MAX[c]);
+            ((value + in_max / 2) / in_max) as u8
+        })
+    }
+}
+
+impl<C> OriginDimensions for Bitplanes<'_, C> {
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+impl<C: ColorChannels> DrawTarget for Bitplanes<'_, C> {
+    type Color = C;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(p, color) in pixels {
+            let levels = self.levels(color);
+            self.set(p, levels);
+        }
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let levels = self.levels(color);
+        let area = area.intersection(&self.bounding_box());
+        for p in util::points(&area) {
+            self.set(p, levels);
+        }
+        Ok(())
+    }
+}
+
+/// An entry in a [`RemapLut`] table, giving the physical position of a pixel.
+pub trait LutEntry: Copy {
+    /// The position of the pixel on a physical display `width` pixels wide, or
+    /// `None` if the logical pixel doesn't exist.
+    fn position(self, width: u32) -> Option<Point>;
+}
+
+/// A pixel index into the physical display in row-major order, so for a
+/// single strip of LEDs this is the LED's position along the strip.
+/// `u16::MAX` marks a logical pixel with no physical LED.
+impl LutEntry for u16 {
+    #[inline]
+    fn position(self, width: u32) -> Option<Point> {
+        if self == u16::MAX || width == 0 {
+            None
+        } else {
+            let index = u32::from(self);
+            Some(Point::new((index % width) as i32, (index / width) as i32))
+        }
+    }
+}
+
+/// A pixel's position on the physical display.
+impl LutEntry for Point {
+    #[inline]
+    fn position(self, _width: u32) -> Option<Point> {
+        Some(self)
+    }
+}
+
+/// Remap pixels through a lookup table.
+///
+/// This handles arbitrary layouts such as charlieplexed boards or hand-wired
+/// LED sculptures. The logical display has the given size, and the table has
+/// one [`LutEntry`] per logical pixel in row-major order, giving its physical
+/// position. Pixels outside the logical display or beyond the end of the table
+/// are discarded.
+pub struct RemapLut<'a, D, T> {
+    target: D,
+    size: Size,
+    table: &'a [T],
+}
+
+impl<'a, D, T: LutEntry> RemapLut<'a, D, T> {
+    /// Remap a logical display of `size` through `table`.
+    pub fn new(target: D, size: Size, table: &'a [T]) -> Self {
+        RemapLut {
+            target,
+            size,
+            table,
+        }
+    }
+
+    fn lookup(size: Size, table: &[T], width: u32, p: Point) -> Option<Point> {
+        if p.x < 0 || p.y < 0 || p.x >= size.width as i32 || p.y >= size.height as i32 {
+            return None;
+        }
+        let index = p.y as usize * size.width as usize + p.x as usize;
+        table.get(index)?.position(width)
+    }
+}
+
+impl<'a, D: Dimensions, T: LutEntry> RemapLut<'a, D, T> {
+    /// Map a logical point to its physical position, if it has one.
+    pub fn map_point(&self, p: Point) -> Option<Point> {
+        let width = self.target.bounding_box().size.width;
+        Self::lookup(self.size, self.table, width, p)
+    }
+
+    /// Find the logical point which maps to the physical point `p`, if any.
+    ///
+    /// This is a linear search of the table, so is intended for occasional use
+    /// such as mapping touch input or testing.
+    pub fn unmap_point(&self, p: Point) -> Option<Point> {
+        let width = self.target.bounding_box().size.width;
+        let index = self
+            .table
+            .iter()
+            .take((self.size.width * self.size.height) as usize)
+            .position(|entry| entry.position(width) == Some(p))?;
+
+        Some(Point::new(
+            (index % self.size.width as usize) as i32,
+            (index / self.size.width as usize) as i32,
+        ))
+    }
+}
+
+impl_wrapper!(RemapLut<'a, D, T>);
+
+impl<D, T> Dimensions for RemapLut<'_, D, T> {
+    fn bounding_box(&self) -> Rectangle {
+        Rectangle::new(Point::zero(), self.size)
+    }
+}
+
+impl<D: DrawTarget, T: LutEntry> DrawTarget for RemapLut<'_, D, T> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let (size, table) = (self.size, self.table);
+        let width = self.target.bounding_box().size.width;
+
+        self.target.draw_iter(
+            pixels.into_iter().filter_map(|Pixel(loc, col)| {
+                Some(Pixel(Self::lookup(size, table, width, loc)?, col))
+            }),
+        )
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.target.clear(color)
+    }
+}
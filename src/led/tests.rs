@@ -0,0 +1,233 @@
+use super::*;
+use crate::{tests::rect, Rotation};
+use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+#[test]
+fn serpentine_pixels() {
+    let mut disp = Serpentine::new(MockDisplay::<BinaryColor>::new());
+
+    disp.draw_iter([
+        Pixel(Point::new(0, 0), BinaryColor::On),
+        Pixel(Point::new(0, 1), BinaryColor::On),
+        Pixel(Point::new(2, 2), BinaryColor::On),
+    ])
+    .unwrap();
+
+    assert!(disp.get_pixel(Point::new(0, 0)).is_some());
+    assert!(disp.get_pixel(Point::new(63, 1)).is_some());
+    assert!(disp.get_pixel(Point::new(2, 2)).is_some());
+
+    let mut disp = Serpentine::new(MockDisplay::<BinaryColor>::new()).reverse_even_rows();
+    disp.draw_iter([Pixel(Point::new(0, 0), BinaryColor::On)])
+        .unwrap();
+    assert_eq!(disp.affected_area(), rect(63, 0, 1, 1));
+}
+
+#[test]
+fn serpentine_fill() {
+    let mut disp = Serpentine::new(MockDisplay::<BinaryColor>::new());
+
+    disp.fill_solid(&rect(1, 1, 3, 2), BinaryColor::On).unwrap();
+    assert!(disp.get_pixel(Point::new(60, 1)).is_some());
+    assert!(disp.get_pixel(Point::new(62, 1)).is_some());
+    assert!(disp.get_pixel(Point::new(1, 2)).is_some());
+    assert!(disp.get_pixel(Point::new(3, 2)).is_some());
+
+    disp.fill_contiguous(&rect(0, 3, 2, 1), [BinaryColor::On, BinaryColor::Off])
+        .unwrap();
+    assert_eq!(disp.get_pixel(Point::new(63, 3)), Some(BinaryColor::On));
+    assert_eq!(disp.get_pixel(Point::new(62, 3)), Some(BinaryColor::Off));
+}
+
+#[test]
+fn module_chain() {
+    let mut disp = ModuleChain::new(MockDisplay::<BinaryColor>::new(), 2, 2);
+    assert_eq!(disp.bounding_box(), rect(0, 0, 16, 16));
+
+    disp.draw_iter([
+        Pixel(Point::new(9, 1), BinaryColor::On),
+        Pixel(Point::new(1, 9), BinaryColor::On),
+        // Outside the canvas
+        Pixel(Point::new(16, 0), BinaryColor::On),
+    ])
+    .unwrap();
+    disp.assert_pattern(&[
+        "                  ", //
+        "         #       #",
+    ]);
+
+    let mut disp = ModuleChain::new(MockDisplay::<BinaryColor>::new(), 2, 2)
+        .with_order(ChainOrder::Serpentine)
+        .with_module_rotation(Rotation::Rotate90);
+    disp.draw_iter([Pixel(Point::new(1, 9), BinaryColor::On)])
+        .unwrap();
+    // Last module in the chain, rotated
+    assert_eq!(disp.affected_area(), rect(24 + 6, 1, 1, 1));
+}
+
+#[test]
+fn module_chain_fill() {
+    let mut disp = ModuleChain::new(MockDisplay::<BinaryColor>::new(), 2, 1)
+        .with_order(ChainOrder::RightToLeft)
+        .with_module_rotation(Rotation::Rotate180);
+
+    disp.fill_solid(&rect(6, 0, 4, 2), BinaryColor::On).unwrap();
+    disp.assert_pattern(&[
+        "          ", //
+        "          ",
+        "          ",
+        "          ",
+        "          ",
+        "          ",
+        "      ####",
+        "      ####",
+    ]);
+}
+
+#[test]
+fn hub75_scan() {
+    let pattern = ScanPattern::new(2, 4);
+    let mut disp = Hub75Scan::new(MockDisplay::<BinaryColor>::new(), Size::new(16, 8), pattern);
+    assert_eq!(disp.bounding_box(), rect(0, 0, 16, 8));
+
+    disp.draw_iter([
+        Pixel(Point::new(0, 0), BinaryColor::On),
+        Pixel(Point::new(0, 2), BinaryColor::On),
+        Pixel(Point::new(5, 3), BinaryColor::On),
+        Pixel(Point::new(0, 4), BinaryColor::On),
+        Pixel(Point::new(16, 0), BinaryColor::On),
+    ])
+    .unwrap();
+    disp.assert_pattern(&[
+        "#   #         ", //
+        "             #",
+        "#             ",
+    ]);
+
+    let mut disp = Hub75Scan::new(
+        MockDisplay::<BinaryColor>::new(),
+        Size::new(16, 8),
+        pattern.with_reversed_bands(),
+    );
+    disp.fill_solid(&rect(2, 0, 4, 1), BinaryColor::On).unwrap();
+    disp.assert_pattern(&["      ##    ##"]);
+}
+
+#[test]
+#[should_panic(expected = "scan")]
+fn hub75_zero_scan() {
+    ScanPattern::new(0, 4);
+}
+
+#[test]
+#[should_panic(expected = "block width")]
+fn hub75_zero_block_width() {
+    ScanPattern::new(2, 0);
+}
+
+#[test]
+#[should_panic(expected = "2 pixels high")]
+fn hub75_short_panel() {
+    Hub75Scan::new(
+        MockDisplay::<BinaryColor>::new(),
+        Size::new(16, 1),
+        ScanPattern::new(2, 4),
+    );
+}
+
+#[test]
+fn remap_lut() {
+    const TABLE: [u16; 6] = [3, 2, 1, 64, u16::MAX, 66];
+    let mut disp = RemapLut::new(MockDisplay::<BinaryColor>::new(), Size::new(3, 2), &TABLE);
+    assert_eq!(disp.bounding_box(), rect(0, 0, 3, 2));
+
+    disp.fill_solid(&rect(0, 0, 3, 2), BinaryColor::On).unwrap();
+    disp.assert_pattern(&[
+        " ###", //
+        "# # ",
+    ]);
+
+    assert_eq!(disp.map_point(Point::new(2, 1)), Some(Point::new(2, 1)));
+    assert_eq!(disp.map_point(Point::new(1, 1)), None);
+    assert_eq!(disp.unmap_point(Point::new(3, 0)), Some(Point::new(0, 0)));
+    assert_eq!(disp.unmap_point(Point::new(0, 0)), None);
+}
+
+#[test]
+fn remap_lut_points() {
+    let table = [Point::new(5, 5), Point::new(0, 1)];
+    let mut disp = RemapLut::new(
+        MockDisplay::<BinaryColor>::new(),
+        Size::new(2, 1),
+        &table[..],
+    );
+
+    disp.draw_iter([Pixel(Point::new(1, 0), BinaryColor::On)])
+        .unwrap();
+    assert_eq!(disp.affected_area(), rect(0, 1, 1, 1));
+    assert_eq!(disp.unmap_point(Point::new(5, 5)), Some(Point::new(0, 0)));
+}
+
+#[test]
+fn bitplanes() {
+    use embedded_graphics::pixelcolor::{Rgb565, Rgb888};
+
+    let size = Size::new(4, 4);
+    let mut buf = [0xff; 64];
+    let len = Bitplanes::<Rgb888>::buffer_len(size, 2);
+    assert_eq!(len, 2 * 4 * 2);
+    let mut planes = Bitplanes::<Rgb888>::new(&mut buf, size, 2);
+
+    // Top half in the low bits, bottom half in the high bits
+    planes
+        .draw_iter([
+            Pixel(Point::new(0, 0), Rgb888::new(255, 0, 0)),
+            Pixel(Point::new(1, 0), Rgb888::new(0, 85, 170)),
+            Pixel(Point::new(1, 2), Rgb888::new(170, 170, 170)),
+            Pixel(Point::new(4, 0), Rgb888::WHITE),
+        ])
+        .unwrap();
+    planes
+        .fill_solid(&rect(3, 1, 2, 3), Rgb888::new(0, 255, 0))
+        .unwrap();
+
+    let mut iter = planes.planes();
+    let (low, high) = (iter.next().unwrap(), iter.next().unwrap());
+    assert!(iter.next().is_none());
+    assert_eq!((low.bit, low.weight), (0, 1));
+    assert_eq!((high.bit, high.weight), (1, 2));
+    assert_eq!(low.data, [0b001, 0b010, 0, 0b010_000, 0, 0, 0, 0b010_010]);
+    assert_eq!(
+        high.data,
+        [0b001, 0b111_100, 0, 0b010_000, 0, 0, 0, 0b010_010]
+    );
+    assert_eq!(high.lines().nth(1), Some(&[0, 0, 0, 0b010_010][..]));
+    assert_eq!(high.lines().count(), 2);
+
+    // Rgb565 scales each channel to the same number of bits
+    let mut buf = [0; 6 * 2];
+    let mut planes = Bitplanes::new(&mut buf, Size::new(1, 2), 6);
+    planes.clear(Rgb565::new(31, 63, 0)).unwrap();
+    assert!(planes.planes().all(|plane| plane.data == [0b011_011]));
+}
+
+#[test]
+fn bitplanes_scan() {
+    use embedded_graphics::pixelcolor::Rgb888;
+
+    // A 4x8 1/2 scan panel is a 8x4 buffer
+    let pattern = ScanPattern::new(2, 2);
+    let mut buf = [0; 8 * 2];
+    let mut panel = Hub75Scan::new(
+        Bitplanes::new(&mut buf, Size::new(8, 4), 1),
+        Size::new(4, 8),
+        pattern,
+    );
+    panel.fill_solid(&rect(0, 2, 1, 1), Rgb888::RED).unwrap();
+    panel.fill_solid(&rect(0, 4, 1, 1), Rgb888::BLUE).unwrap();
+
+    let planes = panel.into_inner();
+    let plane = planes.planes().next().unwrap();
+    assert_eq!(plane.data[2], 0b001);
+    assert_eq!(plane.data[0], 0b100_000);
+}
@@ -10,7 +10,8 @@
 //! - mirroring
 //! - transposition
 //!
-//! Note that these transformations can be composed if needed.
+//! Note that these transformations can be composed if needed, and
+//! [`Reorient`] applies any combination of them as a single mapping.
 //!
 //! Because this is a completely generic implementation, it cannot take
 //! advantage of any hardware or driver specific specializations. In particular,
@@ -21,14 +22,319 @@
 //!
 //! All the transforms implement [`AsRef<D>`]/[`AsMut<D>`] to get access to the
 //! underlying display object so that its inherent functions can be called.
+//!
+//! There are also adapters for:
+//! - composing and choosing orientations: [`Oriented`], [`Reorient`],
+//!   [`compose_transforms!`], [`Invert`], [`AutoRotate`], [`DynTransform`],
+//!   [`draw_transformed`] and [`draw_rotated`]
+//! - the same mappings as plain functions: the [`geometry`] module
+//! - faster paths to the hardware: [`ColumnMajor`], [`Traverse`],
+//!   [`ScanlineEmitter`], [`AddressWindow`], [`Blitter`] and
+//!   [`StridedFramebuffer`]
+//! - LED matrix wiring: [`Serpentine`], [`ModuleChain`], [`Hub75Scan`],
+//!   [`RemapLut`] and [`Bitplanes`]
+//! - shapes and symmetry: [`CircleClip`], [`RoundedClip`], [`Masked`],
+//!   [`Polar`], [`Kaleidoscope`] and [`Interlace`]
+//! - parts of a display: [`Inset`], [`Offset`], [`WithSize`], [`Viewport`],
+//!   [`Zoom`], [`Letterbox`], [`Clipped`], [`PixelShift`], [`ScrollX`] and
+//!   [`ScrollY`]
+//! - several displays: [`Regions`], [`Zones`], [`TiledGrid`], [`Tee`],
+//!   [`Switch`], [`Either`] and [`Failover`]
+//! - keeping what's drawn: [`Lockout`], [`Repair`] and [`SpriteOverlay`]
+//! - colors: [`InvertColor`], [`Highlight`], [`MapColor`], [`SwapRgb`],
+//!   [`ColorLut`], [`Brightness`], [`ColorTemperature`], [`Idle`],
+//!   [`ChromaKey`], [`Palette`], [`AlphaBlend`] and [`read_logical`]
+//! - fewer colors: [`ToBinary`], [`Grayscale`], [`Quantize`],
+//!   [`OrderedDither`], [`ErrorDiffusion`] and [`TemporalDither`]
+//! - errors, debugging and limits: [`MapError`], [`Retry`], [`NullDisplay`],
+//!   [`Recorder`], [`Instrument`], [`Trace`], [`Validate`], [`Budget`] and
+//!   [`Yielding`]
+//!
+//! Optional features add:
+//! - `critical-section`: `SharedDisplay`, for drawing from interrupt handlers
+//! - `test-util`: `TransformTester` and `assert_pattern_rotated`
+//! - `std`: `assert_golden` and `ColorBlind`
+//! - `simulator`: `Preview`, in the embedded-graphics simulator
+//! - `embedded-graphics-framebuf`: `OrientedFrameBuf`
+//! - `embedded-layout`: alignment of views on transformed displays
+//! - `heapless` and `alloc`: storage for [`Recorder`]
+//! - `serde`: serialization of orientations and rotations
 #![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(any(test, feature = "std"))]
+extern crate std;
+
 use core::ops::{Deref, DerefMut};
-use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+use embedded_graphics_core::{image::ImageDrawable, prelude::*, primitives::Rectangle};
 
 #[cfg(test)]
 mod tests;
 
+/// Implement `Dimensions` and `DrawTarget` for `&mut` references to a wrapper
+/// by delegating to the wrapper's own implementation, so that it can be passed
+/// by value to functions taking `impl DrawTarget` without giving it up.
+///
+/// The wrapper's generic parameters are given in brackets, as in
+/// `impl_by_mut_ref!([D, F] Name<D, F>)`.
+macro_rules! impl_by_mut_ref {
+    ([$($gen:tt)*] $ty:ty) => {
+        impl<'r, $($gen)*> Dimensions for &'r mut $ty
+        where
+            $ty: Dimensions,
+        {
+            #[inline]
+            fn bounding_box(&self) -> Rectangle {
+                (**self).bounding_box()
+            }
+        }
+
+        impl<'r, $($gen)*> DrawTarget for &'r mut $ty
+        where
+            $ty: DrawTarget,
+        {
+            type Color = <$ty as DrawTarget>::Color;
+            type Error = <$ty as DrawTarget>::Error;
+
+            #[inline]
+            fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+            where
+                I: IntoIterator<Item = Pixel<Self::Color>>,
+            {
+                (**self).draw_iter(pixels)
+            }
+
+            #[inline]
+            fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+            where
+                I: IntoIterator<Item = Self::Color>,
+            {
+                (**self).fill_contiguous(area, colors)
+            }
+
+            #[inline]
+            fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+                (**self).fill_solid(area, color)
+            }
+
+            #[inline]
+            fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+                (**self).clear(color)
+            }
+        }
+    };
+}
+
+/// Implement the accessors common to all single-display adapters: `into_inner`,
+/// `AsRef`/`AsMut` and `Deref`/`DerefMut` to the display in the `target` field.
+///
+/// Const generic parameters follow a `;`, as in `impl_wrapper!(Name<D; const
+/// N: usize>)`.
+macro_rules! impl_wrapper {
+    ($name:ident < $($lt:lifetime ,)* D $(, $param:ident)* $(; const $c:ident : $cty:ty)* >) => {
+        impl<$($lt ,)* D $(, $param)* $(, const $c: $cty)*> $name<$($lt ,)* D $(, $param)* $(, $c)*> {
+            /// Recover the inner display instance.
+            pub fn into_inner(self) -> D {
+                self.target
+            }
+        }
+
+        impl<$($lt ,)* D $(, $param)* $(, const $c: $cty)*> core::ops::Deref for $name<$($lt ,)* D $(, $param)* $(, $c)*> {
+            type Target = D;
+
+            fn deref(&self) -> &D {
+                &self.target
+            }
+        }
+
+        impl<$($lt ,)* D $(, $param)* $(, const $c: $cty)*> core::ops::DerefMut for $name<$($lt ,)* D $(, $param)* $(, $c)*> {
+            fn deref_mut(&mut self) -> &mut D {
+                &mut self.target
+            }
+        }
+
+        impl<$($lt ,)* D $(, $param)* $(, const $c: $cty)*> AsRef<D> for $name<$($lt ,)* D $(, $param)* $(, $c)*> {
+            #[inline]
+            fn as_ref(&self) -> &D {
+                &self.target
+            }
+        }
+
+        impl<$($lt ,)* D $(, $param)* $(, const $c: $cty)*> AsMut<D> for $name<$($lt ,)* D $(, $param)* $(, $c)*> {
+            #[inline]
+            fn as_mut(&mut self) -> &mut D {
+                &mut self.target
+            }
+        }
+
+        impl_by_mut_ref!([$($lt ,)* D $(, $param)* $(, const $c: $cty)*] $name<$($lt ,)* D $(, $param)* $(, $c)*>);
+    };
+}
+
+/// Implement `Dimensions` and `DrawTarget` for a color adapter, which has the
+/// display in its `target` field and a [`ColorMap`] in its `map` field.
+macro_rules! impl_color_map {
+    ($name:ident < $($lt:lifetime ,)* D $(, $param:ident)* >, $color:ty, where $($bound:tt)+) => {
+        impl<$($lt ,)* D: Dimensions $(, $param)*> Dimensions for $name<$($lt ,)* D $(, $param)*> {
+            fn bounding_box(&self) -> Rectangle {
+                self.target.bounding_box()
+            }
+        }
+
+        impl<$($lt ,)* D: DrawTarget $(, $param)*> DrawTarget for $name<$($lt ,)* D $(, $param)*>
+        where
+            $($bound)+
+        {
+            type Color = $color;
+            type Error = D::Error;
+
+            fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+            where
+                I: IntoIterator<Item = Pixel<Self::Color>>,
+            {
+                let map = &mut self.map;
+                self.target.draw_iter(
+                    pixels
+                        .into_iter()
+                        .map(|Pixel(loc, col)| Pixel(loc, map.map_color(col))),
+                )
+            }
+
+            fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+            where
+                I: IntoIterator<Item = Self::Color>,
+            {
+                let map = &mut self.map;
+                self.target
+                    .fill_contiguous(area, colors.into_iter().map(|col| map.map_color(col)))
+            }
+
+            fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+                let color = self.map.map_color(color);
+                self.target.fill_solid(area, color)
+            }
+
+            fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+                let color = self.map.map_color(color);
+                self.target.clear(color)
+            }
+        }
+    };
+}
+
+mod address;
+mod auto;
+mod blit;
+mod budget;
+mod clip;
+mod color;
+#[cfg(feature = "std")]
+mod colorblind;
+mod column;
+mod debug;
+mod dither;
+mod dma;
+mod dynamic;
+mod encoding;
+mod error;
+#[cfg(feature = "embedded-graphics-framebuf")]
+mod framebuf;
+pub mod geometry;
+#[cfg(feature = "std")]
+mod golden;
+mod idle;
+mod image;
+mod interlace;
+mod kaleidoscope;
+#[cfg(feature = "embedded-layout")]
+mod layout;
+mod led;
+mod lockout;
+mod null;
+mod oriented;
+mod overlay;
+mod pair;
+mod polar;
+mod readback;
+mod recorder;
+mod region;
+mod repair;
+mod scoped;
+mod scroll;
+#[cfg(feature = "critical-section")]
+mod shared;
+#[cfg(feature = "simulator")]
+mod simulator;
+mod strided;
+#[cfg(any(test, feature = "test-util"))]
+mod testing;
+mod tiled;
+mod traverse;
+mod util;
+mod window;
+mod yielding;
+
+pub use address::AddressWindow;
+pub use auto::{AutoRotate, OrientationProvider};
+pub use blit::Blitter;
+pub use budget::{Budget, BudgetError, Limit};
+pub use clip::{Bitmap, CircleClip, Mask, Masked, RoundedClip};
+pub use color::{
+    Brightness, ChromaKey, ColorChannels, ColorLut, ColorMap, ColorTemperature, Grayscale,
+    Highlight, InvertColor, MapColor, Palette, PaletteIndex, Quantize, Rounding, SwapRgb, Tint,
+    ToBinary,
+};
+#[cfg(feature = "std")]
+pub use colorblind::{ColorBlind, Deficiency};
+pub use column::ColumnMajor;
+#[cfg(feature = "defmt")]
+pub use debug::defmt_trace;
+pub use debug::{DrawStats, Instrument, Trace, Validate, ValidationError, Violation};
+pub use dither::{BayerMatrix, ErrorDiffusion, OrderedDither, TemporalDither};
+pub use dma::{DmaChunk, ScanlineEmitter};
+pub use dynamic::DynTransform;
+#[cfg(feature = "alloc")]
+pub use dynamic::{boxed, BoxedTransform};
+pub use encoding::ParseOrientationError;
+pub use error::{MapError, Retry};
+#[cfg(feature = "embedded-graphics-framebuf")]
+pub use framebuf::OrientedFrameBuf;
+#[cfg(feature = "std")]
+pub use golden::{assert_golden, encode_bmp};
+pub use idle::{Clock, Idle, IdleState};
+pub use interlace::{Field, Interlace};
+pub use kaleidoscope::{Kaleidoscope, Symmetry};
+#[cfg(feature = "embedded-layout")]
+pub use layout::{align_to_anchor, align_to_physical};
+pub use led::{
+    Bitplane, Bitplanes, ChainOrder, Hub75Scan, LutEntry, ModuleChain, RemapLut, ScanPattern,
+    Serpentine,
+};
+pub use lockout::{Lockout, LockoutError};
+pub use null::NullDisplay;
+pub use oriented::{Invert, Normalize, Orientation, Oriented, Reorient};
+pub use overlay::{Sprite, SpriteOverlay};
+pub use pair::{Either, Failover, PairError, Selection, Switch, Tee};
+pub use polar::Polar;
+pub use readback::{read_logical, AlphaBlend, ReadPixel};
+pub use recorder::{replay, BufferFull, Command, CommandBuffer, Recorder};
+pub use region::{Region, RegionError, Regions, Zone, Zones};
+pub use repair::{Background, Repair, SolidColor};
+pub use scoped::{draw_rotated, draw_transformed};
+pub use scroll::{HardwareScroll, ScrollX, ScrollY};
+#[cfg(feature = "critical-section")]
+pub use shared::SharedDisplay;
+#[cfg(feature = "simulator")]
+pub use simulator::Preview;
+pub use strided::StridedFramebuffer;
+#[cfg(feature = "test-util")]
+pub use testing::{affected_area_rotated, assert_pattern_rotated, unrotate, TransformTester};
+pub use tiled::TiledGrid;
+pub use traverse::{Traversal, Traverse};
+pub use window::{Clipped, Inset, Insets, Letterbox, Offset, PixelShift, Viewport, WithSize, Zoom};
+pub use yielding::Yielding;
+
 macro_rules! xform_type {
     ($inner:ident , ) => { $inner };
     ($inner:ident , $xform: ident $($rest:ident)*) => {
@@ -54,7 +360,7 @@ macro_rules! impl_as_ref {
 }
 
 macro_rules! impl_xform {
-    ($($(#[$attr:meta])* $name:ident : $($xforms:ident)* ; )*) => {
+    ($($(#[$attr:meta])* $name:ident ($orientation:expr) : $($xforms:ident)* ; )*) => {
         $(
             $(#[$attr])*
             pub struct $name<D> {
@@ -76,6 +382,151 @@ macro_rules! impl_xform {
                 }
             }
 
+            impl_by_mut_ref!([D] $name<D>);
+
+            impl<D: DrawTarget> $name<D> {
+                /// Draw a slice of pixels with a single call to the display.
+                ///
+                /// This avoids the per-pixel overhead of chaining iterator
+                /// adapters. The pixels' coordinates are rewritten in place to
+                /// the display's.
+                pub fn set_pixels(&mut self, pixels: &mut [Pixel<D::Color>]) -> Result<(), D::Error> {
+                    let size = self.bounding_box().size;
+                    oriented::set_pixels(self.as_mut(), $orientation, size, Point::zero(), pixels)
+                }
+
+                /// Fill an area through an `N` pixel buffer.
+                ///
+                /// [`fill_contiguous`](DrawTarget::fill_contiguous) has to draw
+                /// a transformed area pixel by pixel, because its colors aren't
+                /// in the display's order. This is the fast path for blitting
+                /// images and text: it buffers up to `N` colors at a time,
+                /// reorders them into the display's order, and fills each
+                /// buffered chunk with a single call to the display's own
+                /// `fill_contiguous`. Chunks are whole rows of `area` where
+                /// possible, so `N` should be at least its width.
+                pub fn fill_contiguous_buffered<const N: usize, I>(
+                    &mut self,
+                    area: &Rectangle,
+                    colors: I,
+                ) -> Result<(), D::Error>
+                where
+                    I: IntoIterator<Item = D::Color>,
+                {
+                    let size = self.bounding_box().size;
+                    oriented::fill_contiguous_buffered::<_, N>(
+                        self.as_mut(),
+                        $orientation,
+                        size,
+                        Point::zero(),
+                        area,
+                        colors,
+                    )
+                }
+
+                /// Draw a single pixel.
+                ///
+                /// This is faster than drawing a single pixel with
+                /// [`draw_iter`](DrawTarget::draw_iter), for sparse plotting.
+                /// Pixels outside the display are ignored.
+                pub fn write_pixel(&mut self, point: Point, color: D::Color) -> Result<(), D::Error> {
+                    let size = self.bounding_box().size;
+                    let pixel = Pixel(point, color);
+                    oriented::write_pixel(self.as_mut(), $orientation, size, Point::zero(), pixel, false)
+                }
+
+                /// Draw a single pixel without checking it's on the display.
+                ///
+                /// This saves the bounds check of
+                /// [`write_pixel`](Self::write_pixel), for callers which
+                /// already know the point is on the display. A point outside
+                /// the display is mapped to somewhere outside the wrapped
+                /// display, where it's up to that display to clip it.
+                pub fn write_pixel_unchecked(&mut self, point: Point, color: D::Color) -> Result<(), D::Error> {
+                    let size = self.bounding_box().size;
+                    let pixel = Pixel(point, color);
+                    oriented::write_pixel(self.as_mut(), $orientation, size, Point::zero(), pixel, true)
+                }
+
+                /// Draw an image with its top left at `top_left`, in the
+                /// display's order.
+                ///
+                /// Drawing an image as usual fills it row by row, which a
+                /// transform that changes the order of the pixels has to draw
+                /// pixel by pixel. This instead draws the image one display
+                /// row at a time, each of which is a row or column of the
+                /// image, so each is a contiguous write on the display. It
+                /// relies on the image being able to draw any part of itself
+                /// cheaply, as `embedded-graphics`'s `ImageRaw` can.
+                pub fn draw_image<I>(&mut self, image: &I, top_left: Point) -> Result<(), D::Error>
+                where
+                    I: ImageDrawable<Color = D::Color>,
+                {
+                    image::draw_image(self, $orientation, image, top_left)
+                }
+            }
+
+            impl<D: AddressWindow> $name<D> {
+                /// Fill an area with the display's own windowed writes.
+                ///
+                /// The area is mapped to the display, and sent to it as
+                /// [`AddressWindow`] windows rather than pixel by pixel. If the
+                /// transform changes the order of the pixels, each row of
+                /// `area` is written as its own window, since it's a single
+                /// row or column of the display. Rows which run backwards on
+                /// the display are reversed through an `N` pixel buffer.
+                pub fn fill_window<const N: usize, I>(
+                    &mut self,
+                    area: &Rectangle,
+                    colors: I,
+                ) -> Result<(), D::Error>
+                where
+                    I: IntoIterator<Item = D::Color>,
+                {
+                    let size = self.bounding_box().size;
+                    address::fill_window::<_, N>(
+                        self.as_mut(),
+                        $orientation,
+                        size,
+                        Point::zero(),
+                        area,
+                        colors,
+                    )
+                }
+            }
+
+            impl<D: Blitter> $name<D> {
+                /// Copy an area of the display so that its top left is at
+                /// `to`, with the display's own 2D engine.
+                ///
+                /// The copy is mapped to the display, where it's still a
+                /// single rectangular copy, and sent to it with
+                /// [`Blitter::copy_area`]. It's clipped so that both the
+                /// source and destination are on the display. The areas may
+                /// overlap, such as to scroll part of the display.
+                pub fn copy_area(&mut self, area: &Rectangle, to: Point) -> Result<(), D::Error> {
+                    let size = self.bounding_box().size;
+                    blit::copy_area(self.as_mut(), $orientation, size, Point::zero(), area, to)
+                }
+
+                /// Fill an area from a block of colors in memory, with the
+                /// display's own 2D engine where it can.
+                ///
+                /// `colors` are in row-major order, as for
+                /// [`fill_contiguous`](DrawTarget::fill_contiguous). The
+                /// engine is asked to apply the transform as it copies them,
+                /// with [`Blitter::blit`]; if it can't, or the area isn't
+                /// entirely on the display, they're drawn with
+                /// `fill_contiguous` instead.
+                pub fn blit(&mut self, area: &Rectangle, colors: &[D::Color]) -> Result<(), D::Error> {
+                    let size = self.bounding_box().size;
+                    if !blit::blit(self.as_mut(), $orientation, size, Point::zero(), area, colors)? {
+                        self.fill_contiguous(area, colors.iter().copied())?;
+                    }
+                    Ok(())
+                }
+            }
+
             impl<D> Deref for $name<D> {
                 type Target = D;
 
@@ -146,23 +597,181 @@ macro_rules! impl_xform {
 }
 
 // Define rotations in terms of transpose and flip. Note that transforms are
-// applied in order from last to first.
+// applied in order from last to first. The rotations are implemented directly,
+// so that each pixel is only mapped once.
 impl_xform! {
     /// No-op (identity) rotation for completeness.
-    Rotate0: ;
+    Rotate0 (Orientation::IDENTITY): ;
     /// Rotate image 90 degrees to the right.
-    Rotate90: MirrorY TransposeXY;
+    Rotate90 (Orientation::IDENTITY.rotate90()): Rot90;
     /// Rotate image 90 degrees to the left.
-    Rotate270: TransposeXY MirrorY;
+    Rotate270 (Orientation::IDENTITY.rotate270()): Rot270;
     /// Rotate image 180 degrees.
-    Rotate180: MirrorX MirrorY;
+    Rotate180 (Orientation::IDENTITY.rotate180()): Rot180;
 
     /// Transpose X and Y coordinates.
-    Transpose: TransposeXY;
+    Transpose (Orientation::IDENTITY.transpose()): TransposeXY;
     /// Mirror image around X axis.
-    FlipX: MirrorX;
+    FlipX (Orientation::IDENTITY.flip_x()): MirrorX;
     /// Mirror image around Y axis.
-    FlipY: MirrorY;
+    FlipY (Orientation::IDENTITY.flip_y()): MirrorY;
+}
+
+#[doc(hidden)]
+pub use embedded_graphics_core as __core;
+
+/// Define a named transform made of a stack of this crate's transforms.
+///
+/// Panels which need several transforms to correct them can give the
+/// combination its own type, with the same `new`, `into_inner`, `Deref` and
+/// `AsRef` accessors and [`DrawTarget`] implementation as the transforms in
+/// this crate, rather than spelling out the nested types or writing the
+/// forwarding by hand. The transforms are listed from the outside in, as they
+/// would be nested, and are named as they're exported from this crate. Any
+/// arguments to a transform's `new` after the display follow it in
+/// parentheses. Attributes and doc comments before the name are applied to
+/// the type.
+///
+/// For example, `compose_transforms!(pub PanelFix: FlipX, Transpose,
+/// Offset(Point::new(2, 1)))` defines `PanelFix<D>`, whose `new(display)`
+/// draws the same as `FlipX::new(Transpose::new(Offset::new(display,
+/// Point::new(2, 1))))`.
+#[macro_export]
+macro_rules! compose_transforms {
+    (
+        $(#[$attr:meta])*
+        $vis:vis $name:ident : $($xform:ident $(($($arg:expr),* $(,)?))?),+ $(,)?
+    ) => {
+        $(#[$attr])*
+        $vis struct $name<D> {
+            target: $crate::compose_transforms!(@type D; $($xform)+),
+        }
+
+        impl<D> $name<D> {
+            /// Apply the transforms to a display implementing `DrawTarget`.
+            $vis fn new(target: D) -> Self {
+                $name {
+                    target: $crate::compose_transforms!(@new target; $($xform ($($($arg),*)?))+),
+                }
+            }
+
+            /// Recover the inner display instance.
+            $vis fn into_inner(self) -> D {
+                $crate::compose_transforms!(@inner self.target, into_inner; $($xform)+)
+            }
+        }
+
+        impl<D> ::core::ops::Deref for $name<D> {
+            type Target = D;
+
+            fn deref(&self) -> &D {
+                ::core::convert::AsRef::as_ref(self)
+            }
+        }
+
+        impl<D> ::core::ops::DerefMut for $name<D> {
+            fn deref_mut(&mut self) -> &mut D {
+                ::core::convert::AsMut::as_mut(self)
+            }
+        }
+
+        impl<D> ::core::convert::AsRef<D> for $name<D> {
+            #[inline]
+            fn as_ref(&self) -> &D {
+                $crate::compose_transforms!(@inner &self.target, as_ref; $($xform)+)
+            }
+        }
+
+        impl<D> ::core::convert::AsMut<D> for $name<D> {
+            #[inline]
+            fn as_mut(&mut self) -> &mut D {
+                $crate::compose_transforms!(@inner &mut self.target, as_mut; $($xform)+)
+            }
+        }
+
+        impl<D> $crate::__core::geometry::Dimensions for $name<D>
+        where
+            $crate::compose_transforms!(@type D; $($xform)+): $crate::__core::geometry::Dimensions,
+        {
+            #[inline]
+            fn bounding_box(&self) -> $crate::__core::primitives::Rectangle {
+                $crate::__core::geometry::Dimensions::bounding_box(&self.target)
+            }
+        }
+
+        impl<D> $crate::__core::draw_target::DrawTarget for $name<D>
+        where
+            $crate::compose_transforms!(@type D; $($xform)+): $crate::__core::draw_target::DrawTarget,
+        {
+            type Color = <$crate::compose_transforms!(@type D; $($xform)+) as $crate::__core::draw_target::DrawTarget>::Color;
+            type Error = <$crate::compose_transforms!(@type D; $($xform)+) as $crate::__core::draw_target::DrawTarget>::Error;
+
+            #[inline]
+            fn draw_iter<I>(&mut self, pixels: I) -> ::core::result::Result<(), Self::Error>
+            where
+                I: ::core::iter::IntoIterator<Item = $crate::__core::Pixel<Self::Color>>,
+            {
+                $crate::__core::draw_target::DrawTarget::draw_iter(&mut self.target, pixels)
+            }
+
+            #[inline]
+            fn fill_contiguous<I>(
+                &mut self,
+                area: &$crate::__core::primitives::Rectangle,
+                colors: I,
+            ) -> ::core::result::Result<(), Self::Error>
+            where
+                I: ::core::iter::IntoIterator<Item = Self::Color>,
+            {
+                $crate::__core::draw_target::DrawTarget::fill_contiguous(
+                    &mut self.target,
+                    area,
+                    colors,
+                )
+            }
+
+            #[inline]
+            fn fill_solid(
+                &mut self,
+                area: &$crate::__core::primitives::Rectangle,
+                color: Self::Color,
+            ) -> ::core::result::Result<(), Self::Error> {
+                $crate::__core::draw_target::DrawTarget::fill_solid(&mut self.target, area, color)
+            }
+
+            #[inline]
+            fn clear(&mut self, color: Self::Color) -> ::core::result::Result<(), Self::Error> {
+                $crate::__core::draw_target::DrawTarget::clear(&mut self.target, color)
+            }
+        }
+    };
+
+    (@type $inner:ident; ) => { $inner };
+    (@type $inner:ident; $xform:ident $($rest:ident)*) => {
+        $crate::$xform<$crate::compose_transforms!(@type $inner; $($rest)*)>
+    };
+
+    (@new $inner:expr; ) => { $inner };
+    (@new $inner:expr; $xform:ident ($($arg:expr),*) $($rest:tt)*) => {
+        $crate::$xform::new($crate::compose_transforms!(@new $inner; $($rest)*) $(, $arg)*)
+    };
+
+    (@inner $expr:expr, $method:ident; ) => { $expr };
+    (@inner $expr:expr, into_inner; $xform:ident $($rest:ident)*) => {
+        $crate::compose_transforms!(@inner $expr.into_inner(), into_inner; $($rest)*)
+    };
+    (@inner $expr:expr, as_ref; $xform:ident $($rest:ident)*) => {
+        $crate::compose_transforms!(
+            @inner <$crate::$xform<_> as ::core::convert::AsRef<_>>::as_ref($expr), as_ref;
+            $($rest)*
+        )
+    };
+    (@inner $expr:expr, as_mut; $xform:ident $($rest:ident)*) => {
+        $crate::compose_transforms!(
+            @inner <$crate::$xform<_> as ::core::convert::AsMut<_>>::as_mut($expr), as_mut;
+            $($rest)*
+        )
+    };
 }
 
 /// Image rotation direction and amount.
@@ -178,6 +787,19 @@ pub enum Rotation {
     Rotate270,
 }
 
+impl Rotation {
+    /// Return the rotation which undoes this one.
+    pub const fn inverse(self) -> Rotation {
+        match self {
+            Rotation::Rotate0 => Rotation::Rotate0,
+            Rotation::Rotate90 => Rotation::Rotate270,
+            Rotation::Rotate180 => Rotation::Rotate180,
+            Rotation::Rotate270 => Rotation::Rotate90,
+        }
+    }
+}
+
+#[cfg(not(feature = "opt-size"))]
 enum RotateInner<D> {
     Rotate0(Rotate0<D>),
     Rotate90(Rotate90<D>),
@@ -192,10 +814,16 @@ enum RotateInner<D> {
 /// the other implementations, so it should be functionally identical. The only
 /// overhead is the cost of dispatching to the appropriate implementation on
 /// each call.
+///
+/// Dispatching means that all four implementations are compiled for each
+/// display type. With the `opt-size` feature, `Rotate` is instead a single
+/// implementation which computes the mapping from the stored rotation, which
+/// is smaller but slightly slower per pixel.
 pub struct Rotate<D> {
     target: RotateInner<D>,
 }
 
+#[cfg(not(feature = "opt-size"))]
 macro_rules! rotate_impl {
     (& $rot:expr, $func:ident ( $($args:expr),* $(,)?)) => {
         match &$rot.target {
@@ -223,8 +851,22 @@ macro_rules! rotate_impl {
     };
 }
 
+#[cfg(feature = "opt-size")]
+macro_rules! rotate_impl {
+    (& $rot:expr, $func:ident ( $($args:expr),* $(,)?)) => {
+        $rot.target.$func($($args),*)
+    };
+    (&mut $rot:expr, $func:ident ( $($args:expr),* $(,)?)) => {
+        $rot.target.$func($($args),*)
+    };
+    ($rot:expr, $func:ident ( $($args:expr),* $(,)?)) => {
+        $rot.target.$func($($args),*)
+    };
+}
+
 impl<D> Rotate<D> {
     /// Create a new rotation transformation using the given [`Rotation`].
+    #[cfg(not(feature = "opt-size"))]
     pub fn new(rot: Rotation, target: D) -> Self {
         let target = match rot {
             Rotation::Rotate0 => RotateInner::Rotate0(Rotate0::new(target)),
@@ -235,12 +877,102 @@ impl<D> Rotate<D> {
         Rotate { target }
     }
 
+    /// Create a new rotation transformation using the given [`Rotation`].
+    #[cfg(feature = "opt-size")]
+    pub fn new(rotation: Rotation, target: D) -> Self {
+        Rotate {
+            target: RotateInner { target, rotation },
+        }
+    }
+
+    /// Return the rotation being applied.
+    #[cfg(not(feature = "opt-size"))]
+    pub fn rotation(&self) -> Rotation {
+        match self.target {
+            RotateInner::Rotate0(_) => Rotation::Rotate0,
+            RotateInner::Rotate90(_) => Rotation::Rotate90,
+            RotateInner::Rotate180(_) => Rotation::Rotate180,
+            RotateInner::Rotate270(_) => Rotation::Rotate270,
+        }
+    }
+
+    /// Return the rotation being applied.
+    #[cfg(feature = "opt-size")]
+    pub fn rotation(&self) -> Rotation {
+        self.target.rotation
+    }
+
     /// Recover the inner display instance.
     pub fn into_inner(self) -> D {
         rotate_impl!(self, into_inner())
     }
 }
 
+impl<D: DrawTarget> Rotate<D> {
+    /// Draw a slice of pixels with a single call to the display, like
+    /// [`Rotate90::set_pixels`].
+    pub fn set_pixels(&mut self, pixels: &mut [Pixel<D::Color>]) -> Result<(), D::Error> {
+        let size = self.bounding_box().size;
+        let orientation = self.rotation().into();
+        oriented::set_pixels(self.as_mut(), orientation, size, Point::zero(), pixels)
+    }
+
+    /// Fill an area through an `N` pixel buffer, like
+    /// [`Rotate90::fill_contiguous_buffered`].
+    pub fn fill_contiguous_buffered<const N: usize, I>(
+        &mut self,
+        area: &Rectangle,
+        colors: I,
+    ) -> Result<(), D::Error>
+    where
+        I: IntoIterator<Item = D::Color>,
+    {
+        let size = self.bounding_box().size;
+        let orientation = self.rotation().into();
+        oriented::fill_contiguous_buffered::<_, N>(
+            self.as_mut(),
+            orientation,
+            size,
+            Point::zero(),
+            area,
+            colors,
+        )
+    }
+
+    /// Draw a single pixel, like [`Rotate90::write_pixel`].
+    pub fn write_pixel(&mut self, point: Point, color: D::Color) -> Result<(), D::Error> {
+        let size = self.bounding_box().size;
+        let orientation = self.rotation().into();
+        let pixel = Pixel(point, color);
+        oriented::write_pixel(
+            self.as_mut(),
+            orientation,
+            size,
+            Point::zero(),
+            pixel,
+            false,
+        )
+    }
+
+    /// Draw a single pixel without checking it's on the display, like
+    /// [`Rotate90::write_pixel_unchecked`].
+    pub fn write_pixel_unchecked(&mut self, point: Point, color: D::Color) -> Result<(), D::Error> {
+        let size = self.bounding_box().size;
+        let orientation = self.rotation().into();
+        let pixel = Pixel(point, color);
+        oriented::write_pixel(self.as_mut(), orientation, size, Point::zero(), pixel, true)
+    }
+
+    /// Draw an image in the display's order, like [`Rotate90::draw_image`].
+    pub fn draw_image<I>(&mut self, image: &I, top_left: Point) -> Result<(), D::Error>
+    where
+        I: ImageDrawable<Color = D::Color>,
+    {
+        let orientation = self.rotation().into();
+        image::draw_image(self, orientation, image, top_left)
+    }
+}
+
 impl<D> Deref for Rotate<D> {
     type Target = D;
 
@@ -267,6 +999,60 @@ impl<D> AsMut<D> for Rotate<D> {
     }
 }
 
+impl<D: AddressWindow> Rotate<D> {
+    /// Fill an area with the display's own windowed writes, like
+    /// [`Rotate90::fill_window`].
+    pub fn fill_window<const N: usize, I>(
+        &mut self,
+        area: &Rectangle,
+        colors: I,
+    ) -> Result<(), D::Error>
+    where
+        I: IntoIterator<Item = D::Color>,
+    {
+        let size = self.bounding_box().size;
+        let orientation = self.rotation().into();
+        address::fill_window::<_, N>(
+            self.as_mut(),
+            orientation,
+            size,
+            Point::zero(),
+            area,
+            colors,
+        )
+    }
+}
+
+impl<D: Blitter> Rotate<D> {
+    /// Copy an area of the display with the display's own 2D engine, like
+    /// [`Rotate90::copy_area`].
+    pub fn copy_area(&mut self, area: &Rectangle, to: Point) -> Result<(), D::Error> {
+        let size = self.bounding_box().size;
+        let orientation = self.rotation().into();
+        blit::copy_area(self.as_mut(), orientation, size, Point::zero(), area, to)
+    }
+
+    /// Fill an area from a block of colors in memory, with the display's own
+    /// 2D engine where it can, like [`Rotate90::blit`].
+    pub fn blit(&mut self, area: &Rectangle, colors: &[D::Color]) -> Result<(), D::Error> {
+        let size = self.bounding_box().size;
+        let orientation = self.rotation().into();
+        if !blit::blit(
+            self.as_mut(),
+            orientation,
+            size,
+            Point::zero(),
+            area,
+            colors,
+        )? {
+            self.fill_contiguous(area, colors.iter().copied())?;
+        }
+        Ok(())
+    }
+}
+
+impl_by_mut_ref!([D] Rotate<D>);
+
 impl<D: Dimensions> Dimensions for Rotate<D> {
     fn bounding_box(&self) -> Rectangle {
         rotate_impl!(&self, bounding_box())
@@ -300,7 +1086,88 @@ impl<D: DrawTarget> DrawTarget for Rotate<D> {
     }
 }
 
+/// The single implementation of [`Rotate`] with the `opt-size` feature.
+#[cfg(feature = "opt-size")]
+struct RotateInner<D> {
+    target: D,
+    rotation: Rotation,
+}
+
+#[cfg(feature = "opt-size")]
+impl<D> RotateInner<D> {
+    fn into_inner(self) -> D {
+        self.target
+    }
+
+    fn as_ref(&self) -> &D {
+        &self.target
+    }
+
+    fn as_mut(&mut self) -> &mut D {
+        &mut self.target
+    }
+}
+
+#[cfg(feature = "opt-size")]
+impl<D: Dimensions> Dimensions for RotateInner<D> {
+    fn bounding_box(&self) -> Rectangle {
+        use r#impl::Transpose as _;
+
+        let bbox = self.target.bounding_box();
+        match self.rotation {
+            Rotation::Rotate0 | Rotation::Rotate180 => bbox,
+            Rotation::Rotate90 | Rotation::Rotate270 => bbox.transpose(),
+        }
+    }
+}
+
+#[cfg(feature = "opt-size")]
+impl<D: DrawTarget> DrawTarget for RotateInner<D> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let size = self.target.bounding_box().size;
+        let rotation = self.rotation;
+
+        self.target.draw_iter(
+            pixels
+                .into_iter()
+                .map(|Pixel(loc, col)| Pixel(util::rotate_point(rotation, size, loc), col)),
+        )
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let size = self.bounding_box().size;
+        oriented::fill_contiguous(
+            &mut self.target,
+            self.rotation.into(),
+            size,
+            Point::zero(),
+            area,
+            colors,
+        )
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let size = self.target.bounding_box().size;
+        let area = util::rotate_rect(self.rotation, size, area);
+        self.target.fill_solid(&area, color)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.target.clear(color)
+    }
+}
+
 mod r#impl {
+    use crate::{oriented, Orientation};
     use embedded_graphics_core::{prelude::*, primitives::Rectangle};
 
     pub(crate) trait Transpose {
@@ -384,6 +1251,21 @@ mod r#impl {
             )
         }
 
+        fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+        where
+            I: IntoIterator<Item = Self::Color>,
+        {
+            let size = self.bounding_box().size;
+            oriented::fill_contiguous(
+                &mut self.target,
+                Orientation::IDENTITY.transpose(),
+                size,
+                Point::zero(),
+                area,
+                colors,
+            )
+        }
+
         fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
             let area = area.transpose();
             self.target.fill_solid(&area, color)
@@ -445,8 +1327,23 @@ mod r#impl {
             )
         }
 
+        fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+        where
+            I: IntoIterator<Item = Self::Color>,
+        {
+            let size = self.bounding_box().size;
+            oriented::fill_contiguous(
+                &mut self.target,
+                Orientation::IDENTITY.flip_x(),
+                size,
+                Point::zero(),
+                area,
+                colors,
+            )
+        }
+
         fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
-            let width = self.bounding_box().size.width as i32 - 1;
+            let width = self.bounding_box().size.width as i32;
             let area = Rectangle {
                 top_left: Point {
                     x: width - area.top_left.x - area.size.width as i32,
@@ -513,8 +1410,23 @@ mod r#impl {
             )
         }
 
+        fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+        where
+            I: IntoIterator<Item = Self::Color>,
+        {
+            let size = self.bounding_box().size;
+            oriented::fill_contiguous(
+                &mut self.target,
+                Orientation::IDENTITY.flip_y(),
+                size,
+                Point::zero(),
+                area,
+                colors,
+            )
+        }
+
         fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
-            let height = self.bounding_box().size.height as i32 - 1;
+            let height = self.bounding_box().size.height as i32;
             let area = Rectangle {
                 top_left: Point {
                     x: area.top_left.x,
@@ -530,4 +1442,264 @@ mod r#impl {
             self.target.clear(color)
         }
     }
+
+    pub(crate) struct Rot90<D> {
+        target: D,
+    }
+
+    impl<D> Rot90<D> {
+        pub(crate) fn new(target: D) -> Self {
+            Rot90 { target }
+        }
+
+        pub(crate) fn into_inner(self) -> D {
+            self.target
+        }
+    }
+
+    impl<D> AsRef<D> for Rot90<D> {
+        fn as_ref(&self) -> &D {
+            &self.target
+        }
+    }
+
+    impl<D> AsMut<D> for Rot90<D> {
+        fn as_mut(&mut self) -> &mut D {
+            &mut self.target
+        }
+    }
+
+    impl<D: Dimensions> Dimensions for Rot90<D> {
+        #[inline]
+        fn bounding_box(&self) -> Rectangle {
+            self.target.bounding_box().transpose()
+        }
+    }
+
+    impl<D: DrawTarget> DrawTarget for Rot90<D> {
+        type Color = D::Color;
+        type Error = D::Error;
+
+        fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+        where
+            I: IntoIterator<Item = Pixel<Self::Color>>,
+        {
+            let width = self.target.bounding_box().size.width as i32 - 1;
+
+            self.target.draw_iter(
+                pixels
+                    .into_iter()
+                    .map(|Pixel(Point { x, y }, col)| Pixel(Point { x: width - y, y: x }, col)),
+            )
+        }
+
+        fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+        where
+            I: IntoIterator<Item = Self::Color>,
+        {
+            let size = self.bounding_box().size;
+            oriented::fill_contiguous(
+                &mut self.target,
+                Orientation::IDENTITY.rotate90(),
+                size,
+                Point::zero(),
+                area,
+                colors,
+            )
+        }
+
+        fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+            let width = self.target.bounding_box().size.width as i32;
+            let area = Rectangle {
+                top_left: Point {
+                    x: width - area.top_left.y - area.size.height as i32,
+                    y: area.top_left.x,
+                },
+                size: area.size.transpose(),
+            };
+            self.target.fill_solid(&area, color)
+        }
+
+        #[inline]
+        fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+            self.target.clear(color)
+        }
+    }
+
+    pub(crate) struct Rot270<D> {
+        target: D,
+    }
+
+    impl<D> Rot270<D> {
+        pub(crate) fn new(target: D) -> Self {
+            Rot270 { target }
+        }
+
+        pub(crate) fn into_inner(self) -> D {
+            self.target
+        }
+    }
+
+    impl<D> AsRef<D> for Rot270<D> {
+        fn as_ref(&self) -> &D {
+            &self.target
+        }
+    }
+
+    impl<D> AsMut<D> for Rot270<D> {
+        fn as_mut(&mut self) -> &mut D {
+            &mut self.target
+        }
+    }
+
+    impl<D: Dimensions> Dimensions for Rot270<D> {
+        #[inline]
+        fn bounding_box(&self) -> Rectangle {
+            self.target.bounding_box().transpose()
+        }
+    }
+
+    impl<D: DrawTarget> DrawTarget for Rot270<D> {
+        type Color = D::Color;
+        type Error = D::Error;
+
+        fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+        where
+            I: IntoIterator<Item = Pixel<Self::Color>>,
+        {
+            let height = self.target.bounding_box().size.height as i32 - 1;
+
+            self.target
+                .draw_iter(pixels.into_iter().map(|Pixel(Point { x, y }, col)| {
+                    Pixel(
+                        Point {
+                            x: y,
+                            y: height - x,
+                        },
+                        col,
+                    )
+                }))
+        }
+
+        fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+        where
+            I: IntoIterator<Item = Self::Color>,
+        {
+            let size = self.bounding_box().size;
+            oriented::fill_contiguous(
+                &mut self.target,
+                Orientation::IDENTITY.rotate270(),
+                size,
+                Point::zero(),
+                area,
+                colors,
+            )
+        }
+
+        fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+            let height = self.target.bounding_box().size.height as i32;
+            let area = Rectangle {
+                top_left: Point {
+                    x: area.top_left.y,
+                    y: height - area.top_left.x - area.size.width as i32,
+                },
+                size: area.size.transpose(),
+            };
+            self.target.fill_solid(&area, color)
+        }
+
+        #[inline]
+        fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+            self.target.clear(color)
+        }
+    }
+
+    pub(crate) struct Rot180<D> {
+        target: D,
+    }
+
+    impl<D> Rot180<D> {
+        pub(crate) fn new(target: D) -> Self {
+            Rot180 { target }
+        }
+
+        pub(crate) fn into_inner(self) -> D {
+            self.target
+        }
+    }
+
+    impl<D> AsRef<D> for Rot180<D> {
+        fn as_ref(&self) -> &D {
+            &self.target
+        }
+    }
+
+    impl<D> AsMut<D> for Rot180<D> {
+        fn as_mut(&mut self) -> &mut D {
+            &mut self.target
+        }
+    }
+
+    impl<D: Dimensions> Dimensions for Rot180<D> {
+        #[inline]
+        fn bounding_box(&self) -> Rectangle {
+            self.target.bounding_box()
+        }
+    }
+
+    impl<D: DrawTarget> DrawTarget for Rot180<D> {
+        type Color = D::Color;
+        type Error = D::Error;
+
+        fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+        where
+            I: IntoIterator<Item = Pixel<Self::Color>>,
+        {
+            let Size { width, height } = self.bounding_box().size;
+            let (width, height) = (width as i32 - 1, height as i32 - 1);
+
+            self.target
+                .draw_iter(pixels.into_iter().map(|Pixel(Point { x, y }, col)| {
+                    Pixel(
+                        Point {
+                            x: width - x,
+                            y: height - y,
+                        },
+                        col,
+                    )
+                }))
+        }
+
+        fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+        where
+            I: IntoIterator<Item = Self::Color>,
+        {
+            let size = self.bounding_box().size;
+            oriented::fill_contiguous(
+                &mut self.target,
+                Orientation::IDENTITY.rotate180(),
+                size,
+                Point::zero(),
+                area,
+                colors,
+            )
+        }
+
+        fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+            let Size { width, height } = self.bounding_box().size;
+            let area = Rectangle {
+                top_left: Point {
+                    x: width as i32 - area.top_left.x - area.size.width as i32,
+                    y: height as i32 - area.top_left.y - area.size.height as i32,
+                },
+                size: area.size,
+            };
+            self.target.fill_solid(&area, color)
+        }
+
+        #[inline]
+        fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+            self.target.clear(color)
+        }
+    }
 }
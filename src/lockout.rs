@@ -0,0 +1,181 @@
+//! Protect areas of a display from being drawn over.
+
+use crate::util;
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+
+#[cfg(test)]
+mod tests;
+
+/// Error from drawing to a [`Lockout`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum LockoutError<E> {
+    /// Drawing touched a protected area. Only returned if the `Lockout` was
+    /// configured to report errors.
+    Protected,
+    /// The wrapped display returned an error.
+    Display(E),
+}
+
+impl<E> From<E> for LockoutError<E> {
+    fn from(err: E) -> Self {
+        LockoutError::Display(err)
+    }
+}
+
+/// Prevent drawing over protected areas.
+///
+/// Up to `N` rectangles can be protected at a time, and can be changed at
+/// runtime. Any pixels drawn inside them are discarded, for example so a
+/// widget library sharing the display can't draw over a status bar owned by
+/// another module. Optionally, drawing which touches a protected area can also
+/// be reported as [`LockoutError::Protected`]; the rest of the drawing is still
+/// performed.
+///
+/// Protected areas are in this adapter's coordinates, so if it's outside a
+/// rotation they're in the rotated logical coordinates, and if it's inside
+/// they're in the wrapped display's coordinates.
+pub struct Lockout<D, const N: usize> {
+    target: D,
+    areas: [Option<Rectangle>; N],
+    report: bool,
+}
+
+impl<D, const N: usize> Lockout<D, N> {
+    /// Wrap a display, initially with no protected areas.
+    pub fn new(target: D) -> Self {
+        Lockout {
+            target,
+            areas: [None; N],
+            report: false,
+        }
+    }
+
+    /// Return [`LockoutError::Protected`] from drawing which touches a
+    /// protected area.
+    pub fn with_errors(mut self) -> Self {
+        self.report = true;
+        self
+    }
+
+    /// Protect `area` in slot `index`, replacing any area already there.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than `N`.
+    pub fn protect(&mut self, index: usize, area: Rectangle) {
+        self.areas[index] = Some(area);
+    }
+
+    /// Remove the protected area in slot `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than `N`.
+    pub fn unprotect(&mut self, index: usize) {
+        self.areas[index] = None;
+    }
+
+    /// Return the protected areas.
+    pub fn areas(&self) -> &[Option<Rectangle>; N] {
+        &self.areas
+    }
+
+    fn is_protected(&self, p: Point) -> bool {
+        self.areas.iter().flatten().any(|area| area.contains(p))
+    }
+
+    fn overlaps(&self, area: &Rectangle) -> bool {
+        self.areas
+            .iter()
+            .flatten()
+            .any(|protected| !util::is_empty(&protected.intersection(area)))
+    }
+
+    fn result<E>(&self, touched: bool) -> Result<(), LockoutError<E>> {
+        if touched && self.report {
+            Err(LockoutError::Protected)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl_wrapper!(Lockout<D; const N: usize>);
+
+impl<D: Dimensions, const N: usize> Dimensions for Lockout<D, N> {
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+impl<D: DrawTarget, const N: usize> DrawTarget for Lockout<D, N> {
+    type Color = D::Color;
+    type Error = LockoutError<D::Error>;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let Lockout { target, areas, .. } = self;
+        let mut touched = false;
+
+        target.draw_iter(pixels.into_iter().filter(|Pixel(loc, _)| {
+            let protected = areas.iter().flatten().any(|area| area.contains(*loc));
+            touched |= protected;
+            !protected
+        }))?;
+        self.result(touched)
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        if self.overlaps(area) {
+            self.draw_iter(
+                util::points(area)
+                    .zip(colors)
+                    .map(|(loc, col)| Pixel(loc, col)),
+            )
+        } else {
+            Ok(self.target.fill_contiguous(area, colors)?)
+        }
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        if !self.overlaps(area) {
+            return Ok(self.target.fill_solid(area, color)?);
+        }
+
+        // Fill the unprotected spans of each row
+        for row in 0..area.size.height as i32 {
+            let y = area.top_left.y + row;
+            let mut x = area.top_left.x;
+            let right = x + area.size.width as i32;
+
+            while x < right {
+                let start = x;
+                while x < right && !self.is_protected(Point::new(x, y)) {
+                    x += 1;
+                }
+                if x > start {
+                    let span =
+                        Rectangle::new(Point::new(start, y), Size::new((x - start) as u32, 1));
+                    self.target.fill_solid(&span, color)?;
+                }
+                while x < right && self.is_protected(Point::new(x, y)) {
+                    x += 1;
+                }
+            }
+        }
+        self.result(true)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        if self.areas.iter().all(Option::is_none) {
+            Ok(self.target.clear(color)?)
+        } else {
+            self.fill_solid(&self.bounding_box(), color)
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use super::*;
+use crate::{tests::rect, Rotate180};
+use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+#[test]
+fn lockout() {
+    let mut disp = Lockout::<_, 2>::new(MockDisplay::<BinaryColor>::new());
+    disp.protect(0, rect(1, 1, 2, 1));
+    disp.protect(1, rect(4, 0, 1, 10));
+
+    disp.fill_solid(&rect(0, 0, 6, 3), BinaryColor::On).unwrap();
+    disp.unprotect(1);
+    disp.draw_iter([
+        Pixel(Point::new(4, 3), BinaryColor::Off),
+        Pixel(Point::new(1, 1), BinaryColor::Off),
+    ])
+    .unwrap();
+
+    disp.as_ref().assert_pattern(&[
+        "#### #", //
+        "#  # #", //
+        "#### #", //
+        "    . ", //
+    ]);
+}
+
+#[test]
+fn lockout_errors() {
+    let mut disp = Lockout::<_, 1>::new(MockDisplay::<BinaryColor>::new()).with_errors();
+    disp.protect(0, rect(0, 0, 1, 1));
+
+    assert_eq!(
+        disp.fill_contiguous(&rect(0, 0, 2, 1), [BinaryColor::On; 2]),
+        Err(LockoutError::Protected)
+    );
+    assert_eq!(
+        disp.fill_contiguous(&rect(0, 1, 2, 1), [BinaryColor::On; 2]),
+        Ok(())
+    );
+    assert_eq!(disp.as_ref().affected_area(), rect(0, 0, 2, 2));
+    assert_eq!(disp.as_ref().get_pixel(Point::zero()), None);
+}
+
+#[test]
+fn lockout_rotated() {
+    // Protected areas are in logical coordinates when outside the rotation
+    let mut disp = Lockout::<_, 1>::new(Rotate180::new(MockDisplay::<BinaryColor>::new()));
+    disp.protect(0, rect(0, 0, 64, 62));
+    disp.draw_iter([
+        Pixel(Point::new(0, 0), BinaryColor::On),
+        Pixel(Point::new(0, 63), BinaryColor::On),
+    ])
+    .unwrap();
+
+    assert_eq!(disp.as_ref().as_ref().affected_area(), rect(63, 0, 1, 1));
+}
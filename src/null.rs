@@ -0,0 +1,69 @@
+//! A display which discards everything drawn to it.
+
+use core::{convert::Infallible, marker::PhantomData};
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+
+#[cfg(test)]
+mod tests;
+
+/// A display of a given size which accepts and discards all drawing.
+///
+/// This is useful for headless operation, for measuring the overhead of a
+/// stack of transforms on its own, and for tests which don't need the
+/// strictness of `MockDisplay`. It can be used as the inner display of any
+/// wrapper.
+///
+/// Pixel and color iterators are still consumed, so that the work of any
+/// transforms they pass through is done. The number of pixels drawn is
+/// counted, including any outside the display's bounds.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct NullDisplay<C> {
+    size: Size,
+    pixels: u64,
+    _color: PhantomData<C>,
+}
+
+impl<C> NullDisplay<C> {
+    /// Create a display of the given size.
+    pub const fn new(size: Size) -> Self {
+        NullDisplay {
+            size,
+            pixels: 0,
+            _color: PhantomData,
+        }
+    }
+
+    /// Return the number of pixels drawn so far.
+    pub fn pixels(&self) -> u64 {
+        self.pixels
+    }
+
+    /// Reset the count of pixels drawn.
+    pub fn reset(&mut self) {
+        self.pixels = 0;
+    }
+}
+
+impl<C> OriginDimensions for NullDisplay<C> {
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+impl<C: PixelColor> DrawTarget for NullDisplay<C> {
+    type Color = C;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.pixels += pixels.into_iter().count() as u64;
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, _color: Self::Color) -> Result<(), Self::Error> {
+        self.pixels += area.size.width as u64 * area.size.height as u64;
+        Ok(())
+    }
+}
@@ -0,0 +1,33 @@
+use super::*;
+use crate::{Rotate, Rotate90, Rotation};
+use embedded_graphics::{pixelcolor::BinaryColor, primitives::Rectangle};
+
+#[test]
+fn discards_and_counts() {
+    let mut disp = Rotate90::new(NullDisplay::<BinaryColor>::new(Size::new(40, 20)));
+    assert_eq!(
+        disp.bounding_box(),
+        Rectangle::new(Point::zero(), Size::new(20, 40))
+    );
+
+    // Out of bounds drawing and overdraw are both fine
+    disp.draw_iter([
+        Pixel(Point::new(1, 1), BinaryColor::On),
+        Pixel(Point::new(1, 1), BinaryColor::Off),
+        Pixel(Point::new(100, -5), BinaryColor::On),
+    ])
+    .unwrap();
+    assert_eq!(disp.pixels(), 3);
+
+    disp.fill_solid(
+        &Rectangle::new(Point::new(2, 3), Size::new(4, 5)),
+        BinaryColor::On,
+    )
+    .unwrap();
+    assert_eq!(disp.pixels(), 23);
+
+    disp.reset();
+    let mut disp = Rotate::new(Rotation::Rotate180, disp.into_inner());
+    disp.clear(BinaryColor::On).unwrap();
+    assert_eq!(disp.pixels(), 800);
+}
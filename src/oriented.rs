@@ -0,0 +1,1062 @@
+//! Orientations which compose into a single coordinate mapping.
+
+use crate::{
+    address, blit, geometry, image, r#impl::Transpose as _, util, AddressWindow, Blitter, FlipX,
+    FlipY, Offset, Rotate, Rotate0, Rotate180, Rotate270, Rotate90, Rotation, Transpose,
+};
+use core::fmt;
+use embedded_graphics_core::{image::ImageDrawable, prelude::*, primitives::Rectangle};
+
+#[cfg(test)]
+mod tests;
+
+/// One of the eight orientations of a display, as a runtime value.
+///
+/// Each point is first mirrored horizontally if `flip_x`, then vertically if
+/// `flip_y`, and then transposed if `transpose`. This is always normalized: the
+/// composing methods such as [`rotate90`](Self::rotate90) and
+/// [`flip_x`](Self::flip_x), which behave like wrapping a display in the
+/// corresponding wrapper type, return the single equivalent orientation.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Orientation {
+    pub(crate) transpose: bool,
+    pub(crate) flip_x: bool,
+    pub(crate) flip_y: bool,
+}
+
+impl Orientation {
+    /// The orientation which leaves a display unchanged.
+    pub const IDENTITY: Orientation = Orientation::new(false, false, false);
+
+    /// All eight orientations: each rotation, followed by each rotation
+    /// mirrored around the X axis first.
+    pub const ALL: [Orientation; 8] = [
+        Orientation::new(false, false, false),
+        Orientation::new(true, false, true),
+        Orientation::new(false, true, true),
+        Orientation::new(true, true, false),
+        Orientation::new(false, true, false),
+        Orientation::new(true, true, true),
+        Orientation::new(false, false, true),
+        Orientation::new(true, false, false),
+    ];
+
+    /// Construct an orientation from its parts.
+    pub const fn new(transpose: bool, flip_x: bool, flip_y: bool) -> Self {
+        Orientation {
+            transpose,
+            flip_x,
+            flip_y,
+        }
+    }
+
+    /// Returns true if the orientation swaps the X and Y axes.
+    pub const fn swaps_axes(self) -> bool {
+        self.transpose
+    }
+
+    /// Return the orientation which undoes this one.
+    pub const fn inverse(self) -> Self {
+        if self.transpose {
+            Orientation::new(true, self.flip_y, self.flip_x)
+        } else {
+            self
+        }
+    }
+
+    /// Mirror around the X axis, like [`FlipX`].
+    pub const fn flip_x(self) -> Self {
+        Orientation::new(self.transpose, !self.flip_x, self.flip_y)
+    }
+
+    /// Mirror around the Y axis, like [`FlipY`].
+    pub const fn flip_y(self) -> Self {
+        Orientation::new(self.transpose, self.flip_x, !self.flip_y)
+    }
+
+    /// Transpose X and Y coordinates, like [`Transpose`].
+    pub const fn transpose(self) -> Self {
+        Orientation::new(!self.transpose, self.flip_y, self.flip_x)
+    }
+
+    /// Rotate 90 degrees to the right, like [`Rotate90`].
+    pub const fn rotate90(self) -> Self {
+        self.transpose().flip_y()
+    }
+
+    /// Rotate 180 degrees, like [`Rotate180`].
+    pub const fn rotate180(self) -> Self {
+        self.flip_x().flip_y()
+    }
+
+    /// Rotate 90 degrees to the left, like [`Rotate270`].
+    pub const fn rotate270(self) -> Self {
+        self.transpose().flip_x()
+    }
+
+    /// Rotate by `rotation`, like [`Rotate`].
+    pub const fn rotate(self, rotation: Rotation) -> Self {
+        match rotation {
+            Rotation::Rotate0 => self,
+            Rotation::Rotate90 => self.rotate90(),
+            Rotation::Rotate180 => self.rotate180(),
+            Rotation::Rotate270 => self.rotate270(),
+        }
+    }
+
+    /// Return the orientation after this one in [`Orientation::ALL`], wrapping
+    /// around after the last, for cycling through them all.
+    pub fn next(self) -> Self {
+        let all = Orientation::ALL;
+        let idx = all.iter().position(|&o| o == self).unwrap_or(0);
+        all[(idx + 1) % all.len()]
+    }
+
+    /// Map the bounding box of the wrapped display to the logical one.
+    fn map_bounding_box(self, bbox: Rectangle) -> Rectangle {
+        if self.transpose {
+            bbox.transpose()
+        } else {
+            bbox
+        }
+    }
+
+    /// Map a point on a logical display of `size` to the wrapped display.
+    #[inline]
+    fn map_point(self, size: Size, p: Point) -> Point {
+        geometry::transform_point(self, self.physical_bounds(size), p)
+    }
+
+    /// Map an area on a logical display of `size` to the wrapped display.
+    fn map_rect(self, size: Size, area: &Rectangle) -> Rectangle {
+        geometry::transform_rect(self, self.physical_bounds(size), *area)
+    }
+
+    /// The physical bounds of a logical display of `size`.
+    fn physical_bounds(self, size: Size) -> Rectangle {
+        Rectangle::new(Point::zero(), geometry::inverse_transform_size(self, size))
+    }
+
+    /// Map a displacement in logical coordinates to the wrapped display.
+    fn map_offset(self, offset: Point) -> Point {
+        let x = if self.flip_x { -offset.x } else { offset.x };
+        let y = if self.flip_y { -offset.y } else { offset.y };
+
+        if self.transpose {
+            Point::new(y, x)
+        } else {
+            Point::new(x, y)
+        }
+    }
+}
+
+impl Default for Orientation {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl From<Rotation> for Orientation {
+    fn from(rotation: Rotation) -> Self {
+        Orientation::IDENTITY.rotate(rotation)
+    }
+}
+
+impl fmt::Display for Orientation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const NAMES: [&str; 4] = ["Rotate0", "Rotate90", "Rotate180", "Rotate270"];
+
+        let idx = Orientation::ALL.iter().position(|o| o == self).unwrap_or(0);
+        f.write_str(NAMES[idx % 4])?;
+        if idx >= 4 {
+            f.write_str(" mirrored")?;
+        }
+        Ok(())
+    }
+}
+
+/// Map `pixels` on a logical display of `size` to `target` in place, and draw
+/// them with a single call.
+pub(crate) fn set_pixels<D: DrawTarget>(
+    target: &mut D,
+    orientation: Orientation,
+    size: Size,
+    offset: Point,
+    pixels: &mut [Pixel<D::Color>],
+) -> Result<(), D::Error> {
+    for Pixel(loc, _) in pixels.iter_mut() {
+        *loc = orientation.map_point(size, *loc) + offset;
+    }
+    target.draw_iter(pixels.iter().copied())
+}
+
+/// Map a single pixel on a logical display of `size` to `target`, and draw it
+/// if `unchecked` or it's on the display.
+pub(crate) fn write_pixel<D: DrawTarget>(
+    target: &mut D,
+    orientation: Orientation,
+    size: Size,
+    offset: Point,
+    pixel: Pixel<D::Color>,
+    unchecked: bool,
+) -> Result<(), D::Error> {
+    let Pixel(loc, color) = pixel;
+    if !unchecked && !Rectangle::new(Point::zero(), size).contains(loc) {
+        return Ok(());
+    }
+
+    let loc = orientation.map_point(size, loc) + offset;
+    target.draw_iter(core::iter::once(Pixel(loc, color)))
+}
+
+/// Number of colors buffered to reverse a line which runs backwards on the
+/// display.
+const LINE_CHUNK: usize = 32;
+
+/// Fill `area` on a logical display of `size` with contiguous colors.
+///
+/// An area a single row or column wide is still a single row or column of
+/// `target`, so it's filled with `fill_contiguous` rather than pixel by pixel.
+/// If it runs backwards on `target`, it's reversed a chunk at a time, filling
+/// each chunk from the far end. Other areas are drawn pixel by pixel.
+pub(crate) fn fill_contiguous<D: DrawTarget>(
+    target: &mut D,
+    orientation: Orientation,
+    size: Size,
+    offset: Point,
+    area: &Rectangle,
+    colors: impl IntoIterator<Item = D::Color>,
+) -> Result<(), D::Error> {
+    if orientation == Orientation::IDENTITY {
+        return target.fill_contiguous(&util::translate(area, offset), colors);
+    }
+
+    let bounds = Rectangle::new(offset, geometry::inverse_transform_size(orientation, size));
+    let Size { width, height } = area.size;
+    if (width != 1 && height != 1) || util::is_empty(area) {
+        return target.draw_iter(
+            util::points(area)
+                .zip(colors)
+                .map(|(loc, col)| Pixel(geometry::transform_point(orientation, bounds, loc), col)),
+        );
+    }
+
+    let physical = geometry::transform_rect(orientation, bounds, *area);
+    if geometry::transform_point(orientation, bounds, area.top_left) == physical.top_left {
+        return target.fill_contiguous(&physical, colors);
+    }
+
+    let mut colors = colors.into_iter();
+    let mut buf = match colors.next() {
+        Some(first) => [first; LINE_CHUNK],
+        None => return Ok(()),
+    };
+    let mut read = 1;
+    let step = if width == 1 {
+        Point::new(0, 1)
+    } else {
+        Point::new(1, 0)
+    };
+    let mut start = area.top_left;
+    let mut remaining = (width * height) as usize;
+
+    while remaining > 0 {
+        let len = remaining.min(LINE_CHUNK);
+        read += buf[read..len]
+            .iter_mut()
+            .zip(&mut colors)
+            .map(|(slot, color)| *slot = color)
+            .count();
+        if read == 0 {
+            break;
+        }
+
+        let chunk = Rectangle::new(
+            start,
+            if width == 1 {
+                Size::new(1, read as u32)
+            } else {
+                Size::new(read as u32, 1)
+            },
+        );
+        target.fill_contiguous(
+            &geometry::transform_rect(orientation, bounds, chunk),
+            buf[..read].iter().rev().copied(),
+        )?;
+        if read < len {
+            // Ran out of colors.
+            break;
+        }
+
+        start += step * read as i32;
+        remaining -= read;
+        read = 0;
+    }
+
+    Ok(())
+}
+
+/// Fill `area` on a logical display of `size` by reordering up to `N` colors
+/// at a time into `target`'s order.
+///
+/// The area is split into chunks of whole rows, or parts of a row if a row is
+/// longer than `N`. Each chunk is buffered and mapped to a single rectangle of
+/// `target`, which is filled with one `fill_contiguous` call.
+pub(crate) fn fill_contiguous_buffered<D: DrawTarget, const N: usize>(
+    target: &mut D,
+    orientation: Orientation,
+    size: Size,
+    offset: Point,
+    area: &Rectangle,
+    colors: impl IntoIterator<Item = D::Color>,
+) -> Result<(), D::Error> {
+    let mut colors = colors.into_iter();
+    let first = match colors.next() {
+        Some(first) if N > 0 && !util::is_empty(area) => first,
+        _ => return Ok(()),
+    };
+    let mut colors = core::iter::once(first).chain(colors);
+
+    let bounds = Rectangle::new(offset, geometry::inverse_transform_size(orientation, size));
+    let mut buf = [first; N];
+
+    for logical in util::chunks(area, N) {
+        let len = (logical.size.width * logical.size.height) as usize;
+        let read = buf[..len]
+            .iter_mut()
+            .zip(&mut colors)
+            .map(|(slot, color)| *slot = color)
+            .count();
+
+        if read < len {
+            // Ran out of colors, so draw what there is.
+            return target.draw_iter(util::points(&logical).zip(&buf[..read]).map(
+                |(loc, &col)| Pixel(geometry::transform_point(orientation, bounds, loc), col),
+            ));
+        }
+
+        let physical = geometry::transform_rect(orientation, bounds, logical);
+        let stride = logical.size.width as i32;
+        target.fill_contiguous(
+            &physical,
+            util::points(&physical).map(|p| {
+                let p =
+                    geometry::inverse_transform_point(orientation, bounds, p) - logical.top_left;
+                buf[(p.y * stride + p.x) as usize]
+            }),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// A display in any of its eight orientations, with a single coordinate
+/// mapping.
+///
+/// Nesting wrappers such as `FlipX<Rotate90<D>>` applies each one in turn, each
+/// looking up the bounding box and doing its own arithmetic for every pixel.
+/// Instead, `Oriented` is built with [`new`](Oriented::new) and then composed
+/// with methods such as [`rotate90`](Oriented::rotate90) and
+/// [`flip_x`](Oriented::flip_x), which change its type to the combined
+/// orientation. However many operations are composed, drawing does a single
+/// bounds lookup per call and a single mapping per pixel.
+///
+/// The orientation is encoded in the const parameters: each point is first
+/// mirrored horizontally if `FLIP_X`, then vertically if `FLIP_Y`, and then
+/// transposed if `TRANSPOSE`. Each composing method behaves like wrapping the
+/// display in the corresponding wrapper type, so for example
+/// `Oriented::new(display).rotate90().flip_x()` draws the same as
+/// `FlipX::new(Rotate90::new(display))`.
+pub struct Oriented<D, const TRANSPOSE: bool, const FLIP_X: bool, const FLIP_Y: bool> {
+    target: D,
+}
+
+impl<D> Oriented<D, false, false, false> {
+    /// Wrap a display implementing [`DrawTarget`], initially unchanged.
+    pub fn new(target: D) -> Self {
+        Oriented { target }
+    }
+}
+
+impl_wrapper!(Oriented<D; const TRANSPOSE: bool; const FLIP_X: bool; const FLIP_Y: bool>);
+
+/// Implement the composing methods for each orientation. Each entry is the
+/// orientation's parameters, followed by their negations.
+macro_rules! compose {
+    ($(($t:literal $x:literal $y:literal) ($nt:literal $nx:literal $ny:literal);)*) => {
+        $(
+            impl<D> Oriented<D, $t, $x, $y> {
+                /// Mirror the image around the X axis, like [`FlipX`](crate::FlipX).
+                pub fn flip_x(self) -> Oriented<D, $t, $nx, $y> {
+                    Oriented { target: self.target }
+                }
+
+                /// Mirror the image around the Y axis, like [`FlipY`](crate::FlipY).
+                pub fn flip_y(self) -> Oriented<D, $t, $x, $ny> {
+                    Oriented { target: self.target }
+                }
+
+                /// Transpose X and Y coordinates, like [`Transpose`].
+                pub fn transpose(self) -> Oriented<D, $nt, $y, $x> {
+                    Oriented { target: self.target }
+                }
+
+                /// Rotate the image 90 degrees to the right, like
+                /// [`Rotate90`](crate::Rotate90).
+                pub fn rotate90(self) -> Oriented<D, $nt, $y, $nx> {
+                    self.transpose().flip_y()
+                }
+
+                /// Rotate the image 180 degrees, like [`Rotate180`](crate::Rotate180).
+                pub fn rotate180(self) -> Oriented<D, $t, $nx, $ny> {
+                    self.flip_x().flip_y()
+                }
+
+                /// Rotate the image 90 degrees to the left, like
+                /// [`Rotate270`](crate::Rotate270).
+                pub fn rotate270(self) -> Oriented<D, $nt, $ny, $x> {
+                    self.transpose().flip_x()
+                }
+            }
+        )*
+    };
+}
+
+compose! {
+    (false false false) (true true true);
+    (false false true) (true true false);
+    (false true false) (true false true);
+    (false true true) (true false false);
+    (true false false) (false true true);
+    (true false true) (false true false);
+    (true true false) (false false true);
+    (true true true) (false false false);
+}
+
+impl<D, const TRANSPOSE: bool, const FLIP_X: bool, const FLIP_Y: bool>
+    Oriented<D, TRANSPOSE, FLIP_X, FLIP_Y>
+{
+    const ORIENTATION: Orientation = Orientation::new(TRANSPOSE, FLIP_X, FLIP_Y);
+
+    /// Return the orientation as a runtime value.
+    pub fn orientation(&self) -> Orientation {
+        Self::ORIENTATION
+    }
+}
+
+impl<D, const TRANSPOSE: bool, const FLIP_X: bool, const FLIP_Y: bool>
+    Oriented<D, TRANSPOSE, FLIP_X, FLIP_Y>
+where
+    D: DrawTarget,
+{
+    /// Draw a slice of pixels with a single call to the display, like
+    /// [`Rotate90::set_pixels`].
+    pub fn set_pixels(&mut self, pixels: &mut [Pixel<D::Color>]) -> Result<(), D::Error> {
+        let size = self.bounding_box().size;
+        set_pixels(
+            &mut self.target,
+            Self::ORIENTATION,
+            size,
+            Point::zero(),
+            pixels,
+        )
+    }
+
+    /// Fill an area through an `N` pixel buffer, like
+    /// [`Rotate90::fill_contiguous_buffered`].
+    pub fn fill_contiguous_buffered<const N: usize, I>(
+        &mut self,
+        area: &Rectangle,
+        colors: I,
+    ) -> Result<(), D::Error>
+    where
+        I: IntoIterator<Item = D::Color>,
+    {
+        let size = self.bounding_box().size;
+        fill_contiguous_buffered::<_, N>(
+            &mut self.target,
+            Self::ORIENTATION,
+            size,
+            Point::zero(),
+            area,
+            colors,
+        )
+    }
+
+    /// Draw a single pixel, like [`Rotate90::write_pixel`].
+    pub fn write_pixel(&mut self, point: Point, color: D::Color) -> Result<(), D::Error> {
+        let size = self.bounding_box().size;
+        let pixel = Pixel(point, color);
+        write_pixel(
+            &mut self.target,
+            Self::ORIENTATION,
+            size,
+            Point::zero(),
+            pixel,
+            false,
+        )
+    }
+
+    /// Draw a single pixel without checking it's on the display, like
+    /// [`Rotate90::write_pixel_unchecked`].
+    pub fn write_pixel_unchecked(&mut self, point: Point, color: D::Color) -> Result<(), D::Error> {
+        let size = self.bounding_box().size;
+        let pixel = Pixel(point, color);
+        write_pixel(
+            &mut self.target,
+            Self::ORIENTATION,
+            size,
+            Point::zero(),
+            pixel,
+            true,
+        )
+    }
+
+    /// Draw an image in the display's order, like [`Rotate90::draw_image`].
+    pub fn draw_image<I>(&mut self, image: &I, top_left: Point) -> Result<(), D::Error>
+    where
+        I: ImageDrawable<Color = D::Color>,
+    {
+        image::draw_image(self, Self::ORIENTATION, image, top_left)
+    }
+}
+
+impl<D, const TRANSPOSE: bool, const FLIP_X: bool, const FLIP_Y: bool>
+    Oriented<D, TRANSPOSE, FLIP_X, FLIP_Y>
+where
+    D: AddressWindow,
+{
+    /// Fill an area with the display's own windowed writes, like
+    /// [`Rotate90::fill_window`].
+    pub fn fill_window<const N: usize, I>(
+        &mut self,
+        area: &Rectangle,
+        colors: I,
+    ) -> Result<(), D::Error>
+    where
+        I: IntoIterator<Item = D::Color>,
+    {
+        let size = self.bounding_box().size;
+        address::fill_window::<_, N>(
+            &mut self.target,
+            Self::ORIENTATION,
+            size,
+            Point::zero(),
+            area,
+            colors,
+        )
+    }
+}
+
+impl<D, const TRANSPOSE: bool, const FLIP_X: bool, const FLIP_Y: bool>
+    Oriented<D, TRANSPOSE, FLIP_X, FLIP_Y>
+where
+    D: Blitter,
+{
+    /// Copy an area of the display with the display's own 2D engine, like
+    /// [`Rotate90::copy_area`].
+    pub fn copy_area(&mut self, area: &Rectangle, to: Point) -> Result<(), D::Error> {
+        let size = self.bounding_box().size;
+        blit::copy_area(
+            &mut self.target,
+            Self::ORIENTATION,
+            size,
+            Point::zero(),
+            area,
+            to,
+        )
+    }
+
+    /// Fill an area from a block of colors in memory, with the display's own
+    /// 2D engine where it can, like [`Rotate90::blit`].
+    pub fn blit(&mut self, area: &Rectangle, colors: &[D::Color]) -> Result<(), D::Error> {
+        let size = self.bounding_box().size;
+        if !blit::blit(
+            &mut self.target,
+            Self::ORIENTATION,
+            size,
+            Point::zero(),
+            area,
+            colors,
+        )? {
+            self.fill_contiguous(area, colors.iter().copied())?;
+        }
+        Ok(())
+    }
+}
+
+impl<D, const TRANSPOSE: bool, const FLIP_X: bool, const FLIP_Y: bool> Dimensions
+    for Oriented<D, TRANSPOSE, FLIP_X, FLIP_Y>
+where
+    D: Dimensions,
+{
+    fn bounding_box(&self) -> Rectangle {
+        Self::ORIENTATION.map_bounding_box(self.target.bounding_box())
+    }
+}
+
+impl<D, const TRANSPOSE: bool, const FLIP_X: bool, const FLIP_Y: bool> DrawTarget
+    for Oriented<D, TRANSPOSE, FLIP_X, FLIP_Y>
+where
+    D: DrawTarget,
+{
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let size = self.bounding_box().size;
+
+        self.target.draw_iter(
+            pixels
+                .into_iter()
+                .map(|Pixel(loc, col)| Pixel(Self::ORIENTATION.map_point(size, loc), col)),
+        )
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let size = self.bounding_box().size;
+        fill_contiguous(
+            &mut self.target,
+            Self::ORIENTATION,
+            size,
+            Point::zero(),
+            area,
+            colors,
+        )
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = Self::ORIENTATION.map_rect(self.bounding_box().size, area);
+        self.target.fill_solid(&area, color)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.target.clear(color)
+    }
+}
+
+/// A display in an orientation chosen at runtime, with a single coordinate
+/// mapping.
+///
+/// This is the runtime equivalent of [`Oriented`]: every point is mapped by the
+/// [`Orientation`], and then moved by a fixed offset in the wrapped display's
+/// coordinates. A stack of wrappers configured at runtime, such as a
+/// [`Rotate`] inside a [`FlipX`], can be folded into a `Reorient` with
+/// [`Normalize::normalize`], so that the cost per pixel doesn't depend on how
+/// many wrappers there were.
+pub struct Reorient<D> {
+    target: D,
+    orientation: Orientation,
+    offset: Point,
+}
+
+impl<D> Reorient<D> {
+    /// Apply `orientation` to a display implementing [`DrawTarget`].
+    pub fn new(target: D, orientation: Orientation) -> Self {
+        Reorient {
+            target,
+            orientation,
+            offset: Point::zero(),
+        }
+    }
+
+    /// Also move every point by `offset`, after it has been oriented.
+    pub fn with_offset(mut self, offset: Point) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Return the current orientation.
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    /// Change the orientation.
+    pub fn set_orientation(&mut self, orientation: Orientation) {
+        self.orientation = orientation;
+    }
+
+    /// Return the offset applied after the orientation.
+    pub fn offset(&self) -> Point {
+        self.offset
+    }
+}
+
+impl<D: DrawTarget> Reorient<D> {
+    /// Draw a slice of pixels with a single call to the display, like
+    /// [`Rotate90::set_pixels`].
+    pub fn set_pixels(&mut self, pixels: &mut [Pixel<D::Color>]) -> Result<(), D::Error> {
+        let size = self.bounding_box().size;
+        set_pixels(
+            &mut self.target,
+            self.orientation,
+            size,
+            self.offset,
+            pixels,
+        )
+    }
+
+    /// Fill an area through an `N` pixel buffer, like
+    /// [`Rotate90::fill_contiguous_buffered`].
+    pub fn fill_contiguous_buffered<const N: usize, I>(
+        &mut self,
+        area: &Rectangle,
+        colors: I,
+    ) -> Result<(), D::Error>
+    where
+        I: IntoIterator<Item = D::Color>,
+    {
+        let size = self.bounding_box().size;
+        fill_contiguous_buffered::<_, N>(
+            &mut self.target,
+            self.orientation,
+            size,
+            self.offset,
+            area,
+            colors,
+        )
+    }
+
+    /// Draw a single pixel, like [`Rotate90::write_pixel`].
+    pub fn write_pixel(&mut self, point: Point, color: D::Color) -> Result<(), D::Error> {
+        let size = self.bounding_box().size;
+        let pixel = Pixel(point, color);
+        write_pixel(
+            &mut self.target,
+            self.orientation,
+            size,
+            self.offset,
+            pixel,
+            false,
+        )
+    }
+
+    /// Draw a single pixel without checking it's on the display, like
+    /// [`Rotate90::write_pixel_unchecked`].
+    pub fn write_pixel_unchecked(&mut self, point: Point, color: D::Color) -> Result<(), D::Error> {
+        let size = self.bounding_box().size;
+        let pixel = Pixel(point, color);
+        write_pixel(
+            &mut self.target,
+            self.orientation,
+            size,
+            self.offset,
+            pixel,
+            true,
+        )
+    }
+
+    /// Draw an image in the display's order, like [`Rotate90::draw_image`].
+    pub fn draw_image<I>(&mut self, image: &I, top_left: Point) -> Result<(), D::Error>
+    where
+        I: ImageDrawable<Color = D::Color>,
+    {
+        let orientation = self.orientation;
+        image::draw_image(self, orientation, image, top_left)
+    }
+}
+
+impl<D: AddressWindow> Reorient<D> {
+    /// Fill an area with the display's own windowed writes, like
+    /// [`Rotate90::fill_window`].
+    pub fn fill_window<const N: usize, I>(
+        &mut self,
+        area: &Rectangle,
+        colors: I,
+    ) -> Result<(), D::Error>
+    where
+        I: IntoIterator<Item = D::Color>,
+    {
+        let size = self.bounding_box().size;
+        address::fill_window::<_, N>(
+            &mut self.target,
+            self.orientation,
+            size,
+            self.offset,
+            area,
+            colors,
+        )
+    }
+}
+
+impl<D: Blitter> Reorient<D> {
+    /// Copy an area of the display with the display's own 2D engine, like
+    /// [`Rotate90::copy_area`].
+    pub fn copy_area(&mut self, area: &Rectangle, to: Point) -> Result<(), D::Error> {
+        let size = self.bounding_box().size;
+        blit::copy_area(
+            &mut self.target,
+            self.orientation,
+            size,
+            self.offset,
+            area,
+            to,
+        )
+    }
+
+    /// Fill an area from a block of colors in memory, with the display's own
+    /// 2D engine where it can, like [`Rotate90::blit`].
+    pub fn blit(&mut self, area: &Rectangle, colors: &[D::Color]) -> Result<(), D::Error> {
+        let size = self.bounding_box().size;
+        if !blit::blit(
+            &mut self.target,
+            self.orientation,
+            size,
+            self.offset,
+            area,
+            colors,
+        )? {
+            self.fill_contiguous(area, colors.iter().copied())?;
+        }
+        Ok(())
+    }
+}
+
+impl_wrapper!(Reorient<D>);
+
+impl<D: Dimensions> Dimensions for Reorient<D> {
+    fn bounding_box(&self) -> Rectangle {
+        let size = self.target.bounding_box().size;
+        self.orientation
+            .map_bounding_box(Rectangle::new(Point::zero(), size))
+    }
+}
+
+impl<D: DrawTarget> DrawTarget for Reorient<D> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let size = self.bounding_box().size;
+        let Reorient {
+            orientation,
+            offset,
+            ..
+        } = *self;
+
+        self.target.draw_iter(
+            pixels
+                .into_iter()
+                .map(|Pixel(loc, col)| Pixel(orientation.map_point(size, loc) + offset, col)),
+        )
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let size = self.bounding_box().size;
+        fill_contiguous(
+            &mut self.target,
+            self.orientation,
+            size,
+            self.offset,
+            area,
+            colors,
+        )
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = self.orientation.map_rect(self.bounding_box().size, area);
+        self.target
+            .fill_solid(&util::translate(&area, self.offset), color)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        if self.offset == Point::zero() {
+            self.target.clear(color)
+        } else {
+            self.fill_solid(&self.bounding_box(), color)
+        }
+    }
+}
+
+/// Transforms which can be undone.
+///
+/// [`invert`](Invert::invert) rewraps the display in the transform which undoes
+/// this one, such as [`Rotate270`] for [`Rotate90`]. Wrapping a display in a
+/// transform and then its inverse, in either order, leaves it unchanged. The
+/// inverse of an [`Orientation`] is [`Orientation::inverse`].
+pub trait Invert {
+    /// The transform which undoes this one.
+    type Inverse;
+
+    /// Rewrap the display in the inverse transform.
+    fn invert(self) -> Self::Inverse;
+}
+
+macro_rules! invert {
+    ($($name:ident => $inverse:ident;)*) => {
+        $(
+            impl<D> Invert for $name<D> {
+                type Inverse = $inverse<D>;
+
+                fn invert(self) -> $inverse<D> {
+                    $inverse::new(self.into_inner())
+                }
+            }
+        )*
+    };
+}
+
+invert! {
+    Rotate0 => Rotate0;
+    Rotate90 => Rotate270;
+    Rotate180 => Rotate180;
+    Rotate270 => Rotate90;
+    Transpose => Transpose;
+    FlipX => FlipX;
+    FlipY => FlipY;
+}
+
+impl<D> Invert for Rotate<D> {
+    type Inverse = Rotate<D>;
+
+    fn invert(self) -> Rotate<D> {
+        let rotation = self.rotation().inverse();
+        Rotate::new(rotation, self.into_inner())
+    }
+}
+
+/// Implement `Invert` for each orientation of `Oriented`. Transposed
+/// orientations swap their mirroring, and the others are their own inverse.
+macro_rules! invert_oriented {
+    ($(($t:literal $x:literal $y:literal) => ($it:literal $ix:literal $iy:literal);)*) => {
+        $(
+            impl<D> Invert for Oriented<D, $t, $x, $y> {
+                type Inverse = Oriented<D, $it, $ix, $iy>;
+
+                fn invert(self) -> Self::Inverse {
+                    Oriented { target: self.target }
+                }
+            }
+        )*
+    };
+}
+
+invert_oriented! {
+    (false false false) => (false false false);
+    (false false true) => (false false true);
+    (false true false) => (false true false);
+    (false true true) => (false true true);
+    (true false false) => (true false false);
+    (true false true) => (true true false);
+    (true true false) => (true false true);
+    (true true true) => (true true true);
+}
+
+/// Fold a stack of wrappers into a single [`Reorient`].
+///
+/// This is implemented for the rotation, mirroring and transposition wrappers,
+/// [`Oriented`] and [`Offset`], when they wrap something which itself
+/// implements `Normalize`. The innermost wrapper must be a `Reorient`, which
+/// can start with [`Orientation::IDENTITY`]. For example
+/// `FlipX::new(Rotate::new(rotation, Reorient::new(display, Orientation::IDENTITY)))`
+/// normalizes to a `Reorient` of `display` which draws identically, but with a
+/// single coordinate mapping.
+pub trait Normalize {
+    /// The display inside the stack of wrappers.
+    type Target;
+
+    /// Fold the wrappers into a single `Reorient`.
+    fn normalize(self) -> Reorient<Self::Target>;
+}
+
+impl<D> Normalize for Reorient<D> {
+    type Target = D;
+
+    fn normalize(self) -> Reorient<D> {
+        self
+    }
+}
+
+macro_rules! normalize {
+    ($($name:ident => $op:ident;)*) => {
+        $(
+            impl<T: Normalize> Normalize for $name<T> {
+                type Target = T::Target;
+
+                fn normalize(self) -> Reorient<T::Target> {
+                    let mut inner = self.into_inner().normalize();
+                    inner.orientation = inner.orientation.$op();
+                    inner
+                }
+            }
+        )*
+    };
+}
+
+normalize! {
+    FlipX => flip_x;
+    FlipY => flip_y;
+    Transpose => transpose;
+    Rotate90 => rotate90;
+    Rotate180 => rotate180;
+    Rotate270 => rotate270;
+}
+
+impl<T: Normalize> Normalize for Rotate0<T> {
+    type Target = T::Target;
+
+    fn normalize(self) -> Reorient<T::Target> {
+        self.into_inner().normalize()
+    }
+}
+
+impl<T: Normalize> Normalize for Rotate<T> {
+    type Target = T::Target;
+
+    fn normalize(self) -> Reorient<T::Target> {
+        let rotation = self.rotation();
+        let mut inner = self.into_inner().normalize();
+        inner.orientation = inner.orientation.rotate(rotation);
+        inner
+    }
+}
+
+impl<T, const TRANSPOSE: bool, const FLIP_X: bool, const FLIP_Y: bool> Normalize
+    for Oriented<T, TRANSPOSE, FLIP_X, FLIP_Y>
+where
+    T: Normalize,
+{
+    type Target = T::Target;
+
+    fn normalize(self) -> Reorient<T::Target> {
+        let orientation = self.orientation();
+        let mut inner = self.into_inner().normalize();
+        // Apply this orientation's operations to the inner one, in the same
+        // order as `Orientation::map_point`.
+        let mut o = inner.orientation;
+        if orientation.transpose {
+            o = o.transpose();
+        }
+        if orientation.flip_y {
+            o = o.flip_y();
+        }
+        if orientation.flip_x {
+            o = o.flip_x();
+        }
+        inner.orientation = o;
+        inner
+    }
+}
+
+impl<T: Normalize> Normalize for Offset<T> {
+    type Target = T::Target;
+
+    fn normalize(self) -> Reorient<T::Target> {
+        let offset = self.offset();
+        let mut inner = self.into_inner().normalize();
+        inner.offset += inner.orientation.map_offset(offset);
+        inner
+    }
+}
@@ -0,0 +1,241 @@
+use super::*;
+use crate::{testing::TransformTester, WithSize};
+use embedded_graphics::mock_display::MockDisplay;
+use embedded_graphics_core::pixelcolor::Rgb888;
+
+/// Display size for testing, which isn't square.
+fn sized(disp: MockDisplay<Rgb888>) -> WithSize<MockDisplay<Rgb888>> {
+    WithSize::new(disp, Size::new(40, 24))
+}
+
+/// Check that an `Oriented` is equivalent to nesting the wrapper types.
+macro_rules! assert_same {
+    (|$d:ident| $oriented:expr, $($xform:ident)*) => {
+        let mut oriented = TransformTester::new(
+            |disp| {
+                let $d = Oriented::new(sized(disp));
+                $oriented
+            },
+            |target| target.into_inner().into_inner(),
+        );
+        oriented.assert_valid();
+        oriented.assert_equivalent(&mut TransformTester::new(
+            |disp| assert_same!(@wrap sized(disp), $($xform)*),
+            |target| assert_same!(@unwrap target, $($xform)*).into_inner(),
+        ));
+    };
+    (@wrap $inner:expr, ) => { $inner };
+    (@wrap $inner:expr, $xform:ident $($rest:ident)*) => {
+        $xform::new(assert_same!(@wrap $inner, $($rest)*))
+    };
+    (@unwrap $outer:expr, ) => { $outer };
+    (@unwrap $outer:expr, $xform:ident $($rest:ident)*) => {
+        assert_same!(@unwrap $outer.into_inner(), $($rest)*)
+    };
+}
+
+#[test]
+fn generators() {
+    assert_same!(|d| d.flip_x(), FlipX);
+    assert_same!(|d| d.flip_y(), FlipY);
+    assert_same!(|d| d.transpose(), Transpose);
+    assert_same!(|d| d.rotate90(), Rotate90);
+    assert_same!(|d| d.rotate180(), Rotate180);
+    assert_same!(|d| d.rotate270(), Rotate270);
+}
+
+#[test]
+fn compositions() {
+    assert_same!(|d| d.rotate90().flip_x(), FlipX Rotate90);
+    assert_same!(|d| d.flip_y().rotate270(), Rotate270 FlipY);
+    assert_same!(|d| d.transpose().rotate90(), Rotate90 Transpose);
+    assert_same!(|d| d.rotate270().rotate270().flip_y(), FlipY Rotate270 Rotate270);
+}
+
+#[test]
+fn collapse() {
+    // These are checked at compile time
+    let disp: Oriented<_, false, false, false> = Oriented::new(MockDisplay::<Rgb888>::new())
+        .rotate90()
+        .rotate270();
+    let disp: Oriented<_, false, true, true> = disp.flip_x().flip_y();
+    let _: Oriented<_, false, false, false> = disp.rotate180();
+}
+
+#[test]
+fn orientations() {
+    let all = Orientation::ALL;
+    for (i, a) in all.iter().enumerate() {
+        assert!(!all[i + 1..].contains(a));
+    }
+
+    let r90 = Orientation::IDENTITY.rotate90();
+    assert_eq!(r90, Oriented::new(()).rotate90().orientation());
+    assert_eq!(r90.rotate90().rotate90().rotate90(), Orientation::IDENTITY);
+    assert_eq!(Orientation::from(Rotation::Rotate270), r90.rotate180());
+    assert_eq!(all[5], r90.flip_x());
+    assert_eq!(r90.flip_x().flip_x(), r90);
+}
+
+#[test]
+fn next_orientation() {
+    let mut o = Orientation::IDENTITY;
+    for expected in Orientation::ALL.iter().cycle().skip(1).take(8) {
+        o = o.next();
+        assert_eq!(o, *expected);
+    }
+    assert_eq!(o, Orientation::IDENTITY);
+}
+
+#[test]
+fn orientation_names() {
+    use std::string::ToString;
+
+    assert_eq!(Orientation::ALL[1].to_string(), "Rotate90");
+    assert_eq!(Orientation::ALL[6].to_string(), "Rotate180 mirrored");
+}
+
+/// Check that a stack of wrappers draws the same once normalized.
+macro_rules! assert_normalizes {
+    (|$d:ident| $stack:expr, $unwrap:expr) => {
+        let mut stack = TransformTester::new(
+            |disp| {
+                let $d = Reorient::new(sized(disp), Orientation::IDENTITY);
+                $stack
+            },
+            $unwrap,
+        );
+        let mut normalized = TransformTester::new(
+            |disp| {
+                let $d = Reorient::new(sized(disp), Orientation::IDENTITY);
+                $stack.normalize()
+            },
+            |target| target.into_inner().into_inner(),
+        );
+        normalized.assert_equivalent(&mut stack);
+    };
+}
+
+#[test]
+fn normalize() {
+    for rotation in [
+        Rotation::Rotate0,
+        Rotation::Rotate90,
+        Rotation::Rotate180,
+        Rotation::Rotate270,
+    ] {
+        assert_normalizes!(|d| FlipX::new(Rotate::new(rotation, d)), |t| t
+            .into_inner()
+            .into_inner()
+            .into_inner()
+            .into_inner());
+    }
+    assert_normalizes!(
+        |d| Rotate270::new(Oriented::new(FlipY::new(d)).rotate90().flip_x()),
+        |t| t
+            .into_inner()
+            .into_inner()
+            .into_inner()
+            .into_inner()
+            .into_inner()
+    );
+
+    // Offsets are moved inside the orientation
+    assert_normalizes!(
+        |d| Rotate90::new(Offset::new(
+            FlipX::new(Offset::new(d, Point::new(2, 1))),
+            Point::new(3, -1)
+        )),
+        |t| t
+            .into_inner()
+            .into_inner()
+            .into_inner()
+            .into_inner()
+            .into_inner()
+            .into_inner()
+    );
+
+    let disp = Rotate90::new(Offset::new(
+        Reorient::new(MockDisplay::<Rgb888>::new(), Orientation::IDENTITY),
+        Point::new(1, 2),
+    ))
+    .normalize();
+    assert_eq!(disp.orientation(), Orientation::IDENTITY.rotate90());
+    assert_eq!(disp.offset(), Point::new(1, 2));
+}
+
+#[test]
+fn reorient() {
+    let mut tester = TransformTester::new(
+        |disp| Reorient::new(sized(disp), Orientation::ALL[5]),
+        |target| target.into_inner().into_inner(),
+    );
+    tester.assert_valid();
+    tester.assert_equivalent(&mut TransformTester::new(
+        |disp| FlipX::new(Rotate90::new(sized(disp))),
+        |target| target.into_inner().into_inner().into_inner(),
+    ));
+}
+
+/// Check that wrapping in a transform and then its inverse is the identity.
+macro_rules! assert_inverse {
+    (|$d:ident| $xform:expr, |$t:ident| $unwrap:expr) => {
+        TransformTester::new(
+            |disp| {
+                let $d = {
+                    let $d = sized(disp);
+                    $xform.invert()
+                };
+                $xform
+            },
+            |$t| $unwrap.into_inner(),
+        )
+        .assert_identity();
+    };
+}
+
+#[test]
+fn invert() {
+    assert_inverse!(|d| Rotate0::new(d), |t| t.into_inner().into_inner());
+    assert_inverse!(|d| Rotate90::new(d), |t| t.into_inner().into_inner());
+    assert_inverse!(|d| Rotate180::new(d), |t| t.into_inner().into_inner());
+    assert_inverse!(|d| Rotate270::new(d), |t| t.into_inner().into_inner());
+    assert_inverse!(|d| Transpose::new(d), |t| t.into_inner().into_inner());
+    assert_inverse!(|d| FlipX::new(d), |t| t.into_inner().into_inner());
+    assert_inverse!(|d| FlipY::new(d), |t| t.into_inner().into_inner());
+    for rotation in [
+        Rotation::Rotate0,
+        Rotation::Rotate90,
+        Rotation::Rotate180,
+        Rotation::Rotate270,
+    ] {
+        assert_inverse!(|d| Rotate::new(rotation, d), |t| t
+            .into_inner()
+            .into_inner());
+    }
+    assert_inverse!(|d| Oriented::new(d).rotate90().flip_x(), |t| t
+        .into_inner()
+        .into_inner());
+    assert_inverse!(|d| Oriented::new(d).rotate270(), |t| t
+        .into_inner()
+        .into_inner());
+    assert_inverse!(|d| Oriented::new(d).transpose().flip_y(), |t| t
+        .into_inner()
+        .into_inner());
+
+    for orientation in Orientation::ALL {
+        TransformTester::new(
+            |disp| {
+                Reorient::new(
+                    Reorient::new(sized(disp), orientation),
+                    orientation.inverse(),
+                )
+            },
+            |target| target.into_inner().into_inner().into_inner(),
+        )
+        .assert_identity();
+    }
+
+    let _: Rotate270<MockDisplay<Rgb888>> = Rotate90::new(MockDisplay::new()).invert();
+    assert_eq!(Rotation::Rotate90.inverse(), Rotation::Rotate270);
+}
@@ -0,0 +1,249 @@
+//! Show a sprite, such as a cursor, over everything else.
+
+use crate::{util, ReadPixel};
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+
+#[cfg(test)]
+mod tests;
+
+/// A small image with transparent pixels, for [`SpriteOverlay`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Sprite<'a, C> {
+    size: Size,
+    pixels: &'a [Option<C>],
+    hotspot: Point,
+}
+
+impl<'a, C: Copy> Sprite<'a, C> {
+    /// A sprite of `size` with `pixels` in row-major order, where `None` is
+    /// transparent. Any pixels missing from the end are transparent.
+    pub const fn new(size: Size, pixels: &'a [Option<C>]) -> Self {
+        Sprite {
+            size,
+            pixels,
+            hotspot: Point::zero(),
+        }
+    }
+
+    /// Set the point of the sprite which is placed at its position, such as
+    /// the tip of an arrow or the center of a crosshair. This defaults to the
+    /// top left.
+    pub const fn with_hotspot(mut self, hotspot: Point) -> Self {
+        self.hotspot = hotspot;
+        self
+    }
+
+    /// Return the size of the sprite.
+    pub const fn size(&self) -> Size {
+        self.size
+    }
+
+    /// Return the color of the pixel at `index`, if it's opaque.
+    fn pixel(&self, index: usize) -> Option<C> {
+        self.pixels.get(index).copied().flatten()
+    }
+}
+
+/// Show a sprite over everything else drawn to a display.
+///
+/// This is like a hardware cursor: the sprite, such as a mouse pointer or a
+/// touch indicator, can be moved with [`move_to`](Self::move_to) without
+/// redrawing anything else. Its opaque pixels always show over the display's
+/// contents, and the contents underneath are kept in a saved patch of up to
+/// `N` pixels, which is restored when it moves or is hidden.
+///
+/// The patch is read back from the display with [`ReadPixel`] when the sprite
+/// is shown at a new position, and then kept up to date from drawing which
+/// passes through this adapter, which goes to the patch rather than the
+/// display where it's covered by the sprite. Fills which overlap the sprite
+/// are split, so that only the part around it is drawn pixel by pixel.
+pub struct SpriteOverlay<'a, D, C, const N: usize> {
+    target: D,
+    sprite: Sprite<'a, C>,
+    area: Option<Rectangle>,
+    patch: [Option<C>; N],
+}
+
+impl<'a, D, C: Copy, const N: usize> SpriteOverlay<'a, D, C, N> {
+    /// Show `sprite` over `target`, initially hidden.
+    ///
+    /// # Panics
+    ///
+    /// If the sprite has more than `N` pixels.
+    pub fn new(target: D, sprite: Sprite<'a, C>) -> Self {
+        let Size { width, height } = sprite.size;
+        assert!(
+            (width * height) as usize <= N,
+            "sprite doesn't fit in the patch"
+        );
+
+        SpriteOverlay {
+            target,
+            sprite,
+            area: None,
+            patch: [None; N],
+        }
+    }
+
+    /// Return the position of the sprite's hotspot, or `None` if it's hidden.
+    pub fn position(&self) -> Option<Point> {
+        self.area.map(|area| area.top_left + self.sprite.hotspot)
+    }
+}
+
+impl<'a, D: ReadPixel, const N: usize> SpriteOverlay<'a, D, D::Color, N> {
+    /// Show the sprite with its hotspot at `position`, restoring what was under
+    /// it at its previous position.
+    ///
+    /// The sprite is clipped to the display.
+    pub fn move_to(&mut self, position: Point) -> Result<(), D::Error> {
+        self.hide()?;
+
+        let area = Rectangle::new(position - self.sprite.hotspot, self.sprite.size);
+        for (slot, p) in self.patch.iter_mut().zip(util::points(&area)) {
+            *slot = self.target.read_pixel(p);
+        }
+        self.area = Some(area);
+
+        let sprite = self.sprite;
+        let bounds = self.target.bounding_box();
+        self.target.draw_iter(
+            util::points(&area)
+                .enumerate()
+                .filter(|&(_, p)| bounds.contains(p))
+                .filter_map(|(i, p)| Some(Pixel(p, sprite.pixel(i)?))),
+        )
+    }
+
+    /// Hide the sprite, restoring what was under it.
+    pub fn hide(&mut self) -> Result<(), D::Error> {
+        let Some(area) = self.area.take() else {
+            return Ok(());
+        };
+
+        let (sprite, patch) = (self.sprite, &self.patch);
+        self.target.draw_iter(
+            util::points(&area)
+                .enumerate()
+                .filter(|&(i, _)| sprite.pixel(i).is_some())
+                .filter_map(|(i, p)| Some(Pixel(p, patch[i]?))),
+        )
+    }
+}
+
+impl_wrapper!(SpriteOverlay<'a, D, C; const N: usize>);
+
+impl<D: Dimensions, C, const N: usize> Dimensions for SpriteOverlay<'_, D, C, N> {
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+impl<D: DrawTarget, const N: usize> DrawTarget for SpriteOverlay<'_, D, D::Color, N> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let SpriteOverlay {
+            target,
+            sprite,
+            area,
+            patch,
+        } = self;
+        let Some(area) = *area else {
+            return target.draw_iter(pixels);
+        };
+
+        // Pixels under the sprite's opaque pixels only go to the patch.
+        target.draw_iter(pixels.into_iter().filter(|&Pixel(p, color)| {
+            let Some(i) = index(&area, p) else {
+                return true;
+            };
+            patch[i] = Some(color);
+            sprite.pixel(i).is_none()
+        }))
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let SpriteOverlay {
+            target,
+            sprite,
+            area: shown,
+            patch,
+        } = self;
+        let Some(shown) = shown.filter(|shown| !util::is_empty(&shown.intersection(area))) else {
+            return target.fill_contiguous(area, colors);
+        };
+
+        // Save the colors under the sprite, and draw the sprite instead.
+        target.fill_contiguous(
+            area,
+            util::points(area).zip(colors).map(|(p, color)| {
+                let Some(i) = index(&shown, p) else {
+                    return color;
+                };
+                patch[i] = Some(color);
+                sprite.pixel(i).unwrap_or(color)
+            }),
+        )
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let covered = self
+            .area
+            .map_or(Rectangle::zero(), |shown| shown.intersection(area));
+        if util::is_empty(&covered) {
+            return self.target.fill_solid(area, color);
+        }
+
+        // Fill around the sprite as it is, and only the part it covers pixel
+        // by pixel.
+        let (rows, columns) = (area.rows(), area.columns());
+        let (inner_rows, inner_columns) = (covered.rows(), covered.columns());
+        let height = covered.size.height;
+        for part in [
+            Rectangle::new(
+                area.top_left,
+                Size::new(area.size.width, (inner_rows.start - rows.start) as u32),
+            ),
+            Rectangle::new(
+                Point::new(columns.start, inner_rows.start),
+                Size::new((inner_columns.start - columns.start) as u32, height),
+            ),
+            Rectangle::new(
+                Point::new(inner_columns.end, inner_rows.start),
+                Size::new((columns.end - inner_columns.end) as u32, height),
+            ),
+            Rectangle::new(
+                Point::new(columns.start, inner_rows.end),
+                Size::new(area.size.width, (rows.end - inner_rows.end) as u32),
+            ),
+        ] {
+            if !util::is_empty(&part) {
+                self.target.fill_solid(&part, color)?;
+            }
+        }
+
+        let len = (covered.size.width * height) as usize;
+        self.fill_contiguous(&covered, core::iter::repeat_n(color, len))
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        let area = self.bounding_box();
+        self.fill_solid(&area, color)
+    }
+}
+
+/// Index of `p` in the sprite and patch, if it's in the sprite's `area`.
+fn index(area: &Rectangle, p: Point) -> Option<usize> {
+    area.contains(p).then(|| {
+        let p = p - area.top_left;
+        p.y as usize * area.size.width as usize + p.x as usize
+    })
+}
@@ -0,0 +1,74 @@
+use super::*;
+use crate::tests::rect;
+use embedded_graphics::{mock_display::MockDisplay, pixelcolor::Rgb565};
+
+const W: Option<Rgb565> = Some(Rgb565::WHITE);
+
+// A cross, with its hotspot in the middle
+const CROSS: [Option<Rgb565>; 9] = [None, W, None, W, W, W, None, W, None];
+
+fn cross() -> Sprite<'static, Rgb565> {
+    Sprite::new(Size::new(3, 3), &CROSS).with_hotspot(Point::new(1, 1))
+}
+
+#[test]
+fn overlay() {
+    let mut disp = SpriteOverlay::<_, _, 9>::new(MockDisplay::new(), cross());
+    disp.set_allow_overdraw(true);
+    disp.fill_solid(&rect(0, 0, 5, 3), Rgb565::BLACK).unwrap();
+    assert_eq!(disp.position(), None);
+
+    disp.move_to(Point::new(2, 1)).unwrap();
+    assert_eq!(disp.position(), Some(Point::new(2, 1)));
+    disp.assert_pattern(&["KKWKK", "KWWWK", "KKWKK"]);
+
+    // Drawing under the sprite is kept until it moves away
+    disp.draw_iter([
+        Pixel(Point::new(1, 0), Rgb565::RED),
+        Pixel(Point::new(2, 2), Rgb565::GREEN),
+    ])
+    .unwrap();
+    disp.assert_pattern(&["KRWKK", "KWWWK", "KKWKK"]);
+
+    disp.move_to(Point::new(3, 1)).unwrap();
+    disp.assert_pattern(&["KRKWK", "KKWWW", "KKGWK"]);
+
+    disp.hide().unwrap();
+    assert_eq!(disp.position(), None);
+    disp.assert_pattern(&["KRKKK", "KKKKK", "KKGKK"]);
+
+    // Clipped to the display
+    disp.move_to(Point::zero()).unwrap();
+    disp.assert_pattern(&["WWKKK", "WKKKK", "KKGKK"]);
+    disp.hide().unwrap();
+    disp.assert_pattern(&["KRKKK", "KKKKK", "KKGKK"]);
+}
+
+#[test]
+fn overlay_fills() {
+    let mut disp = SpriteOverlay::<_, _, 9>::new(MockDisplay::new(), cross());
+    disp.set_allow_overdraw(true);
+    disp.fill_solid(&rect(0, 0, 5, 3), Rgb565::BLACK).unwrap();
+    disp.move_to(Point::new(2, 1)).unwrap();
+
+    disp.fill_solid(&rect(1, 0, 4, 3), Rgb565::RED).unwrap();
+    disp.assert_pattern(&["KRWRR", "KWWWR", "KRWRR"]);
+
+    let colors = [
+        Rgb565::RED,
+        Rgb565::GREEN,
+        Rgb565::BLUE,
+        Rgb565::GREEN,
+        Rgb565::RED,
+    ];
+    disp.fill_contiguous(&rect(0, 1, 5, 1), colors).unwrap();
+    disp.assert_pattern(&["KRWRR", "RWWWR", "KRWRR"]);
+
+    disp.hide().unwrap();
+    disp.assert_pattern(&["KRRRR", "RGBGR", "KRRRR"]);
+
+    // Fills away from the sprite go straight through
+    disp.move_to(Point::new(1, 1)).unwrap();
+    disp.fill_solid(&rect(4, 0, 1, 3), Rgb565::BLUE).unwrap();
+    disp.assert_pattern(&["KWRRB", "WWWGB", "KWRRB"]);
+}
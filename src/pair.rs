@@ -0,0 +1,555 @@
+//! Adapters which draw to a pair of displays.
+
+use crate::util;
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+
+#[cfg(test)]
+mod tests;
+
+/// Error from an adapter over two displays, identifying which one failed.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum PairError<A, B> {
+    /// The first display returned an error.
+    First(A),
+    /// The second display returned an error.
+    Second(B),
+}
+
+/// Number of pixels buffered so they can be forwarded to both displays.
+const TEE_CHUNK: usize = 32;
+
+/// Draw to two displays at once.
+///
+/// Every draw call is forwarded to both displays, each of which can be behind
+/// its own transform. More than two displays can be handled by nesting, for
+/// example `Tee<A, Tee<B, C>>`.
+///
+/// The bounding box is that of the first display; anything outside the
+/// second display's bounds is clipped (or rejected) by it in the usual way.
+///
+/// Since pixel and color iterators can only be consumed once, they are
+/// forwarded in chunks of a few pixels. `fill_contiguous` is therefore
+/// forwarded as individual pixels, while `fill_solid` and `clear` are
+/// forwarded directly.
+///
+/// # Errors
+///
+/// Each chunk or fill is always drawn to both displays, even if the first one
+/// fails. If either fails, drawing stops and the error is returned; if both
+/// fail, the first display's error is reported.
+pub struct Tee<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A, B> Tee<A, B> {
+    /// Draw to both `first` and `second`.
+    pub fn new(first: A, second: B) -> Self {
+        Tee { first, second }
+    }
+
+    /// Get a reference to the first display.
+    pub fn first(&self) -> &A {
+        &self.first
+    }
+
+    /// Get a mutable reference to the first display.
+    pub fn first_mut(&mut self) -> &mut A {
+        &mut self.first
+    }
+
+    /// Get a reference to the second display.
+    pub fn second(&self) -> &B {
+        &self.second
+    }
+
+    /// Get a mutable reference to the second display.
+    pub fn second_mut(&mut self) -> &mut B {
+        &mut self.second
+    }
+
+    /// Recover both display instances.
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+impl<A, B> Tee<A, B>
+where
+    A: DrawTarget,
+    B: DrawTarget<Color = A::Color>,
+{
+    fn both(
+        &mut self,
+        mut f: impl FnMut(&mut A) -> Result<(), A::Error>,
+        mut g: impl FnMut(&mut B) -> Result<(), B::Error>,
+    ) -> Result<(), PairError<A::Error, B::Error>> {
+        let first = f(&mut self.first);
+        let second = g(&mut self.second);
+
+        first.map_err(PairError::First)?;
+        second.map_err(PairError::Second)
+    }
+}
+
+impl_by_mut_ref!([A, B] Tee<A, B>);
+
+impl<A: Dimensions, B> Dimensions for Tee<A, B> {
+    fn bounding_box(&self) -> Rectangle {
+        self.first.bounding_box()
+    }
+}
+
+impl<A, B> DrawTarget for Tee<A, B>
+where
+    A: DrawTarget,
+    B: DrawTarget<Color = A::Color>,
+{
+    type Color = A::Color;
+    type Error = PairError<A::Error, B::Error>;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let mut pixels = pixels.into_iter();
+        let mut buf = match pixels.next() {
+            Some(pixel) => [pixel; TEE_CHUNK],
+            None => return Ok(()),
+        };
+        let mut len = 1;
+
+        loop {
+            for (slot, pixel) in buf[len..].iter_mut().zip(&mut pixels) {
+                *slot = pixel;
+                len += 1;
+            }
+            if len == 0 {
+                return Ok(());
+            }
+
+            let chunk = &buf[..len];
+            self.both(
+                |first| first.draw_iter(chunk.iter().copied()),
+                |second| second.draw_iter(chunk.iter().copied()),
+            )?;
+
+            if len < TEE_CHUNK {
+                return Ok(());
+            }
+            len = 0;
+        }
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        self.draw_iter(
+            util::points(area)
+                .zip(colors)
+                .map(|(loc, col)| Pixel(loc, col)),
+        )
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.both(
+            |first| first.fill_solid(area, color),
+            |second| second.fill_solid(area, color),
+        )
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.both(|first| first.clear(color), |second| second.clear(color))
+    }
+}
+
+/// Which display a [`Switch`] is drawing to.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Selection {
+    /// Draw to the first display.
+    First,
+    /// Draw to the second display.
+    Second,
+}
+
+/// Route drawing to one of two displays, selected at runtime.
+///
+/// This is useful when a device can drive either of two displays (for example
+/// an internal panel or an external one), possibly with different driver types
+/// and orientations. Only the selected display is drawn to, and its bounding
+/// box is reported. The selection can be changed at any time with
+/// [`select`](Self::select).
+pub struct Switch<A, B> {
+    first: A,
+    second: B,
+    selected: Selection,
+}
+
+macro_rules! switch_impl {
+    ($switch:expr, $func:ident ( $($args:expr),* $(,)?)) => {
+        match $switch.selected {
+            Selection::First => $switch.first.$func($($args),*).map_err(PairError::First),
+            Selection::Second => $switch.second.$func($($args),*).map_err(PairError::Second),
+        }
+    };
+}
+
+impl<A, B> Switch<A, B> {
+    /// Switch between `first` and `second`, initially drawing to `first`.
+    pub fn new(first: A, second: B) -> Self {
+        Switch {
+            first,
+            second,
+            selected: Selection::First,
+        }
+    }
+
+    /// Select which display to draw to.
+    pub fn select(&mut self, selected: Selection) {
+        self.selected = selected;
+    }
+
+    /// Return which display is currently selected.
+    pub fn selected(&self) -> Selection {
+        self.selected
+    }
+
+    /// Get a reference to the first display.
+    pub fn first(&self) -> &A {
+        &self.first
+    }
+
+    /// Get a mutable reference to the first display.
+    pub fn first_mut(&mut self) -> &mut A {
+        &mut self.first
+    }
+
+    /// Get a reference to the second display.
+    pub fn second(&self) -> &B {
+        &self.second
+    }
+
+    /// Get a mutable reference to the second display.
+    pub fn second_mut(&mut self) -> &mut B {
+        &mut self.second
+    }
+
+    /// Recover both display instances.
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+impl_by_mut_ref!([A, B] Switch<A, B>);
+
+impl<A: Dimensions, B: Dimensions> Dimensions for Switch<A, B> {
+    fn bounding_box(&self) -> Rectangle {
+        match self.selected {
+            Selection::First => self.first.bounding_box(),
+            Selection::Second => self.second.bounding_box(),
+        }
+    }
+}
+
+impl<A, B> DrawTarget for Switch<A, B>
+where
+    A: DrawTarget,
+    B: DrawTarget<Color = A::Color>,
+{
+    type Color = A::Color;
+    type Error = PairError<A::Error, B::Error>;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        switch_impl!(self, draw_iter(pixels))
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        switch_impl!(self, fill_contiguous(area, colors))
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        switch_impl!(self, fill_solid(area, color))
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        switch_impl!(self, clear(color))
+    }
+}
+
+/// Draw to a primary display, switching to a backup if the primary keeps
+/// failing.
+///
+/// This is for systems which must keep showing something when a display fails,
+/// for example by moving to a degraded mode on a secondary panel. Drawing goes
+/// to the primary display until `threshold` operations in a row have failed,
+/// and then to the backup; any success on the primary resets the count. The
+/// bounding box is that of whichever display is in use, so layout should be
+/// recomputed after a switch.
+///
+/// The operation which causes the switch returns the primary's error as usual,
+/// and [`take_failover`](Self::take_failover) then reports the switch once, so
+/// that the application can redraw everything on the backup. The backup's
+/// errors are returned as they are, since there's nothing further to switch
+/// to. [`restore`](Self::restore) goes back to the primary, for example once
+/// it's been reinitialized.
+pub struct Failover<A, B> {
+    first: A,
+    second: B,
+    selected: Selection,
+    threshold: usize,
+    failures: usize,
+    switched: bool,
+}
+
+impl<A, B> Failover<A, B> {
+    /// Draw to `primary`, switching to `backup` after `threshold` consecutive
+    /// failures.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `threshold` is zero.
+    pub fn new(primary: A, backup: B, threshold: usize) -> Self {
+        assert!(threshold > 0, "threshold must be at least one failure");
+        Failover {
+            first: primary,
+            second: backup,
+            selected: Selection::First,
+            threshold,
+            failures: 0,
+            switched: false,
+        }
+    }
+
+    /// Return which display is in use.
+    pub fn selected(&self) -> Selection {
+        self.selected
+    }
+
+    /// Return whether drawing has switched to the backup since this was last
+    /// called.
+    pub fn take_failover(&mut self) -> bool {
+        core::mem::take(&mut self.switched)
+    }
+
+    /// Go back to drawing to the primary display, with no failures counted.
+    pub fn restore(&mut self) {
+        self.selected = Selection::First;
+        self.failures = 0;
+        self.switched = false;
+    }
+
+    /// Get a reference to the primary display.
+    pub fn first(&self) -> &A {
+        &self.first
+    }
+
+    /// Get a mutable reference to the primary display.
+    pub fn first_mut(&mut self) -> &mut A {
+        &mut self.first
+    }
+
+    /// Get a reference to the backup display.
+    pub fn second(&self) -> &B {
+        &self.second
+    }
+
+    /// Get a mutable reference to the backup display.
+    pub fn second_mut(&mut self) -> &mut B {
+        &mut self.second
+    }
+
+    /// Recover both display instances.
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+impl<A, B> Failover<A, B> {
+    /// Count the result of an operation on the primary display, switching to
+    /// the backup if it's failed too many times in a row.
+    fn record<E, F>(&mut self, res: Result<(), E>) -> Result<(), PairError<E, F>> {
+        match res {
+            Ok(()) => {
+                self.failures = 0;
+                Ok(())
+            }
+            Err(err) => {
+                self.failures += 1;
+                if self.failures >= self.threshold {
+                    self.selected = Selection::Second;
+                    self.switched = true;
+                }
+                Err(PairError::First(err))
+            }
+        }
+    }
+}
+
+macro_rules! failover_impl {
+    ($failover:expr, $func:ident ( $($args:expr),* $(,)?)) => {
+        match $failover.selected {
+            Selection::First => {
+                let res = $failover.first.$func($($args),*);
+                $failover.record(res)
+            }
+            Selection::Second => $failover.second.$func($($args),*).map_err(PairError::Second),
+        }
+    };
+}
+
+impl_by_mut_ref!([A, B] Failover<A, B>);
+
+impl<A: Dimensions, B: Dimensions> Dimensions for Failover<A, B> {
+    fn bounding_box(&self) -> Rectangle {
+        match self.selected {
+            Selection::First => self.first.bounding_box(),
+            Selection::Second => self.second.bounding_box(),
+        }
+    }
+}
+
+impl<A, B> DrawTarget for Failover<A, B>
+where
+    A: DrawTarget,
+    B: DrawTarget<Color = A::Color>,
+{
+    type Color = A::Color;
+    type Error = PairError<A::Error, B::Error>;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        failover_impl!(self, draw_iter(pixels))
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        failover_impl!(self, fill_contiguous(area, colors))
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        failover_impl!(self, fill_solid(area, color))
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        failover_impl!(self, clear(color))
+    }
+}
+
+/// One of two displays, chosen once at runtime.
+///
+/// This is for firmware which supports two kinds of display, for example
+/// detected at boot, each possibly wrapped in its own transform. Unlike
+/// [`Switch`], only the display in use is kept, and it can't be changed
+/// afterwards. Drawing is dispatched to it in the same way as
+/// [`Rotate`](crate::Rotate) dispatches to the rotation in use.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Either<A, B> {
+    /// Draw to the first kind of display.
+    First(A),
+    /// Draw to the second kind of display.
+    Second(B),
+}
+
+macro_rules! either_impl {
+    ($either:expr, $func:ident ( $($args:expr),* $(,)?)) => {
+        match $either {
+            Either::First(first) => first.$func($($args),*).map_err(PairError::First),
+            Either::Second(second) => second.$func($($args),*).map_err(PairError::Second),
+        }
+    };
+}
+
+impl<A, B> Either<A, B> {
+    /// Return which display is in use.
+    pub fn selected(&self) -> Selection {
+        match self {
+            Either::First(_) => Selection::First,
+            Either::Second(_) => Selection::Second,
+        }
+    }
+
+    /// Get a reference to the first display, if it is the one in use.
+    pub fn first(&self) -> Option<&A> {
+        match self {
+            Either::First(first) => Some(first),
+            Either::Second(_) => None,
+        }
+    }
+
+    /// Get a mutable reference to the first display, if it is the one in use.
+    pub fn first_mut(&mut self) -> Option<&mut A> {
+        match self {
+            Either::First(first) => Some(first),
+            Either::Second(_) => None,
+        }
+    }
+
+    /// Get a reference to the second display, if it is the one in use.
+    pub fn second(&self) -> Option<&B> {
+        match self {
+            Either::First(_) => None,
+            Either::Second(second) => Some(second),
+        }
+    }
+
+    /// Get a mutable reference to the second display, if it is the one in use.
+    pub fn second_mut(&mut self) -> Option<&mut B> {
+        match self {
+            Either::First(_) => None,
+            Either::Second(second) => Some(second),
+        }
+    }
+}
+
+impl_by_mut_ref!([A, B] Either<A, B>);
+
+impl<A: Dimensions, B: Dimensions> Dimensions for Either<A, B> {
+    fn bounding_box(&self) -> Rectangle {
+        match self {
+            Either::First(first) => first.bounding_box(),
+            Either::Second(second) => second.bounding_box(),
+        }
+    }
+}
+
+impl<A, B> DrawTarget for Either<A, B>
+where
+    A: DrawTarget,
+    B: DrawTarget<Color = A::Color>,
+{
+    type Color = A::Color;
+    type Error = PairError<A::Error, B::Error>;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        either_impl!(self, draw_iter(pixels))
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        either_impl!(self, fill_contiguous(area, colors))
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        either_impl!(self, fill_solid(area, color))
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        either_impl!(self, clear(color))
+    }
+}
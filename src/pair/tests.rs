@@ -0,0 +1,151 @@
+use super::*;
+use crate::{tests::rect, Budget, Rotate180, Rotate90, WithSize};
+use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+/// A display which fails every draw call.
+struct Broken;
+
+impl OriginDimensions for Broken {
+    fn size(&self) -> Size {
+        Size::new(64, 64)
+    }
+}
+
+impl DrawTarget for Broken {
+    type Color = BinaryColor;
+    type Error = &'static str;
+
+    fn draw_iter<I>(&mut self, _pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        Err("broken")
+    }
+}
+
+#[test]
+fn tee_draws_both() {
+    let mut tee = Tee::new(
+        MockDisplay::<BinaryColor>::new(),
+        Rotate180::new(MockDisplay::new()),
+    );
+
+    // More than one chunk of pixels
+    tee.draw_iter((0..40).map(|x| Pixel(Point::new(x, 0), BinaryColor::On)))
+        .unwrap();
+    tee.fill_contiguous(&rect(0, 2, 2, 1), [BinaryColor::On; 2])
+        .unwrap();
+
+    let (first, second) = tee.into_inner();
+    assert_eq!(first.affected_area(), rect(0, 0, 40, 3));
+    assert_eq!(second.as_ref().affected_area(), rect(24, 61, 40, 3));
+}
+
+#[test]
+fn tee_whole_chunks() {
+    // A whole number of chunks doesn't end with an empty call to each display
+    let mut tee = Tee::new(
+        Budget::new(MockDisplay::<BinaryColor>::new()).max_calls(2),
+        Budget::new(MockDisplay::new()).max_calls(2),
+    );
+    tee.draw_iter((0..2 * TEE_CHUNK as i32).map(|x| Pixel(Point::new(x, 0), BinaryColor::On)))
+        .unwrap();
+}
+
+#[test]
+fn tee_errors() {
+    let mut tee = Tee::new(Broken, MockDisplay::<BinaryColor>::new());
+
+    assert_eq!(
+        tee.draw_iter([Pixel(Point::new(1, 1), BinaryColor::On)]),
+        Err(PairError::First("broken"))
+    );
+    // The second display is still drawn to
+    assert_eq!(tee.second().affected_area(), rect(1, 1, 1, 1));
+
+    let mut tee = Tee::new(MockDisplay::<BinaryColor>::new(), Broken);
+    assert_eq!(
+        tee.clear(BinaryColor::Off),
+        Err(PairError::Second("broken"))
+    );
+}
+
+#[test]
+fn switch_routes() {
+    let mut switch = Switch::new(MockDisplay::<BinaryColor>::new(), Rotate90::new(Broken));
+
+    assert_eq!(switch.selected(), Selection::First);
+    switch
+        .fill_solid(&rect(1, 1, 2, 2), BinaryColor::On)
+        .unwrap();
+    assert_eq!(switch.first().affected_area(), rect(1, 1, 2, 2));
+
+    switch.select(Selection::Second);
+    assert_eq!(
+        switch.draw_iter([Pixel(Point::new(1, 1), BinaryColor::On)]),
+        Err(PairError::Second("broken"))
+    );
+}
+
+#[test]
+fn either_dispatches() {
+    let mut either: Either<MockDisplay<BinaryColor>, Rotate90<Broken>> =
+        Either::First(MockDisplay::new());
+
+    assert_eq!(either.selected(), Selection::First);
+    assert_eq!(either.bounding_box(), rect(0, 0, 64, 64));
+    either
+        .fill_solid(&rect(1, 1, 2, 2), BinaryColor::On)
+        .unwrap();
+    assert_eq!(
+        either.first().map(|disp| disp.affected_area()),
+        Some(rect(1, 1, 2, 2))
+    );
+    assert!(either.second().is_none());
+
+    let mut either: Either<MockDisplay<BinaryColor>, _> = Either::Second(Rotate90::new(Broken));
+    assert_eq!(either.selected(), Selection::Second);
+    assert_eq!(
+        either.draw_iter([Pixel(Point::new(1, 1), BinaryColor::On)]),
+        Err(PairError::Second("broken"))
+    );
+}
+
+#[test]
+fn failover() {
+    let mut disp = Failover::new(
+        Broken,
+        WithSize::new(MockDisplay::<BinaryColor>::new(), Size::new(32, 16)),
+        2,
+    );
+    assert_eq!(disp.bounding_box(), rect(0, 0, 64, 64));
+
+    assert_eq!(
+        disp.fill_solid(&rect(1, 1, 2, 2), BinaryColor::On),
+        Err(PairError::First("broken"))
+    );
+    assert_eq!(disp.selected(), Selection::First);
+    assert!(!disp.take_failover());
+
+    // The second failure in a row switches to the backup
+    assert_eq!(
+        disp.draw_iter([Pixel(Point::new(1, 1), BinaryColor::On)]),
+        Err(PairError::First("broken"))
+    );
+    assert_eq!(disp.selected(), Selection::Second);
+    assert!(disp.take_failover());
+    assert!(!disp.take_failover());
+    assert_eq!(disp.bounding_box(), rect(0, 0, 32, 16));
+
+    disp.fill_contiguous(&rect(1, 1, 2, 1), [BinaryColor::On; 2])
+        .unwrap();
+    assert_eq!(disp.second().affected_area(), rect(1, 1, 2, 1));
+
+    disp.restore();
+    assert_eq!(disp.selected(), Selection::First);
+    assert_eq!(
+        disp.clear(BinaryColor::Off),
+        Err(PairError::First("broken"))
+    );
+    assert_eq!(disp.selected(), Selection::First);
+}
@@ -0,0 +1,167 @@
+//! Polar coordinates for gauges and dials.
+
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+
+#[cfg(test)]
+mod tests;
+
+/// Quarter-wave sine table: `sin(i * π / 128)` in Q15, for `i` in `0..=64`.
+#[rustfmt::skip]
+const SIN_TABLE: [i16; 65] = [
+    0, 804, 1608, 2410, 3212, 4011, 4808, 5602,
+    6393, 7179, 7962, 8739, 9512, 10278, 11039, 11793,
+    12539, 13279, 14010, 14732, 15446, 16151, 16846, 17530,
+    18204, 18868, 19519, 20159, 20787, 21403, 22005, 22594,
+    23170, 23731, 24279, 24811, 25329, 25832, 26319, 26790,
+    27245, 27683, 28105, 28510, 28898, 29268, 29621, 29956,
+    30273, 30571, 30852, 31113, 31356, 31580, 31785, 31971,
+    32137, 32285, 32412, 32521, 32609, 32678, 32728, 32757,
+    32767,
+];
+
+/// Sine of a binary angle (65536 per revolution) in Q15.
+fn sin_q15(angle: u16) -> i32 {
+    let quadrant = angle >> 14;
+    let offset = u32::from(angle & 0x3fff);
+    // Offset into the quarter wave, mirrored in the 2nd and 4th quadrants.
+    let offset = if quadrant & 1 == 0 {
+        offset
+    } else {
+        0x4000 - offset
+    };
+
+    let (index, frac) = ((offset >> 8) as usize, (offset & 0xff) as i32);
+    let lo = i32::from(SIN_TABLE[index]);
+    let hi = i32::from(SIN_TABLE[(index + 1).min(64)]);
+    let value = lo + (((hi - lo) * frac) >> 8);
+
+    if quadrant & 2 == 0 {
+        value
+    } else {
+        -value
+    }
+}
+
+/// Multiply `r` by a Q15 fraction, rounding to the nearest integer.
+fn mul_q15(r: i32, q: i32) -> i32 {
+    (r * q + (1 << 14)) >> 15
+}
+
+/// Draw in polar coordinates.
+///
+/// The logical display's x axis is the angle and its y axis is the distance
+/// from the center. A full revolution is divided into `steps` angles, so the
+/// logical display is `steps` pixels wide and `radius` pixels high. Angles
+/// outside `0..steps` wrap around, so arcs can cross the zero angle; anything
+/// beyond `radius` is discarded.
+///
+/// By default angle zero points straight up and angles increase clockwise,
+/// like a clock face. This can be changed with
+/// [`with_zero_angle`](Self::with_zero_angle) and
+/// [`counter_clockwise`](Self::counter_clockwise).
+///
+/// Each logical pixel maps to a single physical pixel, computed with
+/// fixed-point trigonometry. Arcs will have gaps where the circumference at
+/// that radius is larger than `steps`, so drawing is best suited to radial
+/// lines (such as needles and tick marks) or to using enough steps.
+pub struct Polar<D> {
+    target: D,
+    map: PolarMap,
+}
+
+#[derive(Clone, Copy)]
+struct PolarMap {
+    center: Point,
+    steps: u32,
+    radius: u32,
+    zero: u32,
+    clockwise: bool,
+}
+
+impl PolarMap {
+    fn map_point(&self, p: Point) -> Option<Point> {
+        if p.y < 0 || p.y >= self.radius as i32 || self.steps == 0 {
+            return None;
+        }
+
+        let steps = i64::from(self.steps);
+        let angle = i64::from(p.x).rem_euclid(steps);
+        let angle = if self.clockwise { angle } else { steps - angle };
+        let angle = (angle + i64::from(self.zero)).rem_euclid(steps);
+        // Convert to a binary angle
+        let angle = ((angle << 16) / steps) as u16;
+
+        let sin = sin_q15(angle);
+        let cos = sin_q15(angle.wrapping_add(0x4000));
+
+        Some(Point::new(
+            self.center.x + mul_q15(p.y, sin),
+            self.center.y - mul_q15(p.y, cos),
+        ))
+    }
+}
+
+impl<D> Polar<D> {
+    /// Draw in polar coordinates around `center`, with `steps` angles per
+    /// revolution, out to `radius`.
+    pub fn new(target: D, center: Point, steps: u32, radius: u32) -> Self {
+        Polar {
+            target,
+            map: PolarMap {
+                center,
+                steps,
+                radius,
+                zero: 0,
+                clockwise: true,
+            },
+        }
+    }
+
+    /// Set the direction of angle zero, in steps clockwise from straight up.
+    pub fn with_zero_angle(mut self, zero: u32) -> Self {
+        self.map.zero = zero;
+        self
+    }
+
+    /// Make angles increase counter-clockwise.
+    pub fn counter_clockwise(mut self) -> Self {
+        self.map.clockwise = false;
+        self
+    }
+
+    /// Map a polar point (`x` angle, `y` radius) to the display, or `None` if
+    /// it's outside the radius.
+    pub fn map_point(&self, p: Point) -> Option<Point> {
+        self.map.map_point(p)
+    }
+}
+
+impl_wrapper!(Polar<D>);
+
+impl<D> Dimensions for Polar<D> {
+    fn bounding_box(&self) -> Rectangle {
+        Rectangle::new(Point::zero(), Size::new(self.map.steps, self.map.radius))
+    }
+}
+
+impl<D: DrawTarget> DrawTarget for Polar<D> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let map = self.map;
+
+        self.target.draw_iter(
+            pixels
+                .into_iter()
+                .filter_map(|Pixel(loc, col)| Some(Pixel(map.map_point(loc)?, col))),
+        )
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.target.clear(color)
+    }
+}
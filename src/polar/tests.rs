@@ -0,0 +1,56 @@
+use super::*;
+use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+#[test]
+fn sine() {
+    assert_eq!(sin_q15(0), 0);
+    assert_eq!(sin_q15(0x4000), 32767);
+    assert_eq!(sin_q15(0x8000), 0);
+    assert_eq!(sin_q15(0xc000), -32767);
+    // sin(30°)
+    assert!((sin_q15(0x1555) - 16384).abs() < 8);
+}
+
+#[test]
+fn polar_mapping() {
+    let polar = Polar::new((), Point::new(32, 32), 360, 20);
+    assert_eq!(polar.bounding_box().size, Size::new(360, 20));
+
+    assert_eq!(polar.map_point(Point::new(0, 10)), Some(Point::new(32, 22)));
+    assert_eq!(
+        polar.map_point(Point::new(90, 10)),
+        Some(Point::new(42, 32))
+    );
+    assert_eq!(
+        polar.map_point(Point::new(180, 10)),
+        Some(Point::new(32, 42))
+    );
+    assert_eq!(
+        polar.map_point(Point::new(-90, 10)),
+        Some(Point::new(22, 32))
+    );
+    assert_eq!(polar.map_point(Point::new(0, 20)), None);
+
+    let polar = polar.counter_clockwise().with_zero_angle(90);
+    assert_eq!(polar.map_point(Point::new(0, 10)), Some(Point::new(42, 32)));
+    assert_eq!(
+        polar.map_point(Point::new(90, 10)),
+        Some(Point::new(32, 22))
+    );
+}
+
+#[test]
+fn polar_needle() {
+    let mut disp = Polar::new(MockDisplay::<BinaryColor>::new(), Point::new(4, 4), 8, 4);
+
+    // A needle pointing right
+    disp.fill_solid(
+        &Rectangle::new(Point::new(2, 0), Size::new(1, 4)),
+        BinaryColor::On,
+    )
+    .unwrap();
+    disp.assert_pattern(&[
+        "        ", //
+        "        ", "        ", "        ", "    ####",
+    ]);
+}
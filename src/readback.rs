@@ -0,0 +1,194 @@
+//! Displays whose contents can be read back.
+
+use crate::{geometry, util, ColorChannels, Orientation};
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+
+#[cfg(test)]
+mod tests;
+
+/// A display whose pixels can be read back.
+///
+/// This is typically implemented by framebuffers, or by drivers for
+/// controllers which support reading their memory.
+pub trait ReadPixel: DrawTarget {
+    /// Return the color of the pixel at `p`, or `None` if it's outside the
+    /// display.
+    fn read_pixel(&self, p: Point) -> Option<Self::Color>;
+}
+
+/// Read back drawing to a `MockDisplay`, with the `test-util` feature.
+#[cfg(any(test, feature = "test-util"))]
+impl<C: PixelColor> ReadPixel for embedded_graphics::mock_display::MockDisplay<C> {
+    fn read_pixel(&self, p: Point) -> Option<C> {
+        if self.bounding_box().contains(p) {
+            self.get_pixel(p)
+        } else {
+            None
+        }
+    }
+}
+
+/// Read part of a display back in logical coordinates.
+///
+/// Reading a display drawn through a transform returns its contents as they
+/// are on the display, such as rotated. This instead reads `area` in the
+/// logical coordinates of `orientation`, as a [`Reorient`](crate::Reorient) in
+/// that orientation, or the equivalent transform, would draw it, and writes
+/// it to `buf` row by row, for screenshots or comparison. Points outside the
+/// display leave their place in `buf` unchanged.
+///
+/// Returns the number of places in `buf` covered, which is less than the size
+/// of `area` if `buf` is too small. Large areas can be read a row at a time.
+pub fn read_logical<D: ReadPixel>(
+    target: &D,
+    orientation: impl Into<Orientation>,
+    area: &Rectangle,
+    buf: &mut [D::Color],
+) -> usize {
+    let orientation = orientation.into();
+    let bounds = target.bounding_box();
+
+    buf.iter_mut()
+        .zip(util::points(area))
+        .map(|(slot, p)| {
+            let p = geometry::transform_point(orientation, bounds, p);
+            if let Some(color) = target.read_pixel(p) {
+                *slot = color;
+            }
+        })
+        .count()
+}
+
+/// Blend drawing with the existing contents of a display.
+///
+/// Each drawn color is combined with the color already at that position,
+/// weighted by an alpha value from 0 (fully transparent) to 255 (opaque), which
+/// can be changed between draws for fades and translucent overlays. This needs
+/// the wrapped display to implement [`ReadPixel`].
+///
+/// Since every pixel needs to be read before being written,
+/// [`fill_contiguous`](DrawTarget::fill_contiguous) and
+/// [`fill_solid`](DrawTarget::fill_solid) are forwarded in runs of up to 32
+/// pixels, and [`draw_iter`](DrawTarget::draw_iter) one pixel at a time.
+pub struct AlphaBlend<D> {
+    target: D,
+    alpha: u8,
+}
+
+/// Maximum number of pixels blended in one run.
+const BLEND_RUN: usize = 32;
+
+impl<D> AlphaBlend<D> {
+    /// Blend drawing with `alpha`.
+    pub fn new(target: D, alpha: u8) -> Self {
+        AlphaBlend { target, alpha }
+    }
+
+    /// Return the current alpha value.
+    pub fn alpha(&self) -> u8 {
+        self.alpha
+    }
+
+    /// Set the alpha value for subsequent drawing.
+    pub fn set_alpha(&mut self, alpha: u8) {
+        self.alpha = alpha;
+    }
+}
+
+impl<D> AlphaBlend<D>
+where
+    D: ReadPixel,
+    D::Color: ColorChannels,
+{
+    fn blend(&self, p: Point, color: D::Color) -> D::Color {
+        let under = match self.target.read_pixel(p) {
+            Some(under) => under.to_channels(),
+            None => return color,
+        };
+        let over = color.to_channels();
+        let alpha = u32::from(self.alpha);
+
+        D::Color::from_channels([0, 1, 2].map(|i| {
+            let (over, under) = (u32::from(over[i]), u32::from(under[i]));
+            ((over * alpha + under * (255 - alpha) + 127) / 255) as u8
+        }))
+    }
+}
+
+impl_wrapper!(AlphaBlend<D>);
+
+impl<D: Dimensions> Dimensions for AlphaBlend<D> {
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+impl<D> DrawTarget for AlphaBlend<D>
+where
+    D: ReadPixel,
+    D::Color: ColorChannels,
+{
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        match self.alpha {
+            0 => Ok(()),
+            255 => self.target.draw_iter(pixels),
+            _ => {
+                for Pixel(loc, color) in pixels {
+                    let color = self.blend(loc, color);
+                    self.target.draw_iter([Pixel(loc, color)])?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        match self.alpha {
+            0 => return Ok(()),
+            255 => return self.target.fill_contiguous(area, colors),
+            _ => {}
+        }
+
+        let mut pixels = util::points(area).zip(colors).peekable();
+        let mut run = match pixels.peek() {
+            Some(&(_, color)) => [color; BLEND_RUN],
+            None => return Ok(()),
+        };
+
+        // Blend runs of pixels within each row
+        while let Some((start, color)) = pixels.next() {
+            run[0] = self.blend(start, color);
+            let mut len = 1;
+
+            while len < BLEND_RUN {
+                match pixels.next_if(|(loc, _)| loc.y == start.y) {
+                    Some((loc, color)) => run[len] = self.blend(loc, color),
+                    None => break,
+                }
+                len += 1;
+            }
+
+            let row = Rectangle::new(start, Size::new(len as u32, 1));
+            self.target
+                .fill_contiguous(&row, run[..len].iter().copied())?;
+        }
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        match self.alpha {
+            0 => Ok(()),
+            255 => self.target.fill_solid(area, color),
+            _ => self.fill_contiguous(area, util::points(area).map(|_| color)),
+        }
+    }
+}
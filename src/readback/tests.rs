@@ -0,0 +1,82 @@
+use super::*;
+use crate::tests::rect;
+use embedded_graphics::{mock_display::MockDisplay, pixelcolor::Rgb888};
+
+fn background() -> MockDisplay<Rgb888> {
+    let mut disp = MockDisplay::new();
+    disp.set_allow_overdraw(true);
+    disp.fill_solid(&rect(0, 0, 40, 2), Rgb888::new(0, 0, 200))
+        .unwrap();
+    disp
+}
+
+#[test]
+fn alpha_blend() {
+    let mut disp = AlphaBlend::new(background(), 64);
+
+    disp.fill_solid(&rect(0, 0, 40, 2), Rgb888::new(255, 0, 0))
+        .unwrap();
+    disp.set_alpha(128);
+    disp.draw_iter([
+        Pixel(Point::new(0, 0), Rgb888::new(255, 255, 255)),
+        Pixel(Point::new(50, 0), Rgb888::new(255, 255, 255)),
+    ])
+    .unwrap();
+
+    let mock = disp.as_ref();
+    assert_eq!(
+        mock.get_pixel(Point::new(0, 0)),
+        Some(Rgb888::new(160, 128, 203))
+    );
+    assert_eq!(
+        mock.get_pixel(Point::new(39, 1)),
+        Some(Rgb888::new(64, 0, 150))
+    );
+    // Nothing to blend with
+    assert_eq!(mock.get_pixel(Point::new(50, 0)), Some(Rgb888::WHITE));
+    assert_eq!(mock.affected_area(), rect(0, 0, 51, 2));
+}
+
+#[test]
+fn alpha_blend_extremes() {
+    let mut disp = AlphaBlend::new(background(), 0);
+    disp.fill_contiguous(&rect(0, 0, 2, 1), [Rgb888::RED; 2])
+        .unwrap();
+    assert_eq!(
+        disp.as_ref().get_pixel(Point::new(0, 0)),
+        Some(Rgb888::new(0, 0, 200))
+    );
+
+    disp.set_alpha(255);
+    disp.fill_contiguous(&rect(0, 0, 2, 1), [Rgb888::RED; 2])
+        .unwrap();
+    assert_eq!(disp.as_ref().get_pixel(Point::new(1, 0)), Some(Rgb888::RED));
+}
+
+#[test]
+fn read_logical_rotated() {
+    use crate::{Orientation, Rotate90, Rotation};
+
+    let colors = [1, 2, 3, 4, 5, 6].map(|i| Rgb888::new(i, 0, 0));
+    let area = rect(2, 1, 3, 2);
+
+    let mut disp = Rotate90::new(MockDisplay::new());
+    disp.fill_contiguous(&area, colors).unwrap();
+    let disp = disp.into_inner();
+
+    let mut buf = [Rgb888::BLACK; 6];
+    assert_eq!(read_logical(&disp, Rotation::Rotate90, &area, &mut buf), 6);
+    assert_eq!(buf, colors);
+
+    // A row at a time, into a smaller buffer
+    let mut row = [Rgb888::BLACK; 4];
+    assert_eq!(read_logical(&disp, Rotation::Rotate90, &area, &mut row), 4);
+    assert_eq!(row, [colors[0], colors[1], colors[2], colors[3]]);
+
+    // Points outside the display are left alone
+    let mut buf = [Rgb888::WHITE; 3];
+    let edge = rect(64, 1, 3, 1);
+    let identity = Orientation::IDENTITY;
+    assert_eq!(read_logical(&disp, identity, &edge, &mut buf), 3);
+    assert_eq!(buf, [Rgb888::WHITE; 3]);
+}
@@ -0,0 +1,202 @@
+//! Record drawing so it can be replayed later.
+
+use core::marker::PhantomData;
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+
+#[cfg(all(test, feature = "heapless"))]
+mod tests;
+
+/// A recorded drawing operation.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Command<C> {
+    /// Draw a single pixel.
+    Pixel(Point, C),
+    /// Fill an area with a solid color.
+    FillSolid(Rectangle, C),
+    /// Fill an area with the colors in the [`Color`](Command::Color) commands
+    /// which follow.
+    FillContiguous(Rectangle),
+    /// One color of a [`FillContiguous`](Command::FillContiguous).
+    Color(C),
+    /// Clear the whole display.
+    Clear(C),
+}
+
+/// Storage for recorded commands.
+///
+/// This is implemented for `heapless::Vec` with the `heapless` feature, and for
+/// `alloc::vec::Vec` with the `alloc` feature.
+pub trait CommandBuffer<C> {
+    /// Append a command, returning it back if the buffer is full.
+    fn push(&mut self, command: Command<C>) -> Result<(), Command<C>>;
+
+    /// Return the recorded commands.
+    fn commands(&self) -> &[Command<C>];
+
+    /// Remove all the recorded commands.
+    fn clear(&mut self);
+}
+
+#[cfg(feature = "heapless")]
+impl<C, const N: usize> CommandBuffer<C> for heapless::Vec<Command<C>, N> {
+    fn push(&mut self, command: Command<C>) -> Result<(), Command<C>> {
+        heapless::Vec::push(self, command)
+    }
+
+    fn commands(&self) -> &[Command<C>] {
+        self
+    }
+
+    fn clear(&mut self) {
+        heapless::Vec::clear(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C> CommandBuffer<C> for alloc::vec::Vec<Command<C>> {
+    fn push(&mut self, command: Command<C>) -> Result<(), Command<C>> {
+        alloc::vec::Vec::push(self, command);
+        Ok(())
+    }
+
+    fn commands(&self) -> &[Command<C>] {
+        self
+    }
+
+    fn clear(&mut self) {
+        alloc::vec::Vec::clear(self)
+    }
+}
+
+/// Error returned when a [`Recorder`]'s buffer is full.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct BufferFull;
+
+/// A display which records drawing operations.
+///
+/// The recorded commands can be [replayed](Self::replay) any number of times
+/// onto other displays, including through different transforms. This allows a
+/// frame to be drawn once and shown on several displays, or for rendering to be
+/// deferred out of a time-critical section.
+///
+/// The recorder has a fixed size, which is reported as its bounding box, but
+/// doesn't clip anything drawn outside it. If the buffer fills up, drawing
+/// returns [`BufferFull`], and the commands recorded up to that point are
+/// kept.
+pub struct Recorder<C, B> {
+    buffer: B,
+    size: Size,
+    color: PhantomData<C>,
+}
+
+impl<C, B: CommandBuffer<C>> Recorder<C, B> {
+    /// Record drawing to a display of `size` into `buffer`.
+    pub fn new(buffer: B, size: Size) -> Self {
+        Recorder {
+            buffer,
+            size,
+            color: PhantomData,
+        }
+    }
+
+    /// Return the recorded commands.
+    pub fn commands(&self) -> &[Command<C>] {
+        self.buffer.commands()
+    }
+
+    /// Discard all recorded commands.
+    pub fn reset(&mut self) {
+        self.buffer.clear()
+    }
+
+    /// Recover the command buffer.
+    pub fn into_inner(self) -> B {
+        self.buffer
+    }
+
+    fn push(&mut self, command: Command<C>) -> Result<(), BufferFull> {
+        self.buffer.push(command).map_err(|_| BufferFull)
+    }
+}
+
+impl<C: PixelColor, B: CommandBuffer<C>> Recorder<C, B> {
+    /// Replay the recorded commands onto `target`.
+    pub fn replay<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        replay(self.commands(), target)
+    }
+}
+
+/// Replay recorded commands onto `target`.
+pub fn replay<C, D>(commands: &[Command<C>], target: &mut D) -> Result<(), D::Error>
+where
+    C: PixelColor,
+    D: DrawTarget<Color = C>,
+{
+    let mut commands = commands.iter().peekable();
+
+    while let Some(command) = commands.next() {
+        match *command {
+            Command::Pixel(loc, color) => target.draw_iter([Pixel(loc, color)])?,
+            Command::FillSolid(area, color) => target.fill_solid(&area, color)?,
+            Command::FillContiguous(area) => {
+                let colors = core::iter::from_fn(|| {
+                    match commands.next_if(|c| matches!(c, Command::Color(_))) {
+                        Some(&Command::Color(color)) => Some(color),
+                        _ => None,
+                    }
+                });
+                target.fill_contiguous(&area, colors)?
+            }
+            // Only present after a `FillContiguous`, so there's nothing to do
+            // with a stray one.
+            Command::Color(_) => (),
+            Command::Clear(color) => target.clear(color)?,
+        }
+    }
+    Ok(())
+}
+
+impl<C, B> Dimensions for Recorder<C, B> {
+    fn bounding_box(&self) -> Rectangle {
+        Rectangle::new(Point::zero(), self.size)
+    }
+}
+
+impl<C: PixelColor, B: CommandBuffer<C>> DrawTarget for Recorder<C, B> {
+    type Color = C;
+    type Error = BufferFull;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(loc, color) in pixels {
+            self.push(Command::Pixel(loc, color))?;
+        }
+        Ok(())
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let count = area.size.width as usize * area.size.height as usize;
+
+        self.push(Command::FillContiguous(*area))?;
+        for color in colors.into_iter().take(count) {
+            self.push(Command::Color(color))?;
+        }
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.push(Command::FillSolid(*area, color))
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.push(Command::Clear(color))
+    }
+}
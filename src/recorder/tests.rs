@@ -0,0 +1,57 @@
+use super::*;
+use crate::{tests::rect, Rotate90};
+use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+fn draw<D: DrawTarget<Color = BinaryColor>>(disp: &mut D) -> Result<(), D::Error> {
+    disp.draw_iter([Pixel(Point::new(0, 0), BinaryColor::On)])?;
+    disp.fill_contiguous(&rect(1, 0, 2, 1), [BinaryColor::Off, BinaryColor::On])?;
+    disp.fill_contiguous(&rect(0, 1, 1, 1), [BinaryColor::On])?;
+    disp.fill_solid(&rect(0, 2, 3, 1), BinaryColor::On)
+}
+
+#[test]
+fn record_replay() {
+    let mut rec = Recorder::new(heapless::Vec::<_, 16>::new(), Size::new(64, 64));
+    draw(&mut rec).unwrap();
+    assert_eq!(rec.commands().len(), 7);
+
+    // Replaying is the same as drawing directly
+    let mut direct = Rotate90::new(MockDisplay::new());
+    draw(&mut direct).unwrap();
+    let mut replayed = Rotate90::new(MockDisplay::new());
+    rec.replay(&mut replayed).unwrap();
+    replayed.as_ref().assert_eq(direct.as_ref());
+}
+
+#[test]
+fn record_full() {
+    let mut rec = Recorder::new(heapless::Vec::<_, 4>::new(), Size::new(64, 64));
+    assert_eq!(draw(&mut rec), Err(BufferFull));
+    assert_eq!(
+        rec.commands(),
+        [
+            Command::Pixel(Point::zero(), BinaryColor::On),
+            Command::FillContiguous(rect(1, 0, 2, 1)),
+            Command::Color(BinaryColor::Off),
+            Command::Color(BinaryColor::On),
+        ]
+    );
+
+    rec.reset();
+    assert!(rec.commands().is_empty());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn record_alloc() {
+    let mut rec = Recorder::new(alloc::vec::Vec::new(), Size::new(64, 64));
+    draw(&mut rec).unwrap();
+
+    let mut disp = MockDisplay::new();
+    replay(&rec.into_inner(), &mut disp).unwrap();
+    disp.assert_pattern(&[
+        "#.#", //
+        "#  ", //
+        "###", //
+    ]);
+}
@@ -0,0 +1,377 @@
+//! Split a display into independent regions.
+
+use crate::{geometry, oriented, util, Orientation};
+use core::cell::RefCell;
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+
+#[cfg(test)]
+mod tests;
+
+/// Errors from [`Regions::split`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum RegionError {
+    /// The region with this index extends outside the display.
+    OutOfBounds(usize),
+    /// The regions with these indices overlap.
+    Overlap(usize, usize),
+}
+
+/// A display which can be split into non-overlapping [`Region`]s.
+///
+/// For the common case of dividing the screen in two, use
+/// [`split_at_x`](Self::split_at_x) or [`split_at_y`](Self::split_at_y).
+/// Arbitrary layouts can be created with [`split`](Self::split).
+///
+/// Each region is an independent [`DrawTarget`] with its own origin and clip
+/// rectangle, so different parts of an application can each own a part of the
+/// screen without coordinating. Regions share the display through a
+/// [`RefCell`], which is borrowed for the duration of each draw call.
+///
+/// Region areas are in the coordinates of the display passed to
+/// [`new`](Self::new), so if it is already transformed (for example with
+/// [`Rotate90`](crate::Rotate90)) the regions are laid out in logical
+/// coordinates. A region can have its own orientation by wrapping it in any of
+/// the transforms in this crate.
+pub struct Regions<D> {
+    target: RefCell<D>,
+}
+
+impl<D> Regions<D> {
+    /// Prepare a display for splitting into regions.
+    pub fn new(target: D) -> Self {
+        Regions {
+            target: RefCell::new(target),
+        }
+    }
+
+    /// Recover the inner display instance.
+    pub fn into_inner(self) -> D {
+        self.target.into_inner()
+    }
+
+    /// Get mutable access to the display.
+    ///
+    /// This can't be used while any regions are borrowed.
+    pub fn get_mut(&mut self) -> &mut D {
+        self.target.get_mut()
+    }
+}
+
+impl<D: Dimensions> Regions<D> {
+    /// Split the display into regions with the given areas.
+    ///
+    /// The areas must lie within the display's bounding box and must not
+    /// overlap each other.
+    pub fn split<const N: usize>(
+        &self,
+        areas: [Rectangle; N],
+    ) -> Result<[Region<'_, D>; N], RegionError> {
+        let bounds = self.target.borrow().bounding_box();
+
+        for (i, area) in areas.iter().enumerate() {
+            if !util::contains_rect(&bounds, area) {
+                return Err(RegionError::OutOfBounds(i));
+            }
+            for (j, other) in areas[..i].iter().enumerate() {
+                if !util::is_empty(&area.intersection(other)) {
+                    return Err(RegionError::Overlap(j, i));
+                }
+            }
+        }
+
+        Ok(areas.map(|area| self.region(area)))
+    }
+
+    /// Split the display into left and right regions, `x` pixels from the left
+    /// edge.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` is greater than the display width.
+    pub fn split_at_x(&self, x: u32) -> (Region<'_, D>, Region<'_, D>) {
+        let bounds = self.target.borrow().bounding_box();
+        assert!(x <= bounds.size.width, "split point outside display");
+
+        let left = Rectangle {
+            top_left: bounds.top_left,
+            size: Size::new(x, bounds.size.height),
+        };
+        let right = Rectangle {
+            top_left: bounds.top_left + Point::new(x as i32, 0),
+            size: Size::new(bounds.size.width - x, bounds.size.height),
+        };
+        (self.region(left), self.region(right))
+    }
+
+    /// Split the display into top and bottom regions, `y` pixels from the top
+    /// edge.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `y` is greater than the display height.
+    pub fn split_at_y(&self, y: u32) -> (Region<'_, D>, Region<'_, D>) {
+        let bounds = self.target.borrow().bounding_box();
+        assert!(y <= bounds.size.height, "split point outside display");
+
+        let top = Rectangle {
+            top_left: bounds.top_left,
+            size: Size::new(bounds.size.width, y),
+        };
+        let bottom = Rectangle {
+            top_left: bounds.top_left + Point::new(0, y as i32),
+            size: Size::new(bounds.size.width, bounds.size.height - y),
+        };
+        (self.region(top), self.region(bottom))
+    }
+
+    fn region(&self, area: Rectangle) -> Region<'_, D> {
+        Region {
+            target: &self.target,
+            area,
+        }
+    }
+}
+
+/// A rectangular part of a display, created by [`Regions`].
+///
+/// The region's bounding box has its top left at the origin, and all drawing
+/// is clipped to the region.
+pub struct Region<'a, D> {
+    target: &'a RefCell<D>,
+    area: Rectangle,
+}
+
+impl<D> Region<'_, D> {
+    /// The area this region covers on the underlying display.
+    pub fn area(&self) -> Rectangle {
+        self.area
+    }
+}
+
+impl<D> Dimensions for Region<'_, D> {
+    fn bounding_box(&self) -> Rectangle {
+        Rectangle {
+            top_left: Point::zero(),
+            size: self.area.size,
+        }
+    }
+}
+
+impl<D: DrawTarget> DrawTarget for Region<'_, D> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let area = self.area;
+        util::window_draw_iter(&mut *self.target.borrow_mut(), area.top_left, area, pixels)
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let clip = self.area;
+        util::window_fill_contiguous(
+            &mut *self.target.borrow_mut(),
+            clip.top_left,
+            clip,
+            area,
+            colors,
+        )
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let clip = self.area;
+        util::window_fill_solid(
+            &mut *self.target.borrow_mut(),
+            clip.top_left,
+            clip,
+            area,
+            color,
+        )
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.target.borrow_mut().fill_solid(&self.area, color)
+    }
+}
+
+/// A part of a [`Zones`] display with its own orientation.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Zone {
+    /// The area the zone covers in the combined logical display.
+    pub logical: Rectangle,
+    /// The top left of the zone on the physical display.
+    pub physical: Point,
+    /// The orientation of the zone relative to the physical display.
+    pub orientation: Orientation,
+}
+
+impl Zone {
+    /// A zone covering `logical` in the combined display, drawn at `physical`
+    /// on the physical display in `orientation`.
+    pub const fn new(logical: Rectangle, physical: Point, orientation: Orientation) -> Self {
+        Zone {
+            logical,
+            physical,
+            orientation,
+        }
+    }
+
+    /// Return the area the zone covers on the physical display.
+    pub const fn physical_area(&self) -> Rectangle {
+        Rectangle::new(
+            self.physical,
+            geometry::inverse_transform_size(self.orientation, self.logical.size),
+        )
+    }
+
+    fn map_point(&self, p: Point) -> Point {
+        geometry::transform_point(
+            self.orientation,
+            self.physical_area(),
+            p - self.logical.top_left,
+        )
+    }
+}
+
+/// A display whose zones each have their own orientation, drawn to as a
+/// single logical display.
+///
+/// Some panels have a part of the glass mounted differently from the rest,
+/// such as a label rail along one side which is read sideways. Each [`Zone`]
+/// maps an area of the logical display to an area of the physical display in
+/// its own orientation, and drawing is routed to the zone it falls in.
+/// Anything drawn outside every zone is discarded.
+///
+/// Unlike [`Regions`], which gives each region its own origin, this is one
+/// display whose bounding box covers all the zones, so a single UI can be laid
+/// out across them.
+pub struct Zones<D, const N: usize> {
+    target: D,
+    zones: [Zone; N],
+    bounds: Rectangle,
+}
+
+impl<D: Dimensions, const N: usize> Zones<D, N> {
+    /// Divide a display into `zones`.
+    ///
+    /// The zones' physical areas must lie within the display's bounding box,
+    /// and neither their physical nor their logical areas may overlap.
+    pub fn new(target: D, zones: [Zone; N]) -> Result<Self, RegionError> {
+        let display = target.bounding_box();
+
+        for (i, zone) in zones.iter().enumerate() {
+            if !util::contains_rect(&display, &zone.physical_area()) {
+                return Err(RegionError::OutOfBounds(i));
+            }
+            for (j, other) in zones[..i].iter().enumerate() {
+                if !util::is_empty(&zone.logical.intersection(&other.logical))
+                    || !util::is_empty(&zone.physical_area().intersection(&other.physical_area()))
+                {
+                    return Err(RegionError::Overlap(j, i));
+                }
+            }
+        }
+
+        let bounds = zones
+            .iter()
+            .map(|zone| zone.logical)
+            .filter(|area| !util::is_empty(area))
+            .reduce(|a, b| {
+                Rectangle::with_corners(
+                    a.top_left.component_min(b.top_left),
+                    a.bottom_right()
+                        .unwrap()
+                        .component_max(b.bottom_right().unwrap()),
+                )
+            })
+            .unwrap_or(Rectangle::zero());
+
+        Ok(Zones {
+            target,
+            zones,
+            bounds,
+        })
+    }
+}
+
+impl<D, const N: usize> Zones<D, N> {
+    /// Return the zones.
+    pub fn zones(&self) -> &[Zone; N] {
+        &self.zones
+    }
+}
+
+impl_wrapper!(Zones<D; const N: usize>);
+
+impl<D, const N: usize> Dimensions for Zones<D, N> {
+    fn bounding_box(&self) -> Rectangle {
+        self.bounds
+    }
+}
+
+impl<D: DrawTarget, const N: usize> DrawTarget for Zones<D, N> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let zones = &self.zones;
+        self.target
+            .draw_iter(pixels.into_iter().filter_map(|Pixel(loc, col)| {
+                let zone = zones.iter().find(|zone| zone.logical.contains(loc))?;
+                Some(Pixel(zone.map_point(loc), col))
+            }))
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        // An area within one zone can be filled in that zone's orientation.
+        let within = self
+            .zones
+            .iter()
+            .find(|zone| util::contains_rect(&zone.logical, area))
+            .copied();
+        match within {
+            Some(zone) => oriented::fill_contiguous(
+                &mut self.target,
+                zone.orientation,
+                zone.logical.size,
+                zone.physical,
+                &util::translate(area, -zone.logical.top_left),
+                colors,
+            ),
+            None => self.draw_iter(
+                util::points(area)
+                    .zip(colors)
+                    .map(|(loc, col)| Pixel(loc, col)),
+            ),
+        }
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        for zone in &self.zones {
+            let clip = area.intersection(&zone.logical);
+            if util::is_empty(&clip) {
+                continue;
+            }
+            let clip = util::translate(&clip, -zone.logical.top_left);
+            let physical = geometry::transform_rect(zone.orientation, zone.physical_area(), clip);
+            self.target.fill_solid(&physical, color)?;
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        let area = self.bounds;
+        self.fill_solid(&area, color)
+    }
+}
@@ -0,0 +1,140 @@
+use super::*;
+use crate::{tests::rect, Rotate90};
+use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+#[test]
+fn split_regions() {
+    let regions = Regions::new(MockDisplay::<BinaryColor>::new());
+
+    let [mut left, mut top, bottom] = regions
+        .split([rect(0, 0, 2, 4), rect(2, 0, 4, 2), rect(2, 2, 4, 2)])
+        .expect("split failed");
+
+    assert_eq!(left.bounding_box(), rect(0, 0, 2, 4));
+    assert_eq!(bottom.area(), rect(2, 2, 4, 2));
+
+    left.clear(BinaryColor::On).unwrap();
+    // Only the first pixel is inside the region
+    top.draw_iter([
+        Pixel(Point::new(3, 1), BinaryColor::On),
+        Pixel(Point::new(4, 1), BinaryColor::On),
+        Pixel(Point::new(0, 2), BinaryColor::On),
+    ])
+    .unwrap();
+
+    // Regions can have their own orientation
+    let mut bottom = Rotate90::new(bottom);
+    assert_eq!(bottom.bounding_box(), rect(0, 0, 2, 4));
+    bottom
+        .draw_iter([Pixel(Point::new(0, 0), BinaryColor::Off)])
+        .unwrap();
+
+    regions
+        .into_inner()
+        .assert_pattern(&["##    ", "##   #", "##   .", "##    "]);
+}
+
+#[test]
+fn split_errors() {
+    let regions = Regions::new(MockDisplay::<BinaryColor>::new());
+
+    assert_eq!(
+        regions
+            .split([rect(0, 0, 10, 10), rect(60, 0, 10, 10)])
+            .err(),
+        Some(RegionError::OutOfBounds(1))
+    );
+    assert_eq!(
+        regions
+            .split([rect(0, 0, 10, 10), rect(20, 0, 10, 10), rect(25, 5, 10, 10)])
+            .err(),
+        Some(RegionError::Overlap(1, 2))
+    );
+}
+
+#[test]
+fn split_at() {
+    let regions = Regions::new(Rotate90::new(MockDisplay::<BinaryColor>::new()));
+
+    let (left, right) = regions.split_at_x(20);
+    assert_eq!(left.bounding_box(), rect(0, 0, 20, 64));
+    assert_eq!(right.bounding_box(), rect(0, 0, 44, 64));
+    assert_eq!(right.area(), rect(20, 0, 44, 64));
+
+    let (mut top, mut bottom) = regions.split_at_y(60);
+    assert_eq!(bottom.bounding_box(), rect(0, 0, 64, 4));
+
+    top.draw_iter([Pixel(Point::new(0, 60), BinaryColor::On)])
+        .unwrap();
+    bottom
+        .draw_iter([Pixel(Point::new(0, 0), BinaryColor::On)])
+        .unwrap();
+
+    // Only the bottom pixel is drawn, at logical (0, 60) on the rotated display
+    let display = regions.into_inner().into_inner();
+    assert_eq!(
+        display.affected_area(),
+        Rectangle::new(Point::new(3, 0), Size::new(1, 1))
+    );
+}
+
+#[test]
+fn zones() {
+    use crate::{Orientation, Rotation};
+
+    // A 40x24 panel with an 8 pixel label rail down its right side, read
+    // sideways, laid out as a 32x24 main area with the rail below it
+    let main = Zone::new(rect(0, 0, 32, 24), Point::zero(), Orientation::IDENTITY);
+    let rail = Zone::new(
+        rect(0, 24, 24, 8),
+        Point::new(32, 0),
+        Rotation::Rotate90.into(),
+    );
+    assert_eq!(rail.physical_area(), rect(32, 0, 8, 24));
+
+    let panel = crate::WithSize::new(MockDisplay::<BinaryColor>::new(), Size::new(40, 24));
+    let mut disp = Zones::new(panel, [main, rail]).unwrap();
+    assert_eq!(disp.bounding_box(), rect(0, 0, 32, 32));
+
+    disp.draw_iter([
+        Pixel(Point::new(1, 2), BinaryColor::On),
+        Pixel(Point::new(0, 24), BinaryColor::On),
+        // Outside every zone
+        Pixel(Point::new(30, 30), BinaryColor::On),
+    ])
+    .unwrap();
+    assert_eq!(disp.get_pixel(Point::new(1, 2)), Some(BinaryColor::On));
+    assert_eq!(disp.get_pixel(Point::new(39, 0)), Some(BinaryColor::On));
+
+    // Fills within a zone and across zones
+    disp.fill_contiguous(&rect(2, 25, 3, 1), [BinaryColor::On; 3])
+        .unwrap();
+    disp.fill_solid(&rect(20, 20, 2, 6), BinaryColor::On)
+        .unwrap();
+    let disp = disp.into_inner().into_inner();
+    assert_eq!(disp.get_pixel(Point::new(38, 2)), Some(BinaryColor::On));
+    assert_eq!(disp.get_pixel(Point::new(38, 4)), Some(BinaryColor::On));
+    assert_eq!(disp.get_pixel(Point::new(21, 23)), Some(BinaryColor::On));
+    assert_eq!(disp.get_pixel(Point::new(39, 20)), Some(BinaryColor::On));
+    assert_eq!(disp.get_pixel(Point::new(38, 21)), Some(BinaryColor::On));
+
+    let panel = crate::WithSize::new(MockDisplay::<BinaryColor>::new(), Size::new(36, 24));
+    assert!(matches!(
+        Zones::new(panel, [main, rail]),
+        Err(RegionError::OutOfBounds(1))
+    ));
+    let panel = crate::WithSize::new(MockDisplay::<BinaryColor>::new(), Size::new(40, 24));
+    assert!(matches!(
+        Zones::new(
+            panel,
+            [
+                main,
+                Zone {
+                    physical: Point::new(30, 0),
+                    ..rail
+                }
+            ]
+        ),
+        Err(RegionError::Overlap(0, 1))
+    ));
+}
@@ -0,0 +1,145 @@
+//! Redraw the background under things which have moved.
+
+use crate::{scoped::ByRef, util, Offset};
+use embedded_graphics_core::{image::ImageDrawable, prelude::*, primitives::Rectangle};
+
+#[cfg(test)]
+mod tests;
+
+/// Static content which any part of can be redrawn, for [`Repair`].
+///
+/// This is implemented for any image, with its top left at the origin, and for
+/// [`SolidColor`].
+pub trait Background {
+    /// The background's color type.
+    type Color: PixelColor;
+
+    /// Draw the part of the background in `area` to `target`, in the same
+    /// coordinates.
+    fn draw_area<D>(&self, target: &mut D, area: &Rectangle) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>;
+}
+
+/// A background of a single color.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct SolidColor<C>(pub C);
+
+impl<C: PixelColor> Background for SolidColor<C> {
+    type Color = C;
+
+    fn draw_area<D>(&self, target: &mut D, area: &Rectangle) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        target.fill_solid(area, self.0)
+    }
+}
+
+impl<I: ImageDrawable> Background for I {
+    type Color = I::Color;
+
+    fn draw_area<D>(&self, target: &mut D, area: &Rectangle) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let area = area.intersection(&self.bounding_box());
+        if util::is_empty(&area) {
+            return Ok(());
+        }
+        self.draw_sub_image(&mut Offset::new(ByRef(target), area.top_left), &area)
+    }
+}
+
+/// A display with a static background which can be redrawn on demand.
+///
+/// Moving a sprite over a static background without redrawing the whole frame
+/// means erasing it from where it was, by redrawing just that part of the
+/// background. [`repair`](Self::repair) does that for any area, typically one
+/// reported by some form of dirty tracking, such as the sprite's previous
+/// bounding box. Drawing is otherwise passed through unchanged.
+///
+/// The background is in the wrapped display's coordinates. It's usually
+/// simplest for this to wrap the driver directly, so that the background is
+/// in physical coordinates, with any transforms outside it; a logical area can
+/// be mapped to physical coordinates with
+/// [`geometry::transform_rect`](crate::geometry::transform_rect).
+pub struct Repair<D, B> {
+    target: D,
+    background: B,
+}
+
+impl<D, B> Repair<D, B> {
+    /// Wrap `target`, whose background is `background`.
+    pub fn new(target: D, background: B) -> Self {
+        Repair { target, background }
+    }
+
+    /// Return the background.
+    pub fn background(&self) -> &B {
+        &self.background
+    }
+
+    /// Replace the background, returning the old one.
+    ///
+    /// This doesn't redraw anything.
+    pub fn set_background(&mut self, background: B) -> B {
+        core::mem::replace(&mut self.background, background)
+    }
+}
+
+impl<D, B> Repair<D, B>
+where
+    D: DrawTarget,
+    B: Background<Color = D::Color>,
+{
+    /// Redraw the background in `area`, clipped to the display.
+    pub fn repair(&mut self, area: &Rectangle) -> Result<(), D::Error> {
+        let area = area.intersection(&self.target.bounding_box());
+        if util::is_empty(&area) {
+            return Ok(());
+        }
+        self.background.draw_area(&mut self.target, &area)
+    }
+
+    /// Redraw the whole background.
+    pub fn repair_all(&mut self) -> Result<(), D::Error> {
+        let area = self.target.bounding_box();
+        self.background.draw_area(&mut self.target, &area)
+    }
+}
+
+impl_wrapper!(Repair<D, B>);
+
+impl<D: Dimensions, B> Dimensions for Repair<D, B> {
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+impl<D: DrawTarget, B> DrawTarget for Repair<D, B> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.target.draw_iter(pixels)
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        self.target.fill_contiguous(area, colors)
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.target.fill_solid(area, color)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.target.clear(color)
+    }
+}
@@ -0,0 +1,62 @@
+use super::*;
+use crate::{tests::rect, Rotate90};
+use embedded_graphics::{
+    image::{Image, ImageRaw},
+    mock_display::MockDisplay,
+    pixelcolor::BinaryColor,
+};
+
+#[test]
+fn repair_image() {
+    // A checkerboard background, 8x4
+    let data = [0b1010_1010, 0b0101_0101, 0b1010_1010, 0b0101_0101];
+    let raw = ImageRaw::<BinaryColor>::new(&data, 8);
+
+    let mut expected = MockDisplay::new();
+    Image::new(&raw, Point::zero()).draw(&mut expected).unwrap();
+
+    let mut disp = Repair::new(MockDisplay::new(), raw);
+    disp.set_allow_overdraw(true);
+    disp.repair_all().unwrap();
+    disp.assert_eq(&expected);
+
+    // Draw a sprite, and then erase it
+    disp.fill_solid(&rect(2, 1, 3, 2), BinaryColor::On).unwrap();
+    disp.repair(&rect(2, 1, 3, 2)).unwrap();
+    disp.assert_eq(&expected);
+
+    // Areas outside the background are left alone
+    disp.fill_solid(&rect(6, 2, 4, 4), BinaryColor::On).unwrap();
+    disp.repair(&rect(6, 2, 4, 4)).unwrap();
+    disp.assert_pattern(&[
+        "#.#.#.#.  ", //
+        ".#.#.#.#  ", //
+        "#.#.#.#.##", //
+        ".#.#.#.###", //
+        "      ####", //
+        "      ####", //
+    ]);
+}
+
+#[test]
+fn repair_logical() {
+    let mut disp = Rotate90::new(Repair::new(
+        MockDisplay::new(),
+        SolidColor(BinaryColor::Off),
+    ));
+    disp.as_mut().set_allow_overdraw(true);
+
+    disp.fill_solid(&rect(0, 0, 2, 1), BinaryColor::On).unwrap();
+    let physical = crate::geometry::transform_rect(
+        crate::Rotation::Rotate90.into(),
+        rect(0, 0, 64, 64),
+        rect(0, 0, 2, 1),
+    );
+    assert_eq!(physical, rect(63, 0, 1, 2));
+    disp.as_mut().repair(&physical).unwrap();
+    assert_eq!(
+        disp.as_ref().get_pixel(Point::new(63, 1)),
+        Some(BinaryColor::Off)
+    );
+    assert_eq!(disp.as_ref().affected_area(), rect(63, 0, 1, 2));
+}
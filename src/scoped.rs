@@ -0,0 +1,84 @@
+//! Draw single drawables through a transform.
+
+use crate::{geometry, Orientation, Reorient, Rotation};
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+
+#[cfg(test)]
+mod tests;
+
+/// A borrowed display, so that it can be wrapped for the duration of a draw.
+pub(crate) struct ByRef<'a, D>(pub(crate) &'a mut D);
+
+impl<D: Dimensions> Dimensions for ByRef<'_, D> {
+    fn bounding_box(&self) -> Rectangle {
+        self.0.bounding_box()
+    }
+}
+
+impl<D: DrawTarget> DrawTarget for ByRef<'_, D> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.0.draw_iter(pixels)
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        self.0.fill_contiguous(area, colors)
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.0.fill_solid(area, color)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.0.clear(color)
+    }
+}
+
+/// Draw `drawable` on `target` in `orientation`.
+///
+/// This draws the same as wrapping the display in the corresponding wrapper,
+/// such as [`FlipX`](crate::FlipX), drawing, and then unwrapping it again, for
+/// the common case of a single item drawn transformed.
+pub fn draw_transformed<D, T>(
+    target: &mut D,
+    drawable: &T,
+    orientation: Orientation,
+) -> Result<T::Output, D::Error>
+where
+    D: DrawTarget,
+    T: Drawable<Color = D::Color>,
+{
+    drawable.draw(&mut Reorient::new(ByRef(target), orientation))
+}
+
+/// Draw `drawable` rotated by `rotation` around `pivot`.
+///
+/// This is for drawing the odd rotated item, such as a vertical axis label on a
+/// plot, without wrapping the whole display. For example, text positioned at
+/// `pivot` and rotated by [`Rotate270`](Rotation::Rotate270) reads upwards
+/// from `pivot`. The display is only wrapped for the duration of the draw.
+pub fn draw_rotated<D, T>(
+    target: &mut D,
+    drawable: &T,
+    rotation: Rotation,
+    pivot: Point,
+) -> Result<T::Output, D::Error>
+where
+    D: DrawTarget,
+    T: Drawable<Color = D::Color>,
+{
+    let orientation = Orientation::from(rotation);
+    let bounds = Rectangle::new(Point::zero(), target.bounding_box().size);
+    // Move the rotated pivot back to where it started
+    let offset = pivot - geometry::transform_point(orientation, bounds, pivot);
+
+    drawable.draw(&mut Reorient::new(ByRef(target), orientation).with_offset(offset))
+}
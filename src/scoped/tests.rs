@@ -0,0 +1,84 @@
+use super::*;
+use embedded_graphics::{
+    mock_display::MockDisplay,
+    mono_font::{ascii::FONT_6X10, MonoTextStyle},
+    pixelcolor::BinaryColor,
+    text::Text,
+};
+
+#[test]
+fn transformed() {
+    use crate::{FlipX, Rotate90};
+
+    let style = MonoTextStyle::new(&FONT_6X10, BinaryColor::On);
+    let text = Text::new("Icon", Point::new(5, 20), style);
+
+    let mut expected = FlipX::new(MockDisplay::new());
+    text.draw(&mut expected).unwrap();
+    let mut disp = MockDisplay::new();
+    draw_transformed(&mut disp, &text, Orientation::IDENTITY.flip_x()).unwrap();
+    disp.assert_eq(expected.as_ref());
+
+    let mut expected = Rotate90::new(MockDisplay::new());
+    text.draw(&mut expected).unwrap();
+    let mut disp = MockDisplay::new();
+    draw_transformed(&mut disp, &text, Rotation::Rotate90.into()).unwrap();
+    disp.assert_eq(expected.as_ref());
+}
+
+#[test]
+fn rotated_pixel() {
+    let mut disp = MockDisplay::new();
+    let pivot = Point::new(20, 30);
+
+    draw_rotated(
+        &mut disp,
+        &Pixel(pivot + Point::new(2, 1), BinaryColor::On),
+        Rotation::Rotate90,
+        pivot,
+    )
+    .unwrap();
+    draw_rotated(
+        &mut disp,
+        &Pixel(pivot, BinaryColor::Off),
+        Rotation::Rotate270,
+        pivot,
+    )
+    .unwrap();
+
+    assert_eq!(disp.get_pixel(Point::new(19, 32)), Some(BinaryColor::On));
+    assert_eq!(disp.get_pixel(pivot), Some(BinaryColor::Off));
+}
+
+/// The points which have been drawn.
+fn drawn(disp: &MockDisplay<BinaryColor>) -> impl Iterator<Item = Point> + '_ {
+    crate::util::points(&disp.bounding_box()).filter(|&p| disp.get_pixel(p).is_some())
+}
+
+#[test]
+fn rotated_text() {
+    let style = MonoTextStyle::new(&FONT_6X10, BinaryColor::On);
+    let pivot = Point::new(30, 30);
+    let text = Text::new("Axis", pivot, style);
+
+    let mut upright = MockDisplay::new();
+    text.draw(&mut upright).unwrap();
+
+    for rotation in [Rotation::Rotate90, Rotation::Rotate180, Rotation::Rotate270] {
+        let mut rotated = MockDisplay::new();
+        draw_rotated(&mut rotated, &text, rotation, pivot).unwrap();
+
+        // Every pixel is rotated around the pivot
+        for p in drawn(&upright) {
+            let d = p - pivot;
+            let d = match rotation {
+                Rotation::Rotate0 => d,
+                Rotation::Rotate90 => Point::new(-d.y, d.x),
+                Rotation::Rotate180 => Point::new(-d.x, -d.y),
+                Rotation::Rotate270 => Point::new(d.y, -d.x),
+            };
+            assert_eq!(rotated.get_pixel(pivot + d), upright.get_pixel(p));
+        }
+        assert_eq!(drawn(&rotated).count(), drawn(&upright).count());
+    }
+}
@@ -0,0 +1,254 @@
+//! Scroll displays with wrap-around.
+
+use crate::r#impl::Transpose;
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+
+#[cfg(test)]
+mod tests;
+
+/// Controller-side vertical scrolling.
+///
+/// Many controllers (such as the SSD1306 or ST7789) can change which row of
+/// display memory is shown at the top of the screen, with rows wrapping around.
+/// Drivers can implement this so that [`ScrollY`] can scroll without redrawing.
+pub trait HardwareScroll: DrawTarget {
+    /// Show row `offset` of display memory at the top of the screen.
+    fn set_vertical_scroll(&mut self, offset: u32) -> Result<(), Self::Error>;
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Axis {
+    X,
+    Y,
+}
+
+impl Axis {
+    /// Convert between coordinates and coordinates where the scroll axis is X.
+    fn swap<T: Transpose>(self, v: T) -> T {
+        match self {
+            Axis::X => v,
+            Axis::Y => v.transpose(),
+        }
+    }
+}
+
+/// Wrap-around mapping. `bounds` is swapped so that scrolling is always along
+/// X.
+#[derive(Debug, Clone, Copy)]
+struct Wrap {
+    axis: Axis,
+    bounds: Rectangle,
+    offset: i32,
+}
+
+impl Wrap {
+    fn new(axis: Axis, bounds: Rectangle, offset: i32) -> Self {
+        Wrap {
+            axis,
+            bounds: axis.swap(bounds),
+            offset,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.bounds.size.width == 0
+    }
+
+    fn right(&self) -> i32 {
+        self.bounds.top_left.x + self.bounds.size.width as i32
+    }
+
+    fn wrap_x(&self, x: i32) -> i32 {
+        let left = self.bounds.top_left.x;
+        left + (x - left - self.offset).rem_euclid(self.bounds.size.width as i32)
+    }
+
+    fn map_point(&self, p: Point) -> Point {
+        let p = self.axis.swap(p);
+        self.axis.swap(Point::new(self.wrap_x(p.x), p.y))
+    }
+
+    /// Map `area` if it doesn't cross the edge.
+    fn map_contiguous(&self, area: &Rectangle) -> Option<Rectangle> {
+        let area = self.axis.swap(*area);
+        let x = self.wrap_x(area.top_left.x);
+
+        (x + area.size.width as i32 <= self.right()).then(|| {
+            self.axis
+                .swap(Rectangle::new(Point::new(x, area.top_left.y), area.size))
+        })
+    }
+
+    /// Split `area` into at most two pieces which don't cross the edge.
+    fn split(&self, area: &Rectangle) -> [Option<Rectangle>; 2] {
+        let area = self.axis.swap(*area);
+        let Size { width, height } = area.size;
+        let y = area.top_left.y;
+
+        if width >= self.bounds.size.width {
+            let full = Rectangle::new(
+                Point::new(self.bounds.top_left.x, y),
+                Size::new(self.bounds.size.width, height),
+            );
+            return [Some(self.axis.swap(full)), None];
+        }
+
+        let x = self.wrap_x(area.top_left.x);
+        let first = width.min((self.right() - x) as u32);
+        let rest = width - first;
+
+        [
+            Some(Rectangle::new(Point::new(x, y), Size::new(first, height))),
+            (rest > 0).then(|| {
+                Rectangle::new(
+                    Point::new(self.bounds.top_left.x, y),
+                    Size::new(rest, height),
+                )
+            }),
+        ]
+        .map(|r| r.map(|r| self.axis.swap(r)))
+    }
+}
+
+macro_rules! scroll {
+    ($($(#[$attr:meta])* $name:ident : $axis:ident;)*) => {
+        $(
+            $(#[$attr])*
+            pub struct $name<D> {
+                target: D,
+                offset: i32,
+                hw_offset: i32,
+            }
+
+            impl<D> $name<D> {
+                /// Apply a scrolling transformation to a display.
+                pub fn new(target: D) -> Self {
+                    $name {
+                        target,
+                        offset: 0,
+                        hw_offset: 0,
+                    }
+                }
+
+                /// Return the current scroll offset.
+                pub fn offset(&self) -> i32 {
+                    self.offset
+                }
+
+                /// Set the scroll offset.
+                pub fn set_offset(&mut self, offset: i32) {
+                    self.offset = offset;
+                }
+            }
+
+            impl<D: Dimensions> $name<D> {
+                fn wrap(&self) -> Wrap {
+                    Wrap::new(
+                        Axis::$axis,
+                        self.target.bounding_box(),
+                        self.offset - self.hw_offset,
+                    )
+                }
+            }
+
+            impl_wrapper!($name<D>);
+
+            impl<D: Dimensions> Dimensions for $name<D> {
+                fn bounding_box(&self) -> Rectangle {
+                    self.target.bounding_box()
+                }
+            }
+
+            impl<D: DrawTarget> DrawTarget for $name<D> {
+                type Color = D::Color;
+                type Error = D::Error;
+
+                fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+                where
+                    I: IntoIterator<Item = Pixel<Self::Color>>,
+                {
+                    let wrap = self.wrap();
+                    if wrap.is_empty() {
+                        return Ok(());
+                    }
+
+                    self.target.draw_iter(
+                        pixels
+                            .into_iter()
+                            .map(|Pixel(loc, col)| Pixel(wrap.map_point(loc), col)),
+                    )
+                }
+
+                fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+                where
+                    I: IntoIterator<Item = Self::Color>,
+                {
+                    let wrap = self.wrap();
+                    if wrap.is_empty() {
+                        return Ok(());
+                    }
+
+                    match wrap.map_contiguous(area) {
+                        Some(area) => self.target.fill_contiguous(&area, colors),
+                        None => self.draw_iter(
+                            crate::util::points(area)
+                                .zip(colors)
+                                .map(|(loc, col)| Pixel(loc, col)),
+                        ),
+                    }
+                }
+
+                fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+                    let wrap = self.wrap();
+                    if wrap.is_empty() {
+                        return Ok(());
+                    }
+
+                    for area in wrap.split(area).into_iter().flatten() {
+                        self.target.fill_solid(&area, color)?;
+                    }
+                    Ok(())
+                }
+
+                fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+                    self.target.clear(color)
+                }
+            }
+        )*
+    };
+}
+
+scroll! {
+    /// Scroll horizontally with wrap-around.
+    ///
+    /// Drawing is shifted left by the scroll offset, and anything which goes
+    /// past one edge of the display re-enters on the other side. Increasing the
+    /// offset by one each frame gives a marquee effect.
+    ScrollX: X;
+    /// Scroll vertically with wrap-around.
+    ///
+    /// Drawing is shifted up by the scroll offset, and anything which goes past
+    /// the top or bottom of the display re-enters on the other side.
+    ScrollY: Y;
+}
+
+impl<D: HardwareScroll> ScrollY<D> {
+    /// Set the scroll offset using the controller's hardware scrolling.
+    ///
+    /// Nothing needs to be redrawn, as the controller moves the existing
+    /// contents of the display. Drawing is remapped to allow for the hardware
+    /// offset, so [`set_offset`](Self::set_offset) may still be used, but then
+    /// the display needs to be redrawn as usual.
+    pub fn scroll_to(&mut self, offset: i32) -> Result<(), D::Error> {
+        let height = self.target.bounding_box().size.height as i32;
+        if height == 0 {
+            return Ok(());
+        }
+
+        let hw_offset = offset.rem_euclid(height);
+        self.target.set_vertical_scroll(hw_offset as u32)?;
+        self.offset = offset;
+        self.hw_offset = hw_offset;
+        Ok(())
+    }
+}
@@ -0,0 +1,146 @@
+use super::*;
+use crate::tests::rect;
+use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+#[test]
+fn scroll_x() {
+    let mut disp = ScrollX::new(MockDisplay::<BinaryColor>::new());
+    disp.set_offset(3);
+    assert_eq!(disp.offset(), 3);
+
+    disp.draw_iter([
+        Pixel(Point::new(3, 0), BinaryColor::On),
+        Pixel(Point::new(0, 0), BinaryColor::On),
+        Pixel(Point::new(66, 1), BinaryColor::On),
+    ])
+    .unwrap();
+    assert_eq!(disp.as_ref().affected_area(), rect(0, 0, 64, 2));
+    assert_eq!(
+        disp.as_ref().get_pixel(Point::new(61, 0)),
+        Some(BinaryColor::On)
+    );
+    assert_eq!(
+        disp.as_ref().get_pixel(Point::new(63, 1)),
+        Some(BinaryColor::On)
+    );
+}
+
+#[test]
+fn scroll_x_fill_solid() {
+    let mut disp = ScrollX::new(MockDisplay::<BinaryColor>::new());
+    disp.set_offset(-62);
+
+    // Crosses the right edge, so it's split in two
+    disp.fill_solid(&rect(0, 1, 4, 1), BinaryColor::On).unwrap();
+    assert_eq!(disp.as_ref().affected_area(), rect(0, 1, 64, 1));
+    assert_eq!(
+        disp.as_ref().get_pixel(Point::new(62, 1)),
+        Some(BinaryColor::On)
+    );
+    assert_eq!(
+        disp.as_ref().get_pixel(Point::new(1, 1)),
+        Some(BinaryColor::On)
+    );
+    assert_eq!(disp.as_ref().get_pixel(Point::new(2, 1)), None);
+
+    // Fills the whole width regardless of offset
+    let mut disp = ScrollX::new(MockDisplay::<BinaryColor>::new());
+    disp.set_offset(5);
+    disp.fill_solid(&rect(-10, 0, 100, 2), BinaryColor::On)
+        .unwrap();
+    assert_eq!(disp.as_ref().affected_area(), rect(0, 0, 64, 2));
+}
+
+#[test]
+fn scroll_y_fill_contiguous() {
+    use BinaryColor::{Off, On};
+
+    let mut disp = ScrollY::new(MockDisplay::<BinaryColor>::new());
+    disp.set_offset(1);
+
+    disp.fill_contiguous(&rect(1, 0, 2, 3), [On, Off, Off, On, On, On])
+        .unwrap();
+    let disp = disp.into_inner();
+    assert_eq!(disp.affected_area(), rect(1, 0, 2, 64));
+    assert_eq!(disp.get_pixel(Point::new(1, 63)), Some(On));
+    assert_eq!(disp.get_pixel(Point::new(2, 63)), Some(Off));
+    assert_eq!(disp.get_pixel(Point::new(1, 0)), Some(Off));
+    assert_eq!(disp.get_pixel(Point::new(2, 0)), Some(On));
+    assert_eq!(disp.get_pixel(Point::new(1, 1)), Some(On));
+    assert_eq!(disp.get_pixel(Point::new(2, 1)), Some(On));
+}
+
+/// Emulates a controller with a vertical scroll start line.
+struct Scrolling {
+    ram: MockDisplay<BinaryColor>,
+    start: u32,
+}
+
+impl Scrolling {
+    /// What's visible on the screen at a given position.
+    fn screen(&self, p: Point) -> Option<BinaryColor> {
+        let y = (p.y as u32 + self.start) % 64;
+        self.ram.get_pixel(Point::new(p.x, y as i32))
+    }
+}
+
+impl Dimensions for Scrolling {
+    fn bounding_box(&self) -> Rectangle {
+        self.ram.bounding_box()
+    }
+}
+
+impl DrawTarget for Scrolling {
+    type Color = BinaryColor;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.ram.draw_iter(pixels)
+    }
+}
+
+impl HardwareScroll for Scrolling {
+    fn set_vertical_scroll(&mut self, offset: u32) -> Result<(), Self::Error> {
+        self.start = offset;
+        Ok(())
+    }
+}
+
+#[test]
+fn hardware_scroll() {
+    let mut disp = ScrollY::new(Scrolling {
+        ram: MockDisplay::new(),
+        start: 0,
+    });
+    disp.draw_iter([Pixel(Point::new(0, 10), BinaryColor::On)])
+        .unwrap();
+
+    // The controller scrolls without any redrawing
+    disp.scroll_to(-4).unwrap();
+    assert_eq!(disp.as_ref().start, 60);
+    assert_eq!(disp.as_ref().ram.affected_area(), rect(0, 10, 1, 1));
+    assert_eq!(
+        disp.as_ref().screen(Point::new(0, 14)),
+        Some(BinaryColor::On)
+    );
+
+    // New drawing appears in the right place on the screen
+    disp.draw_iter([Pixel(Point::new(1, 0), BinaryColor::On)])
+        .unwrap();
+    assert_eq!(
+        disp.as_ref().screen(Point::new(1, 4)),
+        Some(BinaryColor::On)
+    );
+
+    // So does drawing after a software-only scroll
+    disp.set_offset(-5);
+    disp.draw_iter([Pixel(Point::new(2, 0), BinaryColor::On)])
+        .unwrap();
+    assert_eq!(
+        disp.as_ref().screen(Point::new(2, 5)),
+        Some(BinaryColor::On)
+    );
+}
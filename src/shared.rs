@@ -0,0 +1,112 @@
+//! Share a display between the main loop and interrupt handlers.
+
+use core::cell::RefCell;
+use critical_section::Mutex;
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+
+#[cfg(test)]
+mod tests;
+
+/// A display which can be drawn to from both thread and interrupt context.
+///
+/// The display is held in a [`critical_section::Mutex`], and every draw call
+/// made through a `&SharedDisplay` runs in its own critical section, so calls
+/// from different contexts are serialized but may interleave between calls.
+/// [`DrawTarget`] is implemented for `&SharedDisplay<D>`, so a shared
+/// reference (for example to a `static`) can be passed to any drawing code.
+///
+/// The display can either be supplied up front with [`new`](Self::new), or the
+/// `SharedDisplay` can be created [`empty`](Self::empty) in a `static` and the
+/// display installed later with [`init`](Self::init). Drawing to an empty
+/// `SharedDisplay` is silently discarded, and it reports a zero-sized bounding
+/// box.
+pub struct SharedDisplay<D> {
+    target: Mutex<RefCell<Option<D>>>,
+}
+
+impl<D> SharedDisplay<D> {
+    /// Share a display implementing [`DrawTarget`].
+    pub const fn new(target: D) -> Self {
+        SharedDisplay {
+            target: Mutex::new(RefCell::new(Some(target))),
+        }
+    }
+
+    /// Create a `SharedDisplay` with no display installed yet.
+    ///
+    /// This is `const` so it can be used to initialize a `static`.
+    pub const fn empty() -> Self {
+        SharedDisplay {
+            target: Mutex::new(RefCell::new(None)),
+        }
+    }
+
+    /// Install a display, returning the previous one if there was one.
+    pub fn init(&self, target: D) -> Option<D> {
+        critical_section::with(|cs| self.target.borrow(cs).replace(Some(target)))
+    }
+
+    /// Remove the display, leaving the `SharedDisplay` empty.
+    pub fn take(&self) -> Option<D> {
+        critical_section::with(|cs| self.target.borrow(cs).take())
+    }
+
+    /// Recover the inner display instance.
+    pub fn into_inner(self) -> Option<D> {
+        self.target.into_inner().into_inner()
+    }
+
+    /// Run `f` with exclusive access to the display inside a critical section.
+    ///
+    /// This can be used to call the display's inherent functions, or to issue
+    /// several draw calls atomically. Returns `None` if no display is installed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `f` tries to draw through this `SharedDisplay` itself.
+    pub fn lock<R>(&self, f: impl FnOnce(&mut D) -> R) -> Option<R> {
+        critical_section::with(|cs| self.target.borrow(cs).borrow_mut().as_mut().map(f))
+    }
+}
+
+impl<D: Dimensions> Dimensions for &SharedDisplay<D> {
+    fn bounding_box(&self) -> Rectangle {
+        critical_section::with(|cs| {
+            self.target
+                .borrow(cs)
+                .borrow()
+                .as_ref()
+                .map_or(Rectangle::zero(), Dimensions::bounding_box)
+        })
+    }
+}
+
+impl<D: DrawTarget> DrawTarget for &SharedDisplay<D> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.lock(|target| target.draw_iter(pixels))
+            .unwrap_or(Ok(()))
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        self.lock(|target| target.fill_contiguous(area, colors))
+            .unwrap_or(Ok(()))
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.lock(|target| target.fill_solid(area, color))
+            .unwrap_or(Ok(()))
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.lock(|target| target.clear(color)).unwrap_or(Ok(()))
+    }
+}
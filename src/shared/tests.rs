@@ -0,0 +1,28 @@
+use super::*;
+use crate::Rotate90;
+use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+#[test]
+fn shared_static() {
+    static DISPLAY: SharedDisplay<Rotate90<MockDisplay<BinaryColor>>> = SharedDisplay::empty();
+
+    let mut disp = &DISPLAY;
+    assert_eq!(disp.bounding_box(), Rectangle::zero());
+    disp.fill_solid(
+        &Rectangle::new(Point::zero(), Size::new(4, 4)),
+        BinaryColor::On,
+    )
+    .expect("fill failed");
+
+    assert!(DISPLAY.init(Rotate90::new(MockDisplay::new())).is_none());
+    assert_eq!(disp.bounding_box().size, Size::new(64, 64));
+
+    disp.draw_iter([Pixel(Point::new(0, 0), BinaryColor::On)])
+        .expect("draw failed");
+
+    let area = DISPLAY.lock(|d| d.as_ref().affected_area()).unwrap();
+    assert_eq!(area, Rectangle::new(Point::new(63, 0), Size::new(1, 1)));
+
+    assert!(DISPLAY.take().is_some());
+    assert!(DISPLAY.lock(|_| ()).is_none());
+}
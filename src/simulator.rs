@@ -0,0 +1,104 @@
+//! Preview transforms in the embedded-graphics simulator.
+
+use crate::{geometry, util, Orientation, Reorient};
+use embedded_graphics_core::{
+    pixelcolor::{BinaryColor, Rgb888},
+    prelude::*,
+};
+use embedded_graphics_simulator::{
+    sdl2::Keycode, OutputSettings, SimulatorDisplay, SimulatorEvent, Window,
+};
+use std::{format, string::String, thread, time::Duration};
+
+/// Preview drawing through each orientation in a simulator window.
+///
+/// The drawing function is called each frame with a display of the logical
+/// size for the current orientation, and the result is shown on a simulated
+/// display of the physical size through [`Reorient`]. The window title shows
+/// the current orientation, and pressing space cycles through all eight in the
+/// order of [`Orientation::ALL`], so they can be checked without recompiling.
+///
+/// This is available with the `simulator` feature.
+pub struct Preview {
+    title: String,
+    size: Size,
+    settings: OutputSettings,
+    orientation: Orientation,
+}
+
+impl Preview {
+    /// Preview drawing on a display of the physical `size`.
+    pub fn new(title: &str, size: Size, settings: OutputSettings) -> Self {
+        Preview {
+            title: title.into(),
+            size,
+            settings,
+            orientation: Orientation::IDENTITY,
+        }
+    }
+
+    /// Set the initial orientation.
+    pub fn with_orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Return the current orientation.
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    fn window(&self) -> Window {
+        Window::new(
+            &format!("{} - {}", self.title, self.orientation),
+            &self.settings,
+        )
+    }
+
+    /// Draw `canvas` onto a new physical display in the current orientation.
+    fn render<C>(&self, canvas: &SimulatorDisplay<C>) -> SimulatorDisplay<C>
+    where
+        C: PixelColor + From<BinaryColor>,
+    {
+        let pixels = util::points(&canvas.bounding_box()).map(|p| Pixel(p, canvas.get_pixel(p)));
+        let mut target = Reorient::new(SimulatorDisplay::new(self.size), self.orientation);
+        target.draw_iter(pixels).unwrap();
+        target.into_inner()
+    }
+
+    /// Open the window and redraw with `draw` each frame until it's closed.
+    pub fn run<C, F>(mut self, mut draw: F)
+    where
+        C: PixelColor + From<BinaryColor> + Into<Rgb888>,
+        F: FnMut(&mut SimulatorDisplay<C>),
+    {
+        let mut window = self.window();
+
+        loop {
+            let mut canvas =
+                SimulatorDisplay::new(geometry::transform_size(self.orientation, self.size));
+            draw(&mut canvas);
+            window.update(&self.render(&canvas));
+
+            let mut next = false;
+            for event in window.events() {
+                match event {
+                    SimulatorEvent::Quit => return,
+                    SimulatorEvent::KeyDown {
+                        keycode: Keycode::Space,
+                        repeat: false,
+                        ..
+                    } => next = true,
+                    _ => (),
+                }
+            }
+
+            if next {
+                // The title can't be changed, so reopen the window.
+                self.orientation = self.orientation.next();
+                window = self.window();
+            }
+            thread::sleep(Duration::from_millis(20));
+        }
+    }
+}
@@ -0,0 +1,198 @@
+//! Memory-mapped framebuffers with padded rows.
+
+use crate::{geometry, util, Orientation};
+use core::{convert::Infallible, marker::PhantomData};
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+
+#[cfg(test)]
+mod tests;
+
+/// A framebuffer in memory, drawn to in any orientation.
+///
+/// This is the usual layout of the framebuffers scanned out by parallel RGB
+/// controllers such as LTDC: each physical row is `stride` bytes from the
+/// last, which may be more than the row's pixels need for alignment, and each
+/// pixel is `bytes_per_pixel` bytes written by `pack`, as for a
+/// [`ScanlineEmitter`](crate::ScanlineEmitter). Logical points are mapped by
+/// the [`Orientation`] straight to their offset in the buffer, rather than
+/// through a transform wrapping the framebuffer. Within a row of a fill,
+/// consecutive pixels are a fixed distance apart in the buffer, so the offset
+/// is only computed at the start of each row.
+///
+/// Drawing outside the display is ignored.
+pub struct StridedFramebuffer<'a, C, F> {
+    buf: &'a mut [u8],
+    size: Size,
+    stride: usize,
+    bytes_per_pixel: usize,
+    orientation: Orientation,
+    pack: F,
+    _color: PhantomData<C>,
+}
+
+impl<'a, C, F> StridedFramebuffer<'a, C, F>
+where
+    C: PixelColor,
+    F: FnMut(C, &mut [u8]),
+{
+    /// Draw to a physical framebuffer of `size` in `buf`, in `orientation`.
+    ///
+    /// # Panics
+    ///
+    /// If `bytes_per_pixel` is zero, if `stride` is too short for a row, or if
+    /// `buf` is too small for the framebuffer.
+    pub fn new(
+        buf: &'a mut [u8],
+        size: Size,
+        stride: usize,
+        bytes_per_pixel: usize,
+        orientation: Orientation,
+        pack: F,
+    ) -> Self {
+        assert!(bytes_per_pixel > 0, "pixels must have at least one byte");
+        let row = size.width as usize * bytes_per_pixel;
+        assert!(stride >= row, "stride is shorter than a row");
+        let len = match size.height as usize {
+            0 => 0,
+            height => (height - 1) * stride + row,
+        };
+        assert!(buf.len() >= len, "buffer is too small for the framebuffer");
+
+        StridedFramebuffer {
+            buf,
+            size,
+            stride,
+            bytes_per_pixel,
+            orientation,
+            pack,
+            _color: PhantomData,
+        }
+    }
+
+    /// Return the current orientation.
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    /// Change the orientation.
+    ///
+    /// This doesn't change the framebuffer's contents.
+    pub fn set_orientation(&mut self, orientation: Orientation) {
+        self.orientation = orientation;
+    }
+
+    /// Return the framebuffer's contents.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.buf
+    }
+
+    /// Recover the buffer and packing function.
+    pub fn into_inner(self) -> (&'a mut [u8], F) {
+        (self.buf, self.pack)
+    }
+
+    /// Return the offset of a logical point in the buffer, and how far the
+    /// offset moves for each step along a logical row. The point must be
+    /// within the bounding box.
+    fn offset(&self, p: Point) -> (usize, isize) {
+        let bounds = Rectangle::new(Point::zero(), self.size);
+        let at = geometry::transform_point(self.orientation, bounds, p);
+        let next = geometry::transform_point(self.orientation, bounds, p + Point::new(1, 0));
+        let step = (next.y - at.y) as isize * self.stride as isize
+            + (next.x - at.x) as isize * self.bytes_per_pixel as isize;
+        (
+            at.y as usize * self.stride + at.x as usize * self.bytes_per_pixel,
+            step,
+        )
+    }
+
+    /// Write `colors` to a run of `len` points along a logical row, starting
+    /// at `start`, all of which are within the bounding box.
+    fn write_run(&mut self, start: Point, len: usize, colors: impl Iterator<Item = C>) {
+        let (mut offset, step) = self.offset(start);
+        let bpp = self.bytes_per_pixel;
+        for (i, color) in colors.take(len).enumerate() {
+            if i > 0 {
+                offset = offset.wrapping_add_signed(step);
+            }
+            (self.pack)(color, &mut self.buf[offset..offset + bpp]);
+        }
+    }
+}
+
+impl<C, F> Dimensions for StridedFramebuffer<'_, C, F> {
+    fn bounding_box(&self) -> Rectangle {
+        Rectangle::new(
+            Point::zero(),
+            geometry::transform_size(self.orientation, self.size),
+        )
+    }
+}
+
+impl<C, F> DrawTarget for StridedFramebuffer<'_, C, F>
+where
+    C: PixelColor,
+    F: FnMut(C, &mut [u8]),
+{
+    type Color = C;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let bbox = self.bounding_box();
+        let bpp = self.bytes_per_pixel;
+
+        for Pixel(p, color) in pixels {
+            if bbox.contains(p) {
+                let (offset, _) = self.offset(p);
+                (self.pack)(color, &mut self.buf[offset..offset + bpp]);
+            }
+        }
+        Ok(())
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let clipped = area.intersection(&self.bounding_box());
+        if util::is_empty(&clipped) {
+            return Ok(());
+        }
+        if clipped != *area {
+            return self.draw_iter(
+                util::points(area)
+                    .zip(colors)
+                    .map(|(p, color)| Pixel(p, color)),
+            );
+        }
+
+        let width = area.size.width as usize;
+        let mut colors = colors.into_iter();
+        for y in area.rows() {
+            self.write_run(Point::new(area.top_left.x, y), width, colors.by_ref());
+        }
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = area.intersection(&self.bounding_box());
+        if util::is_empty(&area) {
+            return Ok(());
+        }
+
+        let width = area.size.width as usize;
+        for y in area.rows() {
+            let start = Point::new(area.top_left.x, y);
+            self.write_run(start, width, core::iter::repeat(color));
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        let area = self.bounding_box();
+        self.fill_solid(&area, color)
+    }
+}
@@ -0,0 +1,71 @@
+use super::*;
+use crate::tests::{color, rect};
+use embedded_graphics_core::pixelcolor::{raw::RawU16, Rgb565};
+
+fn pack(color: Rgb565, out: &mut [u8]) {
+    out.copy_from_slice(&RawU16::from(color).into_inner().to_be_bytes());
+}
+
+/// A 5x3 physical framebuffer of 16 bit pixels, with rows padded to 12 bytes.
+const SIZE: Size = Size::new(5, 3);
+const STRIDE: usize = 12;
+const PADDING: u8 = 0xaa;
+
+/// Return the color at a physical point, and check the padding is untouched.
+fn pixel(buf: &[u8], p: Point) -> Rgb565 {
+    for row in buf.chunks(STRIDE) {
+        assert_eq!(&row[10..], &[PADDING; 2]);
+    }
+    let i = p.y as usize * STRIDE + p.x as usize * 2;
+    RawU16::new(u16::from_be_bytes([buf[i], buf[i + 1]])).into()
+}
+
+#[test]
+fn strided() {
+    let bounds = Rectangle::new(Point::zero(), SIZE);
+
+    for o in Orientation::ALL {
+        let mut buf = [PADDING; STRIDE * 3];
+        let mut fb = StridedFramebuffer::new(&mut buf, SIZE, STRIDE, 2, o, pack);
+        let bbox = fb.bounding_box();
+        assert_eq!(bbox.size, geometry::transform_size(o, SIZE));
+
+        fb.fill_contiguous(&bbox, util::points(&bbox).map(color))
+            .unwrap();
+        let buf = fb.as_bytes();
+        for p in util::points(&bounds) {
+            let logical = geometry::inverse_transform_point(o, bounds, p);
+            assert_eq!(pixel(buf, p), color(logical), "{:?} at {:?}", o, p);
+        }
+
+        // Drawing outside the display is ignored
+        fb.fill_solid(&rect(-1, -1, 10, 2), Rgb565::WHITE).unwrap();
+        fb.draw_iter([
+            Pixel(Point::new(1, 1), Rgb565::RED),
+            Pixel(Point::new(-1, 1), Rgb565::RED),
+            Pixel(Point::new(1, 5), Rgb565::RED),
+        ])
+        .unwrap();
+        fb.fill_contiguous(&rect(-2, 2, 4, 1), [Rgb565::GREEN; 4])
+            .unwrap();
+
+        let (buf, _) = fb.into_inner();
+        for p in util::points(&bounds) {
+            let logical = geometry::inverse_transform_point(o, bounds, p);
+            let expected = match logical {
+                Point { x: 1, y: 1 } => Rgb565::RED,
+                Point { y: 0, .. } => Rgb565::WHITE,
+                Point { x: 0..=1, y: 2 } => Rgb565::GREEN,
+                _ => color(logical),
+            };
+            assert_eq!(pixel(buf, p), expected, "{:?} at {:?}", o, p);
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "stride")]
+fn short_stride() {
+    let mut buf = [0; 36];
+    StridedFramebuffer::new(&mut buf, SIZE, 8, 2, Orientation::IDENTITY, pack);
+}
@@ -0,0 +1,280 @@
+//! Check that transforms behave consistently.
+
+use crate::{util, Rotation};
+use core::fmt::Debug;
+use embedded_graphics::mock_display::{ColorMapping, MockDisplay};
+use embedded_graphics_core::{pixelcolor::Rgb888, prelude::*, primitives::Rectangle};
+
+#[cfg(test)]
+mod tests;
+
+/// Blue channel value marking a color as the tag of a logical point.
+const TAG: u8 = 0xff;
+
+/// The color used to identify where a logical point is drawn.
+fn tag(p: Point) -> Rgb888 {
+    Rgb888::new(p.x as u8, p.y as u8, TAG)
+}
+
+fn untag(color: Rgb888) -> Option<Point> {
+    (color.b() == TAG).then(|| Point::new(color.r().into(), color.g().into()))
+}
+
+/// A distinct color for each pixel of a fill.
+fn index_color(i: usize) -> Rgb888 {
+    Rgb888::new(i as u8, (i >> 8) as u8, 0)
+}
+
+/// Check the properties of a transform.
+///
+/// The tester is given a function which wraps a [`MockDisplay`] in the
+/// transform being tested, and a function which recovers the `MockDisplay`
+/// again. Each check draws to a fresh display, and panics with a description of
+/// the problem if it fails, so they can be used directly in tests, including to
+/// check third-party transforms. `MockDisplay` also panics if a transform draws
+/// a pixel twice or outside the display.
+///
+/// [`assert_valid`](Self::assert_valid) checks the properties every transform
+/// which doesn't clip should have, while
+/// [`assert_identity`](Self::assert_identity) and
+/// [`assert_equivalent`](Self::assert_equivalent) check algebraic properties of
+/// combinations of transforms, such as that flips are their own inverse.
+///
+/// The logical coordinates must fit in a `u8`, which is always the case for
+/// transforms which don't enlarge the display.
+///
+/// This is available with the `test-util` feature.
+pub struct TransformTester<W, U> {
+    wrap: W,
+    unwrap: U,
+}
+
+impl<T, W, U> TransformTester<W, U>
+where
+    T: DrawTarget<Color = Rgb888>,
+    T::Error: Debug,
+    W: FnMut(MockDisplay<Rgb888>) -> T,
+    U: FnMut(T) -> MockDisplay<Rgb888>,
+{
+    /// Test the transform applied by `wrap`, which is undone by `unwrap`.
+    pub fn new(wrap: W, unwrap: U) -> Self {
+        TransformTester { wrap, unwrap }
+    }
+
+    fn draw(&mut self, f: impl FnOnce(&mut T) -> Result<(), T::Error>) -> MockDisplay<Rgb888> {
+        let mut target = (self.wrap)(MockDisplay::new());
+        f(&mut target).expect("drawing failed");
+        (self.unwrap)(target)
+    }
+
+    /// Draw every point in the bounding box in its tag color.
+    fn draw_tagged(&mut self) -> MockDisplay<Rgb888> {
+        self.draw(|target| {
+            let bbox = target.bounding_box();
+            target.draw_iter(util::points(&bbox).map(|p| Pixel(p, tag(p))))
+        })
+    }
+
+    /// Return the transform's bounding box.
+    pub fn bounding_box(&mut self) -> Rectangle {
+        let mut bbox = Rectangle::zero();
+        self.draw(|target| {
+            bbox = target.bounding_box();
+            Ok(())
+        });
+        bbox
+    }
+
+    /// Return where a logical point is drawn on the display, if anywhere.
+    pub fn map_point(&mut self, point: Point) -> Option<Point> {
+        let disp = self.draw(|target| target.draw_iter([Pixel(point, Rgb888::WHITE)]));
+        util::points(&disp.bounding_box()).find(|&p| disp.get_pixel(p).is_some())
+    }
+
+    /// Return the logical point which is drawn at a point on the display, if
+    /// any.
+    pub fn unmap_point(&mut self, point: Point) -> Option<Point> {
+        self.draw_tagged().get_pixel(point).and_then(untag)
+    }
+
+    /// Assert that every point in the bounding box is drawn to its own pixel on
+    /// the display.
+    ///
+    /// This means that [`map_point`](Self::map_point) and
+    /// [`unmap_point`](Self::unmap_point) are inverses of each other.
+    pub fn assert_bounding_box(&mut self) {
+        let bbox = self.bounding_box();
+        let disp = self.draw_tagged();
+
+        let mut count = 0;
+        for p in util::points(&disp.bounding_box()) {
+            if let Some(color) = disp.get_pixel(p) {
+                let logical = untag(color).expect("unexpected color drawn");
+                assert!(
+                    bbox.contains(logical),
+                    "{:?} drawn at {:?} is outside the bounding box {:?}",
+                    logical,
+                    p,
+                    bbox
+                );
+                count += 1;
+            }
+        }
+        assert_eq!(
+            count,
+            bbox.size.width * bbox.size.height,
+            "not every point in the bounding box {:?} was drawn",
+            bbox
+        );
+    }
+
+    /// Assert that `fill_contiguous`, `fill_solid` and `clear` draw the same
+    /// pixels as `draw_iter`, for a selection of areas within the bounding box.
+    pub fn assert_fills(&mut self) {
+        let bbox = self.bounding_box();
+        let Size { width, height } = bbox.size;
+        let corner = |x, y| Rectangle::new(bbox.top_left + Point::new(x, y), Size::new(1, 1));
+        let (right, bottom) = (width as i32 - 1, height as i32 - 1);
+
+        let areas = [
+            bbox,
+            corner(0, 0),
+            corner(right, 0),
+            corner(0, bottom),
+            corner(right, bottom),
+            Rectangle::new(bbox.top_left + Point::new(1, 2), bbox.size / 2),
+            Rectangle::new(bbox.top_left + Point::new(0, bottom), Size::new(width, 1)),
+            Rectangle::new(bbox.top_left + Point::new(right, 0), Size::new(1, height)),
+        ];
+
+        for area in areas.iter().map(|area| area.intersection(&bbox)) {
+            let expected = self.draw(|target| {
+                target.draw_iter(
+                    util::points(&area)
+                        .enumerate()
+                        .map(|(i, p)| Pixel(p, index_color(i))),
+                )
+            });
+            let count = (area.size.width * area.size.height) as usize;
+            self.draw(|target| target.fill_contiguous(&area, (0..count).map(index_color)))
+                .assert_eq_with_message(&expected, |f| write!(f, "fill_contiguous({:?})", area));
+
+            let expected = self.draw(|target| {
+                target.draw_iter(util::points(&area).map(|p| Pixel(p, Rgb888::WHITE)))
+            });
+            self.draw(|target| target.fill_solid(&area, Rgb888::WHITE))
+                .assert_eq_with_message(&expected, |f| write!(f, "fill_solid({:?})", area));
+        }
+
+        let expected = self
+            .draw(|target| target.draw_iter(util::points(&bbox).map(|p| Pixel(p, Rgb888::WHITE))));
+        self.draw(|target| target.clear(Rgb888::WHITE))
+            .assert_eq_with_message(&expected, |f| write!(f, "clear"));
+    }
+
+    /// Assert the properties every transform which doesn't clip should have:
+    /// [`assert_bounding_box`](Self::assert_bounding_box) and
+    /// [`assert_fills`](Self::assert_fills).
+    pub fn assert_valid(&mut self) {
+        self.assert_bounding_box();
+        self.assert_fills();
+    }
+
+    /// Assert that the transform leaves every point in its bounding box
+    /// unchanged.
+    ///
+    /// For example, applying a flip twice, or four 90 degree rotations, should
+    /// be an identity.
+    pub fn assert_identity(&mut self) {
+        let bbox = self.bounding_box();
+        let mut expected = MockDisplay::new();
+        expected
+            .draw_iter(util::points(&bbox).map(|p| Pixel(p, tag(p))))
+            .unwrap();
+
+        self.draw_tagged()
+            .assert_eq_with_message(&expected, |f| write!(f, "not an identity"));
+    }
+
+    /// Assert that this transform and `other` have the same bounding box and
+    /// draw every point in it in the same place.
+    pub fn assert_equivalent<T2, W2, U2>(&mut self, other: &mut TransformTester<W2, U2>)
+    where
+        T2: DrawTarget<Color = Rgb888>,
+        T2::Error: Debug,
+        W2: FnMut(MockDisplay<Rgb888>) -> T2,
+        U2: FnMut(T2) -> MockDisplay<Rgb888>,
+    {
+        assert_eq!(
+            self.bounding_box(),
+            other.bounding_box(),
+            "bounding boxes differ"
+        );
+        self.draw_tagged()
+            .assert_eq_with_message(&other.draw_tagged(), |f| {
+                write!(f, "transforms not equivalent")
+            });
+    }
+}
+
+/// The logical size of a display of `size` rotated by `rotation`.
+fn rotated_size(rotation: Rotation, size: Size) -> Size {
+    match rotation {
+        Rotation::Rotate0 | Rotation::Rotate180 => size,
+        Rotation::Rotate90 | Rotation::Rotate270 => Size::new(size.height, size.width),
+    }
+}
+
+/// Return the contents of a [`MockDisplay`] drawn through a rotation in
+/// logical coordinates, as if it had been drawn to without the rotation.
+///
+/// This undoes a [`Rotate`](crate::Rotate) with the same `rotation`, or the
+/// equivalent fixed rotation such as [`Rotate90`](crate::Rotate90), wrapping
+/// the display directly, so that the result can be checked with the usual
+/// `MockDisplay` assertions.
+///
+/// This is available with the `test-util` feature.
+pub fn unrotate<C: PixelColor>(display: &MockDisplay<C>, rotation: Rotation) -> MockDisplay<C> {
+    let bbox = display.bounding_box();
+    let size = rotated_size(rotation, bbox.size);
+
+    let mut logical = MockDisplay::new();
+    for p in util::points(&bbox) {
+        if let Some(color) = display.get_pixel(p) {
+            logical.set_pixel(util::rotate_point(rotation.inverse(), size, p), Some(color));
+        }
+    }
+    logical
+}
+
+/// Return the area of a [`MockDisplay`] drawn through a rotation which has
+/// been drawn to, in logical coordinates.
+///
+/// This is the display's `affected_area` mapped back through `rotation`, as
+/// for [`unrotate`].
+///
+/// This is available with the `test-util` feature.
+pub fn affected_area_rotated<C: PixelColor>(
+    display: &MockDisplay<C>,
+    rotation: Rotation,
+) -> Rectangle {
+    let size = rotated_size(rotation, display.bounding_box().size);
+    util::rotate_rect(rotation.inverse(), size, &display.affected_area())
+}
+
+/// Assert that a [`MockDisplay`] drawn through a rotation matches a pattern in
+/// logical coordinates.
+///
+/// This allows the same pattern to be checked for every rotation, rather than
+/// rotating it by hand for each. The display is mapped back through
+/// `rotation` as for [`unrotate`].
+///
+/// This is available with the `test-util` feature.
+#[track_caller]
+pub fn assert_pattern_rotated<C>(display: &MockDisplay<C>, rotation: Rotation, pattern: &[&str])
+where
+    C: PixelColor + ColorMapping,
+{
+    unrotate(display, rotation)
+        .assert_pattern_with_message(pattern, |f| write!(f, "{:?}", rotation));
+}
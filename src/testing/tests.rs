@@ -0,0 +1,121 @@
+use super::*;
+use crate::{
+    FlipX, FlipY, Rotate, Rotate0, Rotate180, Rotate270, Rotate90, Rotation, Transpose, WithSize,
+};
+use embedded_graphics::pixelcolor::BinaryColor;
+
+/// Test a transform of a display which isn't square.
+macro_rules! tester {
+    ($($xform:ident)*) => {
+        TransformTester::new(
+            |disp| tester!(@wrap WithSize::new(disp, Size::new(40, 24)), $($xform)*),
+            |target| tester!(@unwrap target, $($xform)*).into_inner(),
+        )
+    };
+    (@wrap $inner:expr, ) => { $inner };
+    (@wrap $inner:expr, $xform:ident $($rest:ident)*) => {
+        $xform::new(tester!(@wrap $inner, $($rest)*))
+    };
+    (@unwrap $outer:expr, ) => { $outer };
+    (@unwrap $outer:expr, $xform:ident $($rest:ident)*) => {
+        tester!(@unwrap $outer.into_inner(), $($rest)*)
+    };
+}
+
+#[test]
+fn valid() {
+    tester!(Rotate0).assert_valid();
+    tester!(Rotate90).assert_valid();
+    tester!(Rotate180).assert_valid();
+    tester!(Rotate270).assert_valid();
+    tester!(Transpose).assert_valid();
+    tester!(FlipX).assert_valid();
+    tester!(FlipY).assert_valid();
+    tester!(FlipX Rotate90).assert_valid();
+    tester!(FlipY Rotate90).assert_valid();
+}
+
+#[test]
+fn identities() {
+    tester!(FlipX FlipX).assert_identity();
+    tester!(FlipY FlipY).assert_identity();
+    tester!(Transpose Transpose).assert_identity();
+    tester!(Rotate90 Rotate90 Rotate90 Rotate90).assert_identity();
+    tester!(Rotate90 Rotate270).assert_identity();
+    tester!(Rotate180 Rotate180).assert_identity();
+}
+
+#[test]
+fn equivalences() {
+    tester!(Rotate90 Rotate90).assert_equivalent(&mut tester!(Rotate180));
+    tester!(FlipX FlipY).assert_equivalent(&mut tester!(Rotate180));
+    tester!(Rotate180 Rotate90).assert_equivalent(&mut tester!(Rotate270));
+
+    let mut rotate = TransformTester::new(
+        |disp| Rotate::new(Rotation::Rotate270, WithSize::new(disp, Size::new(40, 24))),
+        |target| target.into_inner().into_inner(),
+    );
+    rotate.assert_equivalent(&mut tester!(Rotate270));
+}
+
+#[test]
+fn map_points() {
+    let mut tester = tester!(Rotate90);
+    assert_eq!(tester.bounding_box().size, Size::new(24, 40));
+    assert_eq!(tester.map_point(Point::new(0, 0)), Some(Point::new(39, 0)));
+    assert_eq!(
+        tester.unmap_point(Point::new(39, 0)),
+        Some(Point::new(0, 0))
+    );
+    assert_eq!(tester.map_point(Point::new(24, 0)), None);
+}
+
+#[test]
+#[should_panic(expected = "not an identity")]
+fn not_identity() {
+    tester!(FlipX).assert_identity();
+}
+
+#[test]
+fn rotated_patterns() {
+    // An L shape, 3x2
+    let pattern = ["#  ", "###"];
+    let rotations = [
+        Rotation::Rotate0,
+        Rotation::Rotate90,
+        Rotation::Rotate180,
+        Rotation::Rotate270,
+    ];
+
+    for rotation in rotations {
+        let mut disp = Rotate::new(rotation, MockDisplay::new());
+        disp.draw_iter(
+            [(0, 0), (0, 1), (1, 1), (2, 1)].map(|(x, y)| Pixel(Point::new(x, y), BinaryColor::On)),
+        )
+        .unwrap();
+        let disp = disp.into_inner();
+
+        assert_pattern_rotated(&disp, rotation, &pattern);
+        assert_eq!(
+            affected_area_rotated(&disp, rotation),
+            Rectangle::new(Point::new(0, 0), Size::new(3, 2)),
+            "{:?}",
+            rotation
+        );
+    }
+
+    // Rotated by hand
+    let mut disp = MockDisplay::<BinaryColor>::new();
+    disp.draw_iter(
+        [(63, 0), (63, 1), (62, 1), (62, 2)].map(|(x, y)| Pixel(Point::new(x, y), BinaryColor::On)),
+    )
+    .unwrap();
+    assert_pattern_rotated(&disp, Rotation::Rotate90, &["## ", " ##"]);
+}
+
+#[test]
+#[should_panic(expected = "Rotate270")]
+fn rotated_pattern_mismatch() {
+    let disp = MockDisplay::<BinaryColor>::from_pattern(&["#"]);
+    assert_pattern_rotated(&disp, Rotation::Rotate270, &["#"]);
+}
@@ -1,10 +1,30 @@
 use super::*;
-use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor, primitives::Triangle};
+use embedded_graphics::{
+    mock_display::MockDisplay,
+    pixelcolor::{raw::RawU16, BinaryColor, Rgb565, Rgb888},
+    primitives::Triangle,
+};
 
 fn triangle() -> impl PointsIter {
-    let tri = Triangle::new(Point::new(0, 10), Point::new(0, 0), Point::new(20, 0));
+    Triangle::new(Point::new(0, 10), Point::new(0, 0), Point::new(20, 0))
+}
 
-    tri
+/// A rectangle with its top left at (`x`, `y`), `w` by `h`, for the tests of
+/// every module.
+pub(crate) fn rect(x: i32, y: i32, w: u32, h: u32) -> Rectangle {
+    Rectangle::new(Point::new(x, y), Size::new(w, h))
+}
+
+/// A different color for each point from (-128, -128) to (127, 127), so that
+/// misplaced pixels can be identified.
+pub(crate) fn color(p: Point) -> Rgb565 {
+    let range = -128..128;
+    assert!(
+        range.contains(&p.x) && range.contains(&p.y),
+        "no distinct color for {:?}",
+        p
+    );
+    Rgb565::from(RawU16::new((p.y as u8 as u16) << 8 | p.x as u8 as u16))
 }
 
 #[test]
@@ -295,7 +315,7 @@ fn solid_flipx() {
     assert_eq!(
         disp.as_ref().affected_area(),
         Rectangle {
-            top_left: Point::new(64 - 1 - 1 - 5, 1),
+            top_left: Point::new(64 - 1 - 5, 1),
             size: Size {
                 width: 5,
                 height: 10
@@ -323,7 +343,7 @@ fn solid_flipy() {
     assert_eq!(
         disp.as_ref().affected_area(),
         Rectangle {
-            top_left: Point::new(1, 64 - 1 - 1 - 10),
+            top_left: Point::new(1, 64 - 1 - 10),
             size: Size {
                 width: 5,
                 height: 10
@@ -351,7 +371,7 @@ fn solid_rot90() {
     assert_eq!(
         disp.as_ref().affected_area(),
         Rectangle {
-            top_left: Point::new(64 - 1 - 1 - 10, 1),
+            top_left: Point::new(64 - 1 - 10, 1),
             size: Size {
                 width: 10,
                 height: 5
@@ -379,7 +399,7 @@ fn solid_rot180() {
     assert_eq!(
         disp.as_ref().affected_area(),
         Rectangle {
-            top_left: Point::new(64 - 1 - 1 - 5, 64 - 1 - 1 - 10),
+            top_left: Point::new(64 - 1 - 5, 64 - 1 - 10),
             size: Size {
                 width: 5,
                 height: 10
@@ -407,7 +427,7 @@ fn solid_rot270() {
     assert_eq!(
         disp.as_ref().affected_area(),
         Rectangle {
-            top_left: Point::new(1, 64 - 1 - 1 - 5),
+            top_left: Point::new(1, 64 - 1 - 5),
             size: Size {
                 width: 10,
                 height: 5
@@ -438,3 +458,312 @@ fn rect_transpose() {
 
     assert_eq!(lower_right(&rx), Point::new(110, 25));
 }
+
+#[test]
+fn set_pixels() {
+    let pixels = || {
+        [
+            Pixel(Point::new(0, 0), BinaryColor::On),
+            Pixel(Point::new(5, 2), BinaryColor::Off),
+            Pixel(Point::new(20, 40), BinaryColor::On),
+        ]
+    };
+
+    macro_rules! check {
+        ($($new:expr;)*) => {
+            $(
+                let mut expected = $new;
+                expected.draw_iter(pixels()).unwrap();
+                let mut disp = $new;
+                disp.set_pixels(&mut pixels()).unwrap();
+                disp.as_ref().assert_eq(expected.as_ref());
+            )*
+        };
+    }
+
+    check! {
+        Rotate0::new(MockDisplay::new());
+        Rotate90::new(MockDisplay::new());
+        Rotate180::new(MockDisplay::new());
+        Rotate270::new(MockDisplay::new());
+        Transpose::new(MockDisplay::new());
+        FlipX::new(MockDisplay::new());
+        FlipY::new(MockDisplay::new());
+        Rotate::new(Rotation::Rotate90, MockDisplay::new());
+        Oriented::new(MockDisplay::new()).rotate270().flip_x();
+        Reorient::new(MockDisplay::new(), Orientation::ALL[6]).with_offset(Point::new(1, -2));
+    }
+
+    // The display's own size is used
+    let mut disp = Rotate90::new(WithSize::new(MockDisplay::new(), Size::new(10, 30)));
+    disp.set_pixels(&mut [Pixel(Point::new(1, 2), BinaryColor::On)])
+        .unwrap();
+    assert_eq!(
+        disp.as_ref().as_ref().affected_area().top_left,
+        Point::new(7, 1)
+    );
+}
+
+#[test]
+fn fill_contiguous_buffered() {
+    let area = Rectangle::new(Point::new(3, 5), Size::new(7, 4));
+    let colors = || (0..28).map(|i| Rgb888::new(i, 0, 0));
+
+    macro_rules! check {
+        ($($new:expr;)*) => {
+            // Each in its own frame, to keep the stack small
+            $({
+                let check = || {
+                    let mut expected = $new;
+                    expected.fill_contiguous(&area, colors()).unwrap();
+                    let mut disp = $new;
+                    disp.fill_contiguous_buffered::<1, _>(&area, colors()).unwrap();
+                    disp.as_ref().assert_eq(expected.as_ref());
+                    let mut disp = $new;
+                    disp.fill_contiguous_buffered::<5, _>(&area, colors()).unwrap();
+                    disp.as_ref().assert_eq(expected.as_ref());
+                    let mut disp = $new;
+                    disp.fill_contiguous_buffered::<16, _>(&area, colors()).unwrap();
+                    disp.as_ref().assert_eq(expected.as_ref());
+                };
+                check();
+            })*
+        };
+    }
+
+    check! {
+        Rotate0::new(MockDisplay::new());
+        Rotate90::new(MockDisplay::new());
+        Rotate180::new(MockDisplay::new());
+        Rotate270::new(MockDisplay::new());
+        Transpose::new(MockDisplay::new());
+        FlipX::new(MockDisplay::new());
+        FlipY::new(MockDisplay::new());
+        Rotate::new(Rotation::Rotate270, MockDisplay::new());
+        Oriented::new(MockDisplay::new()).rotate90().flip_x();
+        Reorient::new(MockDisplay::new(), Orientation::ALL[3]).with_offset(Point::new(-2, 1));
+    }
+
+    // Each chunk of two rows is a single fill
+    let mut disp = Rotate90::new(Instrument::new(MockDisplay::<Rgb888>::new()));
+    disp.fill_contiguous_buffered::<16, _>(&area, colors())
+        .unwrap();
+    let stats = disp.as_mut().reset();
+    assert_eq!((stats.calls, stats.fills), (2, 2));
+    assert_eq!(stats.largest_fill.size, Size::new(2, 7));
+
+    // Running out of colors stops drawing
+    disp.as_mut().as_mut().set_allow_overdraw(true);
+    disp.fill_contiguous_buffered::<16, _>(&area, colors().take(10))
+        .unwrap();
+    assert_eq!(disp.as_mut().reset().pixels, 10);
+}
+
+#[test]
+fn write_pixel() {
+    macro_rules! check {
+        ($($new:expr;)*) => {
+            $({
+                let check = || {
+                    let mut expected = $new;
+                    expected
+                        .draw_iter([
+                            Pixel(Point::new(2, 7), BinaryColor::On),
+                            Pixel(Point::new(63, 0), BinaryColor::Off),
+                        ])
+                        .unwrap();
+                    let mut disp = $new;
+                    disp.write_pixel(Point::new(2, 7), BinaryColor::On).unwrap();
+                    disp.write_pixel_unchecked(Point::new(63, 0), BinaryColor::Off)
+                        .unwrap();
+                    // Off the display
+                    disp.write_pixel(Point::new(64, 0), BinaryColor::On).unwrap();
+                    disp.write_pixel(Point::new(0, -1), BinaryColor::On).unwrap();
+                    disp.as_ref().assert_eq(expected.as_ref());
+                };
+                check();
+            })*
+        };
+    }
+
+    check! {
+        Rotate0::new(MockDisplay::new());
+        Rotate90::new(MockDisplay::new());
+        Rotate180::new(MockDisplay::new());
+        Rotate270::new(MockDisplay::new());
+        Transpose::new(MockDisplay::new());
+        FlipX::new(MockDisplay::new());
+        FlipY::new(MockDisplay::new());
+        Rotate::new(Rotation::Rotate180, MockDisplay::new());
+        Oriented::new(MockDisplay::new()).transpose().flip_y();
+        Reorient::new(MockDisplay::new(), Orientation::ALL[5]);
+    }
+}
+
+#[test]
+fn rotate_equivalent() {
+    use crate::testing::TransformTester;
+    use embedded_graphics_core::pixelcolor::Rgb888;
+
+    macro_rules! check {
+        ($($rotation:ident => $xform:ident;)*) => {
+            $({
+                let sized = |disp| WithSize::new(disp, Size::new(40, 24));
+                let mut rotate = TransformTester::new(
+                    |disp| Rotate::new(Rotation::$rotation, sized(disp)),
+                    |target: Rotate<WithSize<MockDisplay<Rgb888>>>| target.into_inner().into_inner(),
+                );
+                rotate.assert_valid();
+                rotate.assert_equivalent(&mut TransformTester::new(
+                    |disp| $xform::new(sized(disp)),
+                    |target| target.into_inner().into_inner(),
+                ));
+            })*
+        };
+    }
+
+    check! {
+        Rotate0 => Rotate0;
+        Rotate90 => Rotate90;
+        Rotate180 => Rotate180;
+        Rotate270 => Rotate270;
+    }
+}
+
+#[test]
+fn direct_rotations() {
+    use crate::testing::TransformTester;
+
+    let sized = |disp| WithSize::new(disp, Size::new(40, 24));
+
+    // Rotations are the same as transposing and flipping
+    let mut rot90 = TransformTester::new(
+        |disp| Rotate90::new(sized(disp)),
+        |target| target.into_inner().into_inner(),
+    );
+    rot90.assert_valid();
+    rot90.assert_equivalent(&mut TransformTester::new(
+        |disp| FlipY::new(Transpose::new(sized(disp))),
+        |target| target.into_inner().into_inner().into_inner(),
+    ));
+
+    let mut rot180 = TransformTester::new(
+        |disp| Rotate180::new(sized(disp)),
+        |target| target.into_inner().into_inner(),
+    );
+    rot180.assert_valid();
+    rot180.assert_equivalent(&mut TransformTester::new(
+        |disp| FlipX::new(FlipY::new(sized(disp))),
+        |target| target.into_inner().into_inner().into_inner(),
+    ));
+
+    let mut rot270 = TransformTester::new(
+        |disp| Rotate270::new(sized(disp)),
+        |target| target.into_inner().into_inner(),
+    );
+    rot270.assert_valid();
+    rot270.assert_equivalent(&mut TransformTester::new(
+        |disp| Transpose::new(FlipY::new(sized(disp))),
+        |target| target.into_inner().into_inner().into_inner(),
+    ));
+}
+
+#[test]
+fn draw_by_mut_ref() {
+    fn draw<D: DrawTarget<Color = BinaryColor>>(mut target: D)
+    where
+        D::Error: core::fmt::Debug,
+    {
+        assert_eq!(target.bounding_box().size, Size::new(64, 64));
+        target
+            .draw_iter([Pixel(Point::new(1, 0), BinaryColor::On)])
+            .unwrap();
+    }
+
+    let mut rot90 = Rotate90::new(MockDisplay::new());
+    draw(&mut rot90);
+    assert_eq!(rot90.get_pixel(Point::new(63, 1)), Some(BinaryColor::On));
+
+    let mut rotate = Rotate::new(Rotation::Rotate180, MockDisplay::new());
+    draw(&mut rotate);
+    assert_eq!(rotate.get_pixel(Point::new(62, 63)), Some(BinaryColor::On));
+
+    let mut tee = Tee::new(FlipX::new(MockDisplay::new()), MockDisplay::new());
+    draw(&mut tee);
+    assert_eq!(
+        tee.first().get_pixel(Point::new(62, 0)),
+        Some(BinaryColor::On)
+    );
+}
+
+#[test]
+fn composed() {
+    use crate::testing::TransformTester;
+
+    compose_transforms! {
+        /// A panel which is mirrored, rotated and smaller than its controller.
+        PanelFix: FlipX, Rotate90, WithSize(Size::new(40, 24))
+    }
+
+    let mut composed = TransformTester::new(PanelFix::new, PanelFix::into_inner);
+    assert_eq!(composed.bounding_box().size, Size::new(24, 40));
+    composed.assert_valid();
+    composed.assert_equivalent(&mut TransformTester::new(
+        |disp| FlipX::new(Rotate90::new(WithSize::new(disp, Size::new(40, 24)))),
+        |target| target.into_inner().into_inner().into_inner(),
+    ));
+
+    // Accessors reach the display through every transform
+    let mut disp = PanelFix::new(MockDisplay::new());
+    disp.draw_iter([Pixel(Point::new(0, 0), BinaryColor::On)])
+        .unwrap();
+    assert_eq!(disp.get_pixel(Point::new(39, 23)), Some(BinaryColor::On));
+    assert_eq!(
+        disp.as_ref().get_pixel(Point::new(39, 23)),
+        Some(BinaryColor::On)
+    );
+    disp.as_mut().set_pixel(Point::new(39, 23), None);
+    assert_eq!(disp.into_inner().affected_area(), Rectangle::zero());
+}
+
+#[test]
+fn line_fills() {
+    use crate::testing::TransformTester;
+
+    // Fills one pixel wide or high, running forwards and backwards on the
+    // display, are the same as drawing them pixel by pixel
+    for orientation in Orientation::ALL {
+        let mut tester = TransformTester::new(
+            |disp| Reorient::new(WithSize::new(disp, Size::new(40, 24)), orientation),
+            |target| target.into_inner().into_inner(),
+        );
+        tester.assert_valid();
+    }
+
+    // A column longer than the reversal buffer, which is a row running
+    // backwards on the display, so it's filled in chunks
+    let mut disp = Rotate90::new(debug::Instrument::new(MockDisplay::new()));
+    let area = Rectangle::new(Point::new(5, 0), Size::new(1, 50));
+    disp.fill_contiguous(
+        &area,
+        (0..50).map(|x| {
+            if x % 3 == 0 {
+                Rgb888::RED
+            } else {
+                Rgb888::GREEN
+            }
+        }),
+    )
+    .unwrap();
+    assert_eq!(disp.stats().fills, 2);
+    assert_eq!(disp.stats().largest_fill.size, Size::new(32, 1));
+    let disp = disp.into_inner().into_inner();
+    for y in 0..50 {
+        let expected = if y % 3 == 0 {
+            Rgb888::RED
+        } else {
+            Rgb888::GREEN
+        };
+        assert_eq!(disp.get_pixel(Point::new(63 - y, 5)), Some(expected));
+    }
+}
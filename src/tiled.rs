@@ -0,0 +1,215 @@
+//! Combine a grid of displays into one large display.
+
+use crate::util;
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+
+#[cfg(test)]
+mod tests;
+
+/// A `R`×`C` grid of displays presented as one large [`DrawTarget`].
+///
+/// Panels are laid out left to right and top to bottom in the order of the
+/// `panels` array. Each column is as wide as its panel in the first row, and
+/// each row is as high as its panel in the first column. Panels can have their
+/// own orientation by wrapping them in a transform; use [`Rotate`](crate::Rotate)
+/// if they need different orientations, since all panels must be the same type.
+///
+/// An optional bezel gap can be configured with [`with_gap`](Self::with_gap).
+/// The gap is part of the logical display, so geometry stays continuous across
+/// panel boundaries, but anything drawn into it is discarded.
+///
+/// Pixels and solid fills are routed to the panels they cover. Contiguous fills
+/// are forwarded as-is if they lie within a single panel, and otherwise fall
+/// back to routing each pixel.
+pub struct TiledGrid<D, const R: usize, const C: usize> {
+    panels: [[D; C]; R],
+    gap: Size,
+}
+
+/// Location of each row and column in logical coordinates.
+struct Layout<const R: usize, const C: usize> {
+    cols: [(i32, u32); C],
+    rows: [(i32, u32); R],
+}
+
+impl<const R: usize, const C: usize> Layout<R, C> {
+    fn area(&self, row: usize, col: usize) -> Rectangle {
+        let (x, width) = self.cols[col];
+        let (y, height) = self.rows[row];
+        Rectangle::new(Point::new(x, y), Size::new(width, height))
+    }
+
+    fn locate(&self, p: Point) -> Option<(usize, usize)> {
+        fn find(spans: &[(i32, u32)], v: i32) -> Option<usize> {
+            spans
+                .iter()
+                .position(|&(start, len)| v >= start && v < start + len as i32)
+        }
+
+        Some((find(&self.rows, p.y)?, find(&self.cols, p.x)?))
+    }
+
+    fn size(&self) -> Size {
+        fn end(spans: &[(i32, u32)]) -> u32 {
+            spans.last().map_or(0, |&(start, len)| start as u32 + len)
+        }
+
+        Size::new(end(&self.cols), end(&self.rows))
+    }
+}
+
+impl<D, const R: usize, const C: usize> TiledGrid<D, R, C> {
+    /// Combine a grid of panels into one display.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the grid has no rows or columns.
+    pub fn new(panels: [[D; C]; R]) -> Self {
+        assert!(R > 0 && C > 0, "grid must have at least one panel");
+        TiledGrid {
+            panels,
+            gap: Size::zero(),
+        }
+    }
+
+    /// Set the horizontal and vertical gap between adjacent panels, in pixels.
+    pub fn with_gap(mut self, gap: Size) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Get a reference to the panel at `row`, `col`.
+    pub fn panel(&self, row: usize, col: usize) -> &D {
+        &self.panels[row][col]
+    }
+
+    /// Get a mutable reference to the panel at `row`, `col`.
+    pub fn panel_mut(&mut self, row: usize, col: usize) -> &mut D {
+        &mut self.panels[row][col]
+    }
+
+    /// Recover the panels.
+    pub fn into_inner(self) -> [[D; C]; R] {
+        self.panels
+    }
+}
+
+impl<D: Dimensions, const R: usize, const C: usize> TiledGrid<D, R, C> {
+    fn layout(&self) -> Layout<R, C> {
+        let mut layout = Layout {
+            cols: [(0, 0); C],
+            rows: [(0, 0); R],
+        };
+
+        let mut x = 0;
+        for (col, span) in layout.cols.iter_mut().enumerate() {
+            let width = self.panels[0][col].bounding_box().size.width;
+            *span = (x, width);
+            x += (width + self.gap.width) as i32;
+        }
+
+        let mut y = 0;
+        for (row, span) in layout.rows.iter_mut().enumerate() {
+            let height = self.panels[row][0].bounding_box().size.height;
+            *span = (y, height);
+            y += (height + self.gap.height) as i32;
+        }
+
+        layout
+    }
+
+    /// Offset from logical coordinates to the coordinates of a panel.
+    fn panel_offset(&self, layout: &Layout<R, C>, row: usize, col: usize) -> Point {
+        self.panels[row][col].bounding_box().top_left - layout.area(row, col).top_left
+    }
+}
+
+impl_by_mut_ref!([D, const R: usize, const C: usize] TiledGrid<D, R, C>);
+
+impl<D: Dimensions, const R: usize, const C: usize> Dimensions for TiledGrid<D, R, C> {
+    fn bounding_box(&self) -> Rectangle {
+        Rectangle::new(Point::zero(), self.layout().size())
+    }
+}
+
+impl<D: DrawTarget, const R: usize, const C: usize> DrawTarget for TiledGrid<D, R, C> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let layout = self.layout();
+        let mut pixels = pixels.into_iter().peekable();
+
+        while let Some(&Pixel(loc, _)) = pixels.peek() {
+            let (row, col) = match layout.locate(loc) {
+                Some(panel) => panel,
+                None => {
+                    // Outside the grid or in a gap
+                    pixels.next();
+                    continue;
+                }
+            };
+            let area = layout.area(row, col);
+            let offset = self.panel_offset(&layout, row, col);
+
+            // Forward the run of pixels which land on the same panel in one call.
+            let mut run =
+                core::iter::from_fn(|| pixels.next_if(|Pixel(loc, _)| area.contains(*loc)));
+            self.panels[row][col]
+                .draw_iter(run.by_ref().map(|Pixel(loc, col)| Pixel(loc + offset, col)))?;
+            // The panel needn't consume every pixel, so skip the rest of the run.
+            run.for_each(drop);
+        }
+
+        Ok(())
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let layout = self.layout();
+
+        let corners = area
+            .bottom_right()
+            .and_then(|br| Some((layout.locate(area.top_left)?, layout.locate(br)?)));
+        match corners {
+            Some((tl, br)) if tl == br => {
+                let (row, col) = tl;
+                let offset = self.panel_offset(&layout, row, col);
+                self.panels[row][col].fill_contiguous(&util::translate(area, offset), colors)
+            }
+            _ => self.draw_iter(
+                util::points(area)
+                    .zip(colors)
+                    .map(|(loc, col)| Pixel(loc, col)),
+            ),
+        }
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let layout = self.layout();
+
+        for row in 0..R {
+            for col in 0..C {
+                let part = area.intersection(&layout.area(row, col));
+                if !util::is_empty(&part) {
+                    let offset = self.panel_offset(&layout, row, col);
+                    self.panels[row][col].fill_solid(&util::translate(&part, offset), color)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        for panel in self.panels.iter_mut().flatten() {
+            panel.clear(color)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,108 @@
+use super::*;
+use crate::{tests::rect, Rotate180};
+use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+/// A panel which ignores the pixels it's given.
+#[derive(Default)]
+struct Ignoring {
+    calls: usize,
+}
+
+impl OriginDimensions for Ignoring {
+    fn size(&self) -> Size {
+        Size::new(4, 4)
+    }
+}
+
+impl DrawTarget for Ignoring {
+    type Color = BinaryColor;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, _pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.calls += 1;
+        Ok(())
+    }
+}
+
+fn new_grid() -> TiledGrid<MockDisplay<BinaryColor>, 2, 2> {
+    TiledGrid::new([
+        [MockDisplay::new(), MockDisplay::new()],
+        [MockDisplay::new(), MockDisplay::new()],
+    ])
+    .with_gap(Size::new(2, 4))
+}
+
+#[test]
+fn grid_layout() {
+    let mut grid = new_grid();
+
+    assert_eq!(grid.bounding_box(), rect(0, 0, 130, 132));
+
+    grid.draw_iter([
+        Pixel(Point::new(1, 1), BinaryColor::On),
+        Pixel(Point::new(2, 1), BinaryColor::On),
+        // In the gap
+        Pixel(Point::new(64, 1), BinaryColor::On),
+        Pixel(Point::new(66, 1), BinaryColor::On),
+        Pixel(Point::new(67, 69), BinaryColor::On),
+    ])
+    .unwrap();
+
+    assert_eq!(grid.panel(0, 0).affected_area(), rect(1, 1, 2, 1));
+    assert_eq!(grid.panel(0, 1).affected_area(), rect(0, 1, 1, 1));
+    assert_eq!(grid.panel(1, 0).affected_area(), Rectangle::zero());
+    assert_eq!(grid.panel(1, 1).affected_area(), rect(1, 1, 1, 1));
+}
+
+#[test]
+fn grid_unconsumed() {
+    let mut grid: TiledGrid<Ignoring, 1, 2> = TiledGrid::new(Default::default());
+
+    // Pixels a panel doesn't take are skipped, rather than offered again
+    // forever
+    grid.draw_iter(
+        [(0, 0), (1, 0), (5, 1), (6, 1), (2, 2)]
+            .into_iter()
+            .map(|(x, y)| Pixel(Point::new(x, y), BinaryColor::On)),
+    )
+    .unwrap();
+    assert_eq!(grid.panel(0, 0).calls, 2);
+    assert_eq!(grid.panel(0, 1).calls, 1);
+}
+
+#[test]
+fn grid_fills() {
+    let mut grid = new_grid();
+
+    // Spans all four panels
+    grid.fill_solid(&rect(60, 60, 10, 10), BinaryColor::On)
+        .unwrap();
+    assert_eq!(grid.panel(0, 0).affected_area(), rect(60, 60, 4, 4));
+    assert_eq!(grid.panel(0, 1).affected_area(), rect(0, 60, 4, 4));
+    assert_eq!(grid.panel(1, 0).affected_area(), rect(60, 0, 4, 2));
+    assert_eq!(grid.panel(1, 1).affected_area(), rect(0, 0, 4, 2));
+
+    let mut grid = new_grid();
+    grid.fill_contiguous(&rect(62, 0, 6, 1), [BinaryColor::On; 6])
+        .unwrap();
+    assert_eq!(grid.panel(0, 0).affected_area(), rect(62, 0, 2, 1));
+    assert_eq!(grid.panel(0, 1).affected_area(), rect(0, 0, 2, 1));
+}
+
+#[test]
+fn grid_oriented_panels() {
+    let mut grid = TiledGrid::new([[
+        Rotate180::new(MockDisplay::<BinaryColor>::new()),
+        Rotate180::new(MockDisplay::new()),
+    ]]);
+
+    grid.draw_iter([Pixel(Point::new(64, 0), BinaryColor::On)])
+        .unwrap();
+    assert_eq!(
+        grid.panel(0, 1).as_ref().affected_area(),
+        rect(63, 63, 1, 1)
+    );
+}
@@ -0,0 +1,193 @@
+//! Reorder filled areas to suit a controller's memory access pattern.
+
+use crate::util;
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+
+#[cfg(test)]
+mod tests;
+
+/// The order a [`Traverse`] sends the pixels of a filled area in.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Traversal {
+    /// Row by row, each from left to right, as fills are drawn anyway.
+    RowMajor,
+    /// Column by column, each from top to bottom.
+    ColumnMajor,
+    /// Row by row, alternating between left to right and right to left,
+    /// starting with left to right on the area's top row.
+    Boustrophedon,
+}
+
+/// Send filled areas to a display in a given traversal order.
+///
+/// Some controllers, such as memory LCDs, are much faster when they're written
+/// in a particular order. This reorders `fill_contiguous` and `fill_solid` to
+/// match: column-major fills are sent as a series of single column fills, and
+/// boustrophedon fills as alternating rows, with the right to left rows drawn
+/// pixel by pixel in that order. Coordinates are unchanged, as are pixels
+/// drawn individually.
+///
+/// Colors are reordered through an `N` pixel buffer. Column-major fills are
+/// done in bands of as many whole rows as fit in the buffer, so each column
+/// of a band is one fill; `N` should be several times the width of the areas
+/// being filled. Rows longer than `N` are reversed `N` pixels at a time.
+pub struct Traverse<D, const N: usize> {
+    target: D,
+    order: Traversal,
+}
+
+impl<D, const N: usize> Traverse<D, N> {
+    /// Send fills to `target` in `order`.
+    pub fn new(target: D, order: Traversal) -> Self {
+        Traverse { target, order }
+    }
+
+    /// Return the traversal order.
+    pub fn order(&self) -> Traversal {
+        self.order
+    }
+
+    /// Change the traversal order.
+    pub fn set_order(&mut self, order: Traversal) {
+        self.order = order;
+    }
+}
+
+impl_wrapper!(Traverse<D; const N: usize>);
+
+impl<D: DrawTarget, const N: usize> Traverse<D, N> {
+    fn fill_columns(
+        &mut self,
+        area: &Rectangle,
+        colors: impl Iterator<Item = D::Color>,
+    ) -> Result<(), D::Error> {
+        let mut colors = colors.peekable();
+        let mut buf = match colors.peek() {
+            Some(&first) if N > 0 => [first; N],
+            _ => return Ok(()),
+        };
+
+        for band in util::chunks(area, N) {
+            let len = (band.size.width * band.size.height) as usize;
+            let read = buf[..len]
+                .iter_mut()
+                .zip(&mut colors)
+                .map(|(slot, color)| *slot = color)
+                .count();
+
+            if read < len {
+                // Ran out of colors, so draw what there is.
+                return self.target.draw_iter(
+                    util::points(&band)
+                        .zip(&buf[..read])
+                        .map(|(p, &c)| Pixel(p, c)),
+                );
+            }
+
+            let width = band.size.width as usize;
+            for x in 0..width {
+                let column = Rectangle::new(
+                    band.top_left + Point::new(x as i32, 0),
+                    Size::new(1, band.size.height),
+                );
+                self.target
+                    .fill_contiguous(&column, buf[x..len].iter().step_by(width).copied())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn fill_snake(
+        &mut self,
+        area: &Rectangle,
+        colors: impl Iterator<Item = D::Color>,
+    ) -> Result<(), D::Error> {
+        let mut colors = colors.peekable();
+        let mut buf = match colors.peek() {
+            Some(&first) if N > 0 => [first; N],
+            _ => return Ok(()),
+        };
+        let width = area.size.width;
+
+        for (i, y) in area.rows().enumerate() {
+            let row = Rectangle::new(Point::new(area.top_left.x, y), Size::new(width, 1));
+            if i % 2 == 0 {
+                self.target
+                    .fill_contiguous(&row, colors.by_ref().take(width as usize))?;
+                continue;
+            }
+
+            for segment in util::chunks(&row, N) {
+                let len = segment.size.width as usize;
+                let read = buf[..len]
+                    .iter_mut()
+                    .zip(&mut colors)
+                    .map(|(slot, color)| *slot = color)
+                    .count();
+                self.target.draw_iter(
+                    (0..read)
+                        .rev()
+                        .map(|x| Pixel(segment.top_left + Point::new(x as i32, 0), buf[x])),
+                )?;
+                if read < len {
+                    return Ok(());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<D: Dimensions, const N: usize> Dimensions for Traverse<D, N> {
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+impl<D: DrawTarget, const N: usize> DrawTarget for Traverse<D, N> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.target.draw_iter(pixels)
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        match self.order {
+            Traversal::RowMajor => self.target.fill_contiguous(area, colors),
+            Traversal::ColumnMajor => self.fill_columns(area, colors.into_iter()),
+            Traversal::Boustrophedon => self.fill_snake(area, colors.into_iter()),
+        }
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        match self.order {
+            Traversal::RowMajor => self.target.fill_solid(area, color),
+            Traversal::ColumnMajor => area.columns().try_for_each(|x| {
+                let column = Rectangle::new(
+                    Point::new(x, area.top_left.y),
+                    Size::new(1, area.size.height),
+                );
+                self.target.fill_solid(&column, color)
+            }),
+            Traversal::Boustrophedon => {
+                let colors =
+                    core::iter::repeat_n(color, (area.size.width * area.size.height) as usize);
+                self.fill_snake(area, colors)
+            }
+        }
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        let area = self.bounding_box();
+        self.fill_solid(&area, color)
+    }
+}
@@ -0,0 +1,92 @@
+use super::*;
+use crate::{tests::rect, Command, Trace};
+use embedded_graphics::{mock_display::MockDisplay, pixelcolor::Rgb888};
+
+fn color(i: usize) -> Rgb888 {
+    Rgb888::new(i as u8, 0, 0)
+}
+
+#[test]
+fn same_pixels() {
+    let area = rect(2, 3, 5, 7);
+    let mut expected = MockDisplay::new();
+    expected.fill_contiguous(&area, (0..35).map(color)).unwrap();
+
+    for order in [
+        Traversal::RowMajor,
+        Traversal::ColumnMajor,
+        Traversal::Boustrophedon,
+    ] {
+        let mut disp = Traverse::<_, 12>::new(MockDisplay::new(), order);
+        disp.fill_contiguous(&area, (0..35).map(color)).unwrap();
+        disp.assert_eq_with_message(&expected, |f| write!(f, "{:?}", order));
+
+        let mut disp = Traverse::<_, 12>::new(MockDisplay::new(), order);
+        disp.fill_solid(&area, Rgb888::WHITE).unwrap();
+        assert_eq!(disp.affected_area(), area, "{:?}", order);
+
+        // Running out of colors
+        let mut disp = Traverse::<_, 12>::new(MockDisplay::<Rgb888>::new(), order);
+        disp.fill_contiguous(&area, (0..8).map(color)).unwrap();
+        assert_eq!(disp.affected_area(), rect(2, 3, 5, 2), "{:?}", order);
+    }
+}
+
+#[test]
+fn order() {
+    let mut ops = [None; 8];
+    let mut count = 0;
+    let trace = Trace::new(MockDisplay::new(), |op: &Command<_>| {
+        if let Command::FillContiguous(_) | Command::Pixel(..) = op {
+            ops[count] = Some(*op);
+            count += 1;
+        }
+    });
+
+    // Bands of two whole rows, each sent a column at a time
+    {
+        let mut disp = Traverse::<_, 6>::new(trace, Traversal::ColumnMajor);
+        disp.fill_contiguous(&rect(0, 0, 2, 3), (0..6).map(color))
+            .unwrap();
+    }
+    assert_eq!(
+        ops,
+        [
+            Some(Command::FillContiguous(rect(0, 0, 1, 3))),
+            Some(Command::FillContiguous(rect(1, 0, 1, 3))),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        ]
+    );
+
+    let mut ops = [None; 8];
+    let mut count = 0;
+    let trace = Trace::new(MockDisplay::new(), |op: &Command<_>| {
+        if let Command::FillContiguous(_) | Command::Pixel(..) = op {
+            ops[count] = Some(*op);
+            count += 1;
+        }
+    });
+    {
+        let mut disp = Traverse::<_, 6>::new(trace, Traversal::Boustrophedon);
+        disp.fill_contiguous(&rect(0, 0, 3, 2), (0..6).map(color))
+            .unwrap();
+    }
+    assert_eq!(
+        ops,
+        [
+            Some(Command::FillContiguous(rect(0, 0, 3, 1))),
+            Some(Command::Pixel(Point::new(2, 1), color(5))),
+            Some(Command::Pixel(Point::new(1, 1), color(4))),
+            Some(Command::Pixel(Point::new(0, 1), color(3))),
+            None,
+            None,
+            None,
+            None,
+        ]
+    );
+}
@@ -0,0 +1,176 @@
+//! Small geometry helpers shared by the adapters.
+
+use crate::Rotation;
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+
+/// Iterate over all the points of `area` in row-major order.
+pub(crate) fn points(area: &Rectangle) -> impl Iterator<Item = Point> {
+    let Rectangle { top_left, size } = *area;
+
+    (0..size.height as i32).flat_map(move |y| {
+        (0..size.width as i32).map(move |x| Point::new(top_left.x + x, top_left.y + y))
+    })
+}
+
+/// Returns true if `area` contains no points.
+#[inline]
+pub(crate) fn is_empty(area: &Rectangle) -> bool {
+    area.size.width == 0 || area.size.height == 0
+}
+
+/// Move `area` by `offset`.
+#[inline]
+pub(crate) fn translate(area: &Rectangle, offset: Point) -> Rectangle {
+    Rectangle {
+        top_left: area.top_left + offset,
+        size: area.size,
+    }
+}
+
+/// Returns true if `inner` lies entirely within `outer`.
+#[inline]
+pub(crate) fn contains_rect(outer: &Rectangle, inner: &Rectangle) -> bool {
+    is_empty(inner) || outer.intersection(inner) == *inner
+}
+
+/// Map a point in logical coordinates to a display of `size` rotated by
+/// `rotation`.
+pub(crate) fn rotate_point(rotation: Rotation, size: Size, p: Point) -> Point {
+    let (w, h) = (size.width as i32 - 1, size.height as i32 - 1);
+
+    match rotation {
+        Rotation::Rotate0 => p,
+        Rotation::Rotate90 => Point::new(w - p.y, p.x),
+        Rotation::Rotate180 => Point::new(w - p.x, h - p.y),
+        Rotation::Rotate270 => Point::new(p.y, h - p.x),
+    }
+}
+
+/// Map a rectangle in logical coordinates to a display of `size` rotated by
+/// `rotation`.
+pub(crate) fn rotate_rect(rotation: Rotation, size: Size, area: &Rectangle) -> Rectangle {
+    match area.bottom_right() {
+        Some(br) => Rectangle::with_corners(
+            rotate_point(rotation, size, area.top_left),
+            rotate_point(rotation, size, br),
+        ),
+        None => Rectangle::zero(),
+    }
+}
+
+/// Split `area` into chunks of at most `n` points, in row-major order.
+///
+/// Each chunk is a number of whole rows, or part of a row if a row has more
+/// than `n` points.
+pub(crate) fn chunks(area: &Rectangle, n: usize) -> impl Iterator<Item = Rectangle> {
+    let Rectangle { top_left, size } = *area;
+    let chunk = if is_empty(area) || n == 0 {
+        Size::zero()
+    } else if size.width as usize <= n {
+        Size::new(size.width, (n / size.width as usize) as u32)
+    } else {
+        Size::new(n as u32, 1)
+    };
+
+    // No rows at all if there are no chunks
+    let height = if chunk.height == 0 { 0 } else { size.height };
+
+    (0..height)
+        .step_by(chunk.height.max(1) as usize)
+        .flat_map(move |y| {
+            (0..size.width)
+                .step_by(chunk.width.max(1) as usize)
+                .map(move |x| {
+                    Rectangle::new(
+                        top_left + Point::new(x as i32, y as i32),
+                        Size::new(
+                            chunk.width.min(size.width - x),
+                            chunk.height.min(size.height - y),
+                        ),
+                    )
+                })
+        })
+}
+
+/// Integer square root, rounded down.
+pub(crate) fn isqrt(n: u64) -> u64 {
+    if n < 2 {
+        return n;
+    }
+
+    // Newton's method, starting from an overestimate.
+    let mut x = 1 << ((64 - n.leading_zeros()).div_ceil(2));
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+/// Draw `pixels` to `target`, offset by `offset` and clipped to `clip` (in the
+/// target's coordinates).
+pub(crate) fn window_draw_iter<D, I>(
+    target: &mut D,
+    offset: Point,
+    clip: Rectangle,
+    pixels: I,
+) -> Result<(), D::Error>
+where
+    D: DrawTarget,
+    I: IntoIterator<Item = Pixel<D::Color>>,
+{
+    target.draw_iter(
+        pixels
+            .into_iter()
+            .map(|Pixel(loc, col)| Pixel(loc + offset, col))
+            .filter(|Pixel(loc, _)| clip.contains(*loc)),
+    )
+}
+
+/// Fill `area` of `target` with `colors`, offset by `offset` and clipped to
+/// `clip`. This is forwarded as a contiguous fill if it's entirely within the
+/// clip rectangle.
+pub(crate) fn window_fill_contiguous<D, I>(
+    target: &mut D,
+    offset: Point,
+    clip: Rectangle,
+    area: &Rectangle,
+    colors: I,
+) -> Result<(), D::Error>
+where
+    D: DrawTarget,
+    I: IntoIterator<Item = D::Color>,
+{
+    let area = translate(area, offset);
+
+    if contains_rect(&clip, &area) {
+        target.fill_contiguous(&area, colors)
+    } else {
+        target.draw_iter(
+            points(&area)
+                .zip(colors)
+                .map(|(loc, col)| Pixel(loc, col))
+                .filter(|Pixel(loc, _)| clip.contains(*loc)),
+        )
+    }
+}
+
+/// Fill `area` of `target` with `color`, offset by `offset` and clipped to
+/// `clip`.
+pub(crate) fn window_fill_solid<D: DrawTarget>(
+    target: &mut D,
+    offset: Point,
+    clip: Rectangle,
+    area: &Rectangle,
+    color: D::Color,
+) -> Result<(), D::Error> {
+    let area = translate(area, offset).intersection(&clip);
+
+    if is_empty(&area) {
+        Ok(())
+    } else {
+        target.fill_solid(&area, color)
+    }
+}
@@ -0,0 +1,875 @@
+//! Adapters which present part of a display, or a display offset within a
+//! larger one.
+
+use crate::util;
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+
+#[cfg(test)]
+mod tests;
+
+/// Margins on each side of a display.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct Insets {
+    /// Margin at the top.
+    pub top: u32,
+    /// Margin at the right.
+    pub right: u32,
+    /// Margin at the bottom.
+    pub bottom: u32,
+    /// Margin at the left.
+    pub left: u32,
+}
+
+impl Insets {
+    /// Margins with the given size on each side.
+    pub const fn new(top: u32, right: u32, bottom: u32, left: u32) -> Self {
+        Insets {
+            top,
+            right,
+            bottom,
+            left,
+        }
+    }
+
+    /// The same margin on all sides.
+    pub const fn uniform(margin: u32) -> Self {
+        Insets::new(margin, margin, margin, margin)
+    }
+}
+
+/// Shrink a display by a margin on each side.
+///
+/// This is useful for panels whose outer pixels are hidden by a bezel, or for
+/// keeping content within a safe area. The logical display is the area inside
+/// the margins, with its top left at the origin, and anything drawn outside it
+/// is clipped.
+pub struct Inset<D> {
+    target: D,
+    insets: Insets,
+}
+
+impl<D> Inset<D> {
+    /// Shrink the display by `insets`.
+    pub fn new(target: D, insets: Insets) -> Self {
+        Inset { target, insets }
+    }
+
+    /// Return the current margins.
+    pub fn insets(&self) -> Insets {
+        self.insets
+    }
+
+    /// Change the margins.
+    pub fn set_insets(&mut self, insets: Insets) {
+        self.insets = insets;
+    }
+}
+
+impl<D: Dimensions> Inset<D> {
+    /// The visible area in the inner display's coordinates.
+    fn clip(&self) -> Rectangle {
+        let Insets {
+            top,
+            right,
+            bottom,
+            left,
+        } = self.insets;
+        let bounds = self.target.bounding_box();
+
+        Rectangle::new(
+            bounds.top_left + Point::new(left as i32, top as i32),
+            Size::new(
+                bounds.size.width.saturating_sub(left + right),
+                bounds.size.height.saturating_sub(top + bottom),
+            ),
+        )
+    }
+}
+
+impl_wrapper!(Inset<D>);
+
+impl<D: Dimensions> Dimensions for Inset<D> {
+    fn bounding_box(&self) -> Rectangle {
+        Rectangle::new(Point::zero(), self.clip().size)
+    }
+}
+
+impl<D: DrawTarget> DrawTarget for Inset<D> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let clip = self.clip();
+        util::window_draw_iter(&mut self.target, clip.top_left, clip, pixels)
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let clip = self.clip();
+        util::window_fill_contiguous(&mut self.target, clip.top_left, clip, area, colors)
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let clip = self.clip();
+        util::window_fill_solid(&mut self.target, clip.top_left, clip, area, color)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        let clip = self.clip();
+        self.target.fill_solid(&clip, color)
+    }
+}
+
+/// Add a fixed offset to every coordinate.
+///
+/// Some panels (such as 80x160 ST7735 variants, or SH1106 OLEDs) have
+/// controller RAM which is larger than the glass, so the visible pixels start
+/// at a fixed column and row offset. The offset is applied in the coordinates
+/// of the wrapped display, so it should wrap the driver directly, with any
+/// rotation outside it.
+///
+/// The reported size is the same as the wrapped display's, with the top left at
+/// the origin. If the driver reports the size of its RAM rather than the glass,
+/// combine this with [`WithSize`].
+pub struct Offset<D> {
+    target: D,
+    offset: Point,
+}
+
+impl<D> Offset<D> {
+    /// Offset every coordinate by `offset` (column, row).
+    pub fn new(target: D, offset: Point) -> Self {
+        Offset { target, offset }
+    }
+
+    /// Return the current offset.
+    pub fn offset(&self) -> Point {
+        self.offset
+    }
+
+    /// Change the offset.
+    pub fn set_offset(&mut self, offset: Point) {
+        self.offset = offset;
+    }
+}
+
+impl_wrapper!(Offset<D>);
+
+impl<D: Dimensions> Dimensions for Offset<D> {
+    fn bounding_box(&self) -> Rectangle {
+        Rectangle::new(Point::zero(), self.target.bounding_box().size)
+    }
+}
+
+impl<D: DrawTarget> DrawTarget for Offset<D> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let offset = self.offset;
+        self.target.draw_iter(
+            pixels
+                .into_iter()
+                .map(|Pixel(loc, col)| Pixel(loc + offset, col)),
+        )
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        self.target
+            .fill_contiguous(&util::translate(area, self.offset), colors)
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.target
+            .fill_solid(&util::translate(area, self.offset), color)
+    }
+}
+
+/// Override the size of a display.
+///
+/// Some drivers report the size of the controller's RAM rather than the glass,
+/// which breaks transforms which mirror coordinates. This reports a bounding
+/// box of the given size at the origin instead, and clips anything drawn
+/// outside it. Coordinates are otherwise passed through unchanged.
+pub struct WithSize<D> {
+    target: D,
+    size: Size,
+}
+
+impl<D> WithSize<D> {
+    /// Report the display as having `size`.
+    pub fn new(target: D, size: Size) -> Self {
+        WithSize { target, size }
+    }
+
+    fn clip(&self) -> Rectangle {
+        Rectangle::new(Point::zero(), self.size)
+    }
+}
+
+impl_wrapper!(WithSize<D>);
+
+impl<D> Dimensions for WithSize<D> {
+    fn bounding_box(&self) -> Rectangle {
+        self.clip()
+    }
+}
+
+impl<D: DrawTarget> DrawTarget for WithSize<D> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let clip = self.clip();
+        util::window_draw_iter(&mut self.target, Point::zero(), clip, pixels)
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let clip = self.clip();
+        util::window_fill_contiguous(&mut self.target, Point::zero(), clip, area, colors)
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let clip = self.clip();
+        util::window_fill_solid(&mut self.target, Point::zero(), clip, area, color)
+    }
+}
+
+/// Show a movable window onto a larger canvas.
+///
+/// The logical display is a canvas of any size, and the wrapped display shows
+/// the part of it starting at the current pan offset. Anything outside the
+/// visible window is clipped, so content can be drawn in canvas coordinates
+/// regardless of where it's scrolled to. The pan offset is not constrained, so
+/// the window may extend past the edges of the canvas.
+pub struct Viewport<D> {
+    target: D,
+    canvas: Size,
+    pan: Point,
+}
+
+impl<D> Viewport<D> {
+    /// Present a canvas of size `canvas`, initially showing its top left.
+    pub fn new(target: D, canvas: Size) -> Self {
+        Viewport {
+            target,
+            canvas,
+            pan: Point::zero(),
+        }
+    }
+
+    /// Return the position on the canvas of the top left of the display.
+    pub fn pan(&self) -> Point {
+        self.pan
+    }
+
+    /// Set the position on the canvas of the top left of the display.
+    pub fn set_pan(&mut self, pan: Point) {
+        self.pan = pan;
+    }
+
+    /// Move the window by `delta`.
+    pub fn pan_by(&mut self, delta: Point) {
+        self.pan += delta;
+    }
+}
+
+impl<D: Dimensions> Viewport<D> {
+    /// The area of the canvas currently visible.
+    pub fn visible(&self) -> Rectangle {
+        Rectangle::new(self.pan, self.target.bounding_box().size).intersection(&self.bounding_box())
+    }
+
+    /// The offset from canvas to display coordinates, and the visible area in
+    /// display coordinates.
+    fn window(&self) -> (Point, Rectangle) {
+        let bounds = self.target.bounding_box();
+        let offset = bounds.top_left - self.pan;
+
+        (offset, util::translate(&self.visible(), offset))
+    }
+}
+
+impl_wrapper!(Viewport<D>);
+
+impl<D> Dimensions for Viewport<D> {
+    fn bounding_box(&self) -> Rectangle {
+        Rectangle::new(Point::zero(), self.canvas)
+    }
+}
+
+impl<D: DrawTarget> DrawTarget for Viewport<D> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let (offset, clip) = self.window();
+        util::window_draw_iter(&mut self.target, offset, clip, pixels)
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let (offset, clip) = self.window();
+        util::window_fill_contiguous(&mut self.target, offset, clip, area, colors)
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let (offset, clip) = self.window();
+        util::window_fill_solid(&mut self.target, offset, clip, area, color)
+    }
+}
+
+/// Magnify drawing by an integer factor around a movable center.
+///
+/// The logical display is the same size as the wrapped display. At a zoom of 1
+/// with the center in its default position, drawing is unchanged. At higher
+/// zoom factors each logical pixel is drawn as a square block, and the logical
+/// [`center`](Self::center) point is shown in the middle of the display.
+/// Anything which ends up outside the display is clipped.
+pub struct Zoom<D> {
+    target: D,
+    zoom: u32,
+    center: Option<Point>,
+}
+
+impl<D> Zoom<D> {
+    /// Wrap a display, initially with no magnification.
+    pub fn new(target: D) -> Self {
+        Zoom {
+            target,
+            zoom: 1,
+            center: None,
+        }
+    }
+
+    /// Return the current magnification.
+    pub fn zoom(&self) -> u32 {
+        self.zoom
+    }
+
+    /// Set the magnification.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `zoom` is 0.
+    pub fn set_zoom(&mut self, zoom: u32) {
+        assert!(zoom > 0, "zoom must be at least 1");
+        self.zoom = zoom;
+    }
+
+    /// Set the logical point shown in the middle of the display.
+    pub fn set_center(&mut self, center: Point) {
+        self.center = Some(center);
+    }
+
+    /// Return the center to its default position, the middle of the logical
+    /// display.
+    pub fn reset_center(&mut self) {
+        self.center = None;
+    }
+}
+
+impl<D: Dimensions> Zoom<D> {
+    /// Return the logical point shown in the middle of the display.
+    pub fn center(&self) -> Point {
+        self.center.unwrap_or_else(|| self.middle())
+    }
+
+    /// The middle of the wrapped display.
+    fn middle(&self) -> Point {
+        let bounds = self.target.bounding_box();
+        bounds.top_left + bounds.size / 2
+    }
+
+    fn scaled(&self) -> Scaled {
+        Scaled {
+            origin: self.middle() - self.center() * self.zoom as i32,
+            scale: self.zoom,
+            clip: self.target.bounding_box(),
+        }
+    }
+}
+
+impl_wrapper!(Zoom<D>);
+
+impl<D: Dimensions> Dimensions for Zoom<D> {
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+impl<D: DrawTarget> DrawTarget for Zoom<D> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.scaled().draw_iter(&mut self.target, pixels)
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        self.scaled()
+            .fill_contiguous(&mut self.target, area, colors)
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.scaled().fill_solid(&mut self.target, area, color)
+    }
+}
+
+/// Center a fixed-size display on a larger one.
+///
+/// This allows a UI designed for one resolution to be used on a larger panel.
+/// The logical display has the given size, and is optionally magnified by an
+/// integer scale factor. The border around it can be filled with a separate
+/// color when the display is [cleared](DrawTarget::clear), or with
+/// [`fill_border`](Self::fill_border). Anything drawn outside the logical
+/// display is clipped.
+pub struct Letterbox<D, C> {
+    target: D,
+    size: Size,
+    scale: u32,
+    border: Option<C>,
+}
+
+impl<D: DrawTarget> Letterbox<D, D::Color> {
+    /// Center a logical display of `size` on `target`.
+    pub fn new(target: D, size: Size) -> Self {
+        Letterbox {
+            target,
+            size,
+            scale: 1,
+            border: None,
+        }
+    }
+}
+
+impl<D, C> Letterbox<D, C> {
+    /// Magnify the logical display by `scale`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scale` is 0.
+    pub fn with_scale(mut self, scale: u32) -> Self {
+        assert!(scale > 0, "scale must be at least 1");
+        self.scale = scale;
+        self
+    }
+
+    /// Fill the border with `color` when the display is cleared.
+    pub fn with_border(mut self, color: C) -> Self {
+        self.border = Some(color);
+        self
+    }
+}
+
+impl<D: Dimensions, C> Letterbox<D, C> {
+    /// Magnify the logical display by the largest integer scale which fits.
+    pub fn with_best_scale(self) -> Self {
+        let Size { width, height } = self.target.bounding_box().size;
+        let scale = (width / self.size.width.max(1))
+            .min(height / self.size.height.max(1))
+            .max(1);
+
+        self.with_scale(scale)
+    }
+
+    /// The area of the wrapped display which the logical display occupies.
+    pub fn content_area(&self) -> Rectangle {
+        let bounds = self.target.bounding_box();
+        let size = self.size * self.scale;
+        let border = Point::new(
+            bounds.size.width as i32 - size.width as i32,
+            bounds.size.height as i32 - size.height as i32,
+        ) / 2;
+
+        Rectangle::new(bounds.top_left + border, size)
+    }
+
+    fn scaled(&self) -> Scaled {
+        let area = self.content_area();
+        Scaled {
+            origin: area.top_left,
+            scale: self.scale,
+            clip: area.intersection(&self.target.bounding_box()),
+        }
+    }
+}
+
+impl<D: DrawTarget> Letterbox<D, D::Color> {
+    /// Fill the border around the logical display with the border color, if
+    /// one is set.
+    pub fn fill_border(&mut self) -> Result<(), D::Error> {
+        let color = match self.border {
+            Some(color) => color,
+            None => return Ok(()),
+        };
+        let bounds = self.target.bounding_box();
+        let content = self.content_area().intersection(&bounds);
+
+        if util::is_empty(&content) {
+            return self.target.fill_solid(&bounds, color);
+        }
+
+        let top = (content.top_left.y - bounds.top_left.y) as u32;
+        let left = (content.top_left.x - bounds.top_left.x) as u32;
+        let bottom = bounds.size.height - top - content.size.height;
+        let right = bounds.size.width - left - content.size.width;
+
+        let bottom_right = content.top_left + content.size;
+        for area in [
+            Rectangle::new(bounds.top_left, Size::new(bounds.size.width, top)),
+            Rectangle::new(
+                Point::new(bounds.top_left.x, bottom_right.y),
+                Size::new(bounds.size.width, bottom),
+            ),
+            Rectangle::new(
+                Point::new(bounds.top_left.x, content.top_left.y),
+                Size::new(left, content.size.height),
+            ),
+            Rectangle::new(
+                Point::new(bottom_right.x, content.top_left.y),
+                Size::new(right, content.size.height),
+            ),
+        ] {
+            if !util::is_empty(&area) {
+                self.target.fill_solid(&area, color)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl_wrapper!(Letterbox<D, C>);
+
+impl<D, C> Dimensions for Letterbox<D, C> {
+    fn bounding_box(&self) -> Rectangle {
+        Rectangle::new(Point::zero(), self.size)
+    }
+}
+
+impl<D: DrawTarget> DrawTarget for Letterbox<D, D::Color> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.scaled().draw_iter(&mut self.target, pixels)
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        self.scaled()
+            .fill_contiguous(&mut self.target, area, colors)
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.scaled().fill_solid(&mut self.target, area, color)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        let scaled = self.scaled();
+        if !util::is_empty(&scaled.clip) {
+            self.target.fill_solid(&scaled.clip, color)?;
+        }
+        self.fill_border()
+    }
+}
+
+/// Draw each logical pixel as a `scale` sized block, with the logical origin at
+/// `origin`, clipped to `clip`.
+#[derive(Debug, Clone, Copy)]
+struct Scaled {
+    origin: Point,
+    scale: u32,
+    clip: Rectangle,
+}
+
+impl Scaled {
+    fn map_rect(&self, area: &Rectangle) -> Rectangle {
+        Rectangle::new(
+            self.origin + area.top_left * self.scale as i32,
+            area.size * self.scale,
+        )
+    }
+
+    fn draw_iter<D, I>(&self, target: &mut D, pixels: I) -> Result<(), D::Error>
+    where
+        D: DrawTarget,
+        I: IntoIterator<Item = Pixel<D::Color>>,
+    {
+        if self.scale == 1 {
+            util::window_draw_iter(target, self.origin, self.clip, pixels)
+        } else {
+            for Pixel(loc, color) in pixels {
+                let block = self.map_rect(&Rectangle::new(loc, Size::new(1, 1)));
+                util::window_fill_solid(target, Point::zero(), self.clip, &block, color)?;
+            }
+            Ok(())
+        }
+    }
+
+    fn fill_contiguous<D, I>(
+        &self,
+        target: &mut D,
+        area: &Rectangle,
+        colors: I,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget,
+        I: IntoIterator<Item = D::Color>,
+    {
+        if self.scale == 1 {
+            util::window_fill_contiguous(target, self.origin, self.clip, area, colors)
+        } else {
+            self.draw_iter(
+                target,
+                util::points(area)
+                    .zip(colors)
+                    .map(|(loc, col)| Pixel(loc, col)),
+            )
+        }
+    }
+
+    fn fill_solid<D: DrawTarget>(
+        &self,
+        target: &mut D,
+        area: &Rectangle,
+        color: D::Color,
+    ) -> Result<(), D::Error> {
+        util::window_fill_solid(
+            target,
+            Point::zero(),
+            self.clip,
+            &self.map_rect(area),
+            color,
+        )
+    }
+}
+
+/// Clip drawing to a rectangle.
+///
+/// Unlike [`WithSize`] or `embedded_graphics`' `clipped()`, this doesn't
+/// change the reported bounding box, only which pixels are drawn, and
+/// coordinates are passed through unchanged. This makes the result independent
+/// of where it's placed in a stack of transforms: the clip rectangle is always
+/// in the coordinates of the `Clipped` itself, and the transforms on either side
+/// of it see the same display size.
+///
+/// So `Clipped<Rotate90<D>>` clips in the rotated logical coordinates, while
+/// `Rotate90<Clipped<D>>` clips in `D`'s physical coordinates. In both cases
+/// the rotation is computed from `D`'s full size. (By contrast, a `clipped()`
+/// draw target under a rotation would change the size used for the rotation,
+/// moving everything drawn through it.)
+pub struct Clipped<D> {
+    target: D,
+    clip: Rectangle,
+}
+
+impl<D> Clipped<D> {
+    /// Clip drawing to `clip`.
+    pub fn new(target: D, clip: Rectangle) -> Self {
+        Clipped { target, clip }
+    }
+
+    /// Return the current clip rectangle.
+    pub fn clip(&self) -> Rectangle {
+        self.clip
+    }
+
+    /// Change the clip rectangle.
+    pub fn set_clip(&mut self, clip: Rectangle) {
+        self.clip = clip;
+    }
+}
+
+impl_wrapper!(Clipped<D>);
+
+impl<D: Dimensions> Dimensions for Clipped<D> {
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+impl<D: DrawTarget> DrawTarget for Clipped<D> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        util::window_draw_iter(&mut self.target, Point::zero(), self.clip, pixels)
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        util::window_fill_contiguous(&mut self.target, Point::zero(), self.clip, area, colors)
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        util::window_fill_solid(&mut self.target, Point::zero(), self.clip, area, color)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        let area = self.clip.intersection(&self.target.bounding_box());
+        util::window_fill_solid(&mut self.target, Point::zero(), self.clip, &area, color)
+    }
+}
+
+/// Periodically move everything drawn by a pixel or two, to avoid burn-in.
+///
+/// OLEDs showing the same content for long periods wear unevenly, leaving a
+/// ghost image. Like the pixel shift on TVs, this moves the whole image around
+/// a small orbit, spreading the wear at the edges of static content. The
+/// logical display is smaller than the wrapped one by `margin` on each side,
+/// and is shifted by up to `margin` pixels in each direction from the center.
+///
+/// Each call to [`step`](Self::step) moves to the next position, one pixel
+/// from the last, back and forth across every position in the orbit. The
+/// application calls it from a timer, every few minutes, and then redraws
+/// everything; [`clear`](DrawTarget::clear) clears the whole wrapped display,
+/// margins included, so nothing is left behind at the old position.
+pub struct PixelShift<D> {
+    target: D,
+    margin: u32,
+    step: u32,
+}
+
+impl<D> PixelShift<D> {
+    /// Shift drawing by up to `margin` pixels, starting at the center.
+    pub fn new(target: D, margin: u32) -> Self {
+        let mut shift = PixelShift {
+            target,
+            margin,
+            step: 0,
+        };
+        // Start in the middle of the first pass, at the center.
+        shift.step = shift.positions() / 2;
+        shift
+    }
+
+    /// Return the margin.
+    pub fn margin(&self) -> u32 {
+        self.margin
+    }
+
+    /// Number of positions in the orbit.
+    fn positions(&self) -> u32 {
+        let side = 2 * self.margin + 1;
+        side * side
+    }
+
+    /// Move to the next position, returning the new shift.
+    pub fn step(&mut self) -> Point {
+        // Going out and back takes twice the positions, less the two ends.
+        let period = (2 * self.positions()).saturating_sub(2).max(1);
+        self.step = (self.step + 1) % period;
+        self.shift()
+    }
+
+    /// Return the current shift from the center, from `-margin` to `margin`
+    /// in each direction.
+    pub fn shift(&self) -> Point {
+        let positions = self.positions();
+        let index = if self.step < positions {
+            self.step
+        } else {
+            2 * (positions - 1) - self.step
+        };
+
+        // Snake through the square of positions so each step is one pixel.
+        let side = 2 * self.margin + 1;
+        let (row, col) = (index / side, index % side);
+        let col = if row % 2 == 0 { col } else { side - 1 - col };
+        let margin = self.margin as i32;
+        Point::new(col as i32 - margin, row as i32 - margin)
+    }
+}
+
+impl<D: Dimensions> PixelShift<D> {
+    /// The visible area in the inner display's coordinates.
+    fn clip(&self) -> Rectangle {
+        let bounds = self.target.bounding_box();
+        let margin = self.margin as i32;
+
+        Rectangle::new(
+            bounds.top_left + Point::new(margin, margin) + self.shift(),
+            Size::new(
+                bounds.size.width.saturating_sub(2 * self.margin),
+                bounds.size.height.saturating_sub(2 * self.margin),
+            ),
+        )
+    }
+}
+
+impl_wrapper!(PixelShift<D>);
+
+impl<D: Dimensions> Dimensions for PixelShift<D> {
+    fn bounding_box(&self) -> Rectangle {
+        Rectangle::new(Point::zero(), self.clip().size)
+    }
+}
+
+impl<D: DrawTarget> DrawTarget for PixelShift<D> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let clip = self.clip();
+        util::window_draw_iter(&mut self.target, clip.top_left, clip, pixels)
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let clip = self.clip();
+        util::window_fill_contiguous(&mut self.target, clip.top_left, clip, area, colors)
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let clip = self.clip();
+        util::window_fill_solid(&mut self.target, clip.top_left, clip, area, color)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.target.clear(color)
+    }
+}
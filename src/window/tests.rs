@@ -0,0 +1,306 @@
+use super::*;
+use crate::{tests::rect, FlipX, Rotate180, Rotate90};
+use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+#[test]
+fn inset() {
+    let mut disp = Inset::new(MockDisplay::<BinaryColor>::new(), Insets::new(1, 2, 3, 4));
+    assert_eq!(disp.bounding_box(), rect(0, 0, 58, 60));
+
+    disp.draw_iter([
+        Pixel(Point::new(0, 0), BinaryColor::On),
+        Pixel(Point::new(-1, 0), BinaryColor::On),
+        Pixel(Point::new(58, 0), BinaryColor::On),
+    ])
+    .unwrap();
+    assert_eq!(disp.affected_area(), rect(4, 1, 1, 1));
+
+    let mut disp = Inset::new(MockDisplay::<BinaryColor>::new(), Insets::uniform(2));
+    disp.fill_solid(&rect(-5, -5, 10, 10), BinaryColor::On)
+        .unwrap();
+    assert_eq!(disp.affected_area(), rect(2, 2, 5, 5));
+
+    let mut disp = Inset::new(MockDisplay::<BinaryColor>::new(), Insets::uniform(2));
+    disp.clear(BinaryColor::On).unwrap();
+    assert_eq!(disp.affected_area(), rect(2, 2, 60, 60));
+}
+
+#[test]
+fn inset_rotated() {
+    // Margins are in the coordinates of the wrapped display
+    let mut disp = Inset::new(
+        Rotate90::new(MockDisplay::<BinaryColor>::new()),
+        Insets::new(0, 0, 0, 10),
+    );
+    disp.fill_contiguous(&rect(0, 0, 1, 1), [BinaryColor::On])
+        .unwrap();
+    assert_eq!(disp.as_ref().as_ref().affected_area(), rect(63, 10, 1, 1));
+}
+
+#[test]
+fn offset() {
+    let mut disp = Offset::new(MockDisplay::<BinaryColor>::new(), Point::new(2, 3));
+    assert_eq!(disp.bounding_box(), rect(0, 0, 64, 64));
+
+    disp.fill_contiguous(&rect(0, 0, 2, 2), [BinaryColor::On; 4])
+        .unwrap();
+    disp.fill_solid(&rect(4, 0, 2, 1), BinaryColor::On).unwrap();
+    disp.draw_iter([Pixel(Point::new(0, 3), BinaryColor::On)])
+        .unwrap();
+    disp.as_ref().assert_pattern(&[
+        "        ", "        ", "        ", "  ##  ##", "  ##    ", "        ", "  #     ",
+    ]);
+}
+
+#[test]
+fn offset_rotated() {
+    // The offset is applied after rotation
+    let mut disp = Rotate90::new(Offset::new(
+        MockDisplay::<BinaryColor>::new(),
+        Point::new(2, 3),
+    ));
+    disp.draw_iter([Pixel(Point::new(5, 10), BinaryColor::On)])
+        .unwrap();
+    assert_eq!(disp.as_ref().as_ref().affected_area(), rect(55, 8, 1, 1));
+}
+
+#[test]
+fn with_size() {
+    let mut disp = FlipX::new(WithSize::new(
+        MockDisplay::<BinaryColor>::new(),
+        Size::new(6, 3),
+    ));
+    assert_eq!(disp.bounding_box(), rect(0, 0, 6, 3));
+
+    disp.draw_iter([
+        Pixel(Point::new(0, 0), BinaryColor::On),
+        Pixel(Point::new(1, 3), BinaryColor::On),
+        Pixel(Point::new(6, 1), BinaryColor::On),
+    ])
+    .unwrap();
+    disp.as_mut()
+        .fill_contiguous(&rect(4, 1, 3, 2), [BinaryColor::On; 6])
+        .unwrap();
+    disp.as_ref().as_ref().assert_pattern(&[
+        "     #", //
+        "    ##", "    ##",
+    ]);
+}
+
+#[test]
+fn with_size_offset() {
+    // A 4x2 panel at column 3, row 1 of a larger controller
+    let mut disp = Rotate180::new(WithSize::new(
+        Offset::new(MockDisplay::<BinaryColor>::new(), Point::new(3, 1)),
+        Size::new(4, 2),
+    ));
+    let mut colors = [BinaryColor::On; 8];
+    colors[0] = BinaryColor::Off;
+    disp.fill_contiguous(&rect(0, 0, 4, 2), colors).unwrap();
+    disp.as_ref().as_ref().as_ref().assert_pattern(&[
+        "       ", //
+        "   ####", "   ###.",
+    ]);
+}
+
+#[test]
+fn viewport() {
+    let mut disp = Viewport::new(MockDisplay::<BinaryColor>::new(), Size::new(200, 100));
+    assert_eq!(disp.bounding_box(), rect(0, 0, 200, 100));
+    assert_eq!(disp.visible(), rect(0, 0, 64, 64));
+
+    disp.set_pan(Point::new(100, 10));
+    disp.pan_by(Point::new(2, 0));
+    assert_eq!(disp.pan(), Point::new(102, 10));
+    assert_eq!(disp.visible(), rect(102, 10, 64, 64));
+
+    disp.draw_iter([
+        Pixel(Point::new(0, 0), BinaryColor::On),
+        Pixel(Point::new(103, 12), BinaryColor::On),
+    ])
+    .unwrap();
+    disp.fill_solid(&rect(90, 10, 14, 1), BinaryColor::On)
+        .unwrap();
+    disp.as_ref().assert_pattern(&[
+        "## ", //
+        "   ", //
+        " # ", //
+    ]);
+}
+
+#[test]
+fn viewport_edge() {
+    // The window extends past the bottom right of the canvas
+    let mut disp = Viewport::new(MockDisplay::<BinaryColor>::new(), Size::new(100, 100));
+    disp.set_pan(Point::new(90, 80));
+    assert_eq!(disp.visible(), rect(90, 80, 10, 20));
+
+    disp.clear(BinaryColor::On).unwrap();
+    assert_eq!(disp.as_ref().affected_area(), rect(0, 0, 10, 20));
+}
+
+#[test]
+fn zoom() {
+    let mut disp = Zoom::new(MockDisplay::<BinaryColor>::new());
+    assert_eq!(disp.center(), Point::new(32, 32));
+
+    // Unchanged at zoom 1
+    disp.draw_iter([Pixel(Point::new(1, 2), BinaryColor::On)])
+        .unwrap();
+    assert_eq!(disp.as_ref().affected_area(), rect(1, 2, 1, 1));
+
+    let mut disp = Zoom::new(MockDisplay::<BinaryColor>::new());
+    disp.set_zoom(3);
+    disp.set_center(Point::new(1, 1));
+    disp.draw_iter([
+        Pixel(Point::new(1, 1), BinaryColor::On),
+        Pixel(Point::new(-100, 1), BinaryColor::On),
+    ])
+    .unwrap();
+    assert_eq!(disp.as_ref().affected_area(), rect(32, 32, 3, 3));
+
+    // Blocks are clipped at the edges
+    disp.fill_solid(&rect(-10, 0, 11, 1), BinaryColor::Off)
+        .unwrap();
+    assert_eq!(disp.as_ref().affected_area(), rect(0, 29, 35, 6));
+}
+
+#[test]
+fn zoom_fill_contiguous() {
+    use BinaryColor::{Off, On};
+
+    let mut disp = Zoom::new(MockDisplay::<BinaryColor>::new());
+    disp.set_zoom(2);
+    disp.set_center(Point::new(16, 16));
+    disp.fill_contiguous(&rect(0, 0, 2, 2), [On, Off, Off, On])
+        .unwrap();
+    disp.as_ref().assert_pattern(&[
+        "##..", //
+        "##..", //
+        "..##", //
+        "..##", //
+    ]);
+}
+
+#[test]
+fn letterbox() {
+    let mut disp = Letterbox::new(MockDisplay::<BinaryColor>::new(), Size::new(60, 62))
+        .with_border(BinaryColor::Off);
+    assert_eq!(disp.bounding_box(), rect(0, 0, 60, 62));
+    assert_eq!(disp.content_area(), rect(2, 1, 60, 62));
+
+    disp.clear(BinaryColor::On).unwrap();
+    let mock = disp.as_ref();
+    assert_eq!(mock.affected_area(), rect(0, 0, 64, 64));
+    assert_eq!(mock.get_pixel(Point::new(2, 1)), Some(BinaryColor::On));
+    assert_eq!(mock.get_pixel(Point::new(61, 62)), Some(BinaryColor::On));
+    for p in [(1, 1), (62, 1), (2, 0), (2, 63)] {
+        assert_eq!(
+            mock.get_pixel(Point::new(p.0, p.1)),
+            Some(BinaryColor::Off),
+            "{p:?}"
+        );
+    }
+}
+
+#[test]
+fn letterbox_scaled() {
+    let mut disp =
+        Letterbox::new(MockDisplay::<BinaryColor>::new(), Size::new(20, 10)).with_best_scale();
+    assert_eq!(disp.content_area(), rect(2, 17, 60, 30));
+
+    disp.draw_iter([
+        Pixel(Point::new(1, 0), BinaryColor::On),
+        Pixel(Point::new(20, 0), BinaryColor::On),
+    ])
+    .unwrap();
+    assert_eq!(disp.as_ref().affected_area(), rect(5, 17, 3, 3));
+
+    // No border color, so only the content area is cleared
+    let mut disp = Letterbox::new(MockDisplay::<BinaryColor>::new(), Size::new(20, 10));
+    disp.clear(BinaryColor::On).unwrap();
+    assert_eq!(disp.as_ref().affected_area(), rect(22, 27, 20, 10));
+}
+
+#[test]
+fn clipped() {
+    let mut disp = Clipped::new(MockDisplay::<BinaryColor>::new(), rect(1, 1, 3, 2));
+    assert_eq!(disp.bounding_box(), rect(0, 0, 64, 64));
+
+    disp.clear(BinaryColor::Off).unwrap();
+    disp.as_mut().set_allow_overdraw(true);
+    disp.fill_contiguous(&rect(2, 0, 1, 3), [BinaryColor::On; 3])
+        .unwrap();
+    disp.as_ref().assert_pattern(&[
+        "    ", //
+        " .#.", //
+        " .#.", //
+    ]);
+}
+
+#[test]
+fn clipped_ordering() {
+    // Outside the rotation, the clip is in logical coordinates
+    let mut disp = Clipped::new(
+        Rotate90::new(MockDisplay::<BinaryColor>::new()),
+        rect(0, 0, 64, 1),
+    );
+    disp.draw_iter([
+        Pixel(Point::new(5, 0), BinaryColor::On),
+        Pixel(Point::new(5, 1), BinaryColor::On),
+    ])
+    .unwrap();
+    assert_eq!(disp.as_ref().as_ref().affected_area(), rect(63, 5, 1, 1));
+
+    // Inside, it's in physical coordinates, and doesn't affect the rotation
+    let mut disp = Rotate90::new(Clipped::new(
+        MockDisplay::<BinaryColor>::new(),
+        rect(0, 0, 64, 1),
+    ));
+    assert_eq!(disp.bounding_box(), rect(0, 0, 64, 64));
+    disp.draw_iter([
+        Pixel(Point::new(0, 5), BinaryColor::On),
+        Pixel(Point::new(1, 5), BinaryColor::On),
+    ])
+    .unwrap();
+    assert_eq!(disp.as_ref().as_ref().affected_area(), rect(58, 0, 1, 1));
+}
+
+#[test]
+fn pixel_shift() {
+    let mut disp = PixelShift::new(MockDisplay::<BinaryColor>::new(), 1);
+    assert_eq!(disp.margin(), 1);
+    assert_eq!(disp.bounding_box(), rect(0, 0, 62, 62));
+    assert_eq!(disp.shift(), Point::zero());
+
+    disp.fill_solid(&rect(0, 0, 100, 1), BinaryColor::On)
+        .unwrap();
+    assert_eq!(disp.affected_area(), rect(1, 1, 62, 1));
+
+    // Every position is visited, one pixel at a time, and then in reverse.
+    let mut shifts = [Point::zero(); 16];
+    let mut last = disp.shift();
+    for shift in &mut shifts {
+        *shift = disp.step();
+        let d = *shift - last;
+        assert_eq!(d.x.abs() + d.y.abs(), 1);
+        assert!(shift.x.abs() <= 1 && shift.y.abs() <= 1);
+        last = *shift;
+    }
+    for x in -1..=1 {
+        for y in -1..=1 {
+            assert!(shifts.contains(&Point::new(x, y)));
+        }
+    }
+    // Back at the center after going out and back
+    assert_eq!(disp.shift(), Point::zero());
+
+    let mut disp = PixelShift::new(MockDisplay::<BinaryColor>::new(), 2);
+    disp.step();
+    let shift = disp.shift();
+    disp.fill_solid(&rect(0, 0, 1, 1), BinaryColor::On).unwrap();
+    assert_eq!(disp.affected_area(), rect(2 + shift.x, 2 + shift.y, 1, 1));
+    disp.as_mut().set_allow_overdraw(true);
+    disp.clear(BinaryColor::Off).unwrap();
+    assert_eq!(disp.affected_area(), rect(0, 0, 64, 64));
+}
@@ -0,0 +1,152 @@
+//! Let other work run during long draws.
+
+use crate::util;
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+
+#[cfg(test)]
+mod tests;
+
+/// Call a function every so many pixels drawn, for cooperative schedulers.
+///
+/// A full-screen redraw, particularly through a software rotation, can keep
+/// the CPU busy for long enough to starve everything else in a cooperative
+/// system. This splits drawing into calls of no more than `every` pixels, and
+/// calls `callback` after each `every` pixels, for example to feed a watchdog
+/// or poll a communications stack.
+///
+/// The callback is only ever called between calls to the inner display, never
+/// in the middle of one, so it can safely use a bus shared with the display.
+/// Fills are split into bands of whole rows where they fit, or into parts of
+/// rows where they don't, and `clear` is done as a series of fills. The count
+/// carries over from one draw call to the next.
+pub struct Yielding<D, F> {
+    target: D,
+    callback: F,
+    every: u32,
+    count: u32,
+}
+
+impl<D, F: FnMut()> Yielding<D, F> {
+    /// Wrap `target`, calling `callback` after each `every` pixels drawn.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `every` is zero.
+    pub fn new(target: D, every: u32, callback: F) -> Self {
+        assert!(every > 0, "must yield after at least one pixel");
+        Yielding {
+            target,
+            callback,
+            every,
+            count: 0,
+        }
+    }
+
+    /// Count `pixels` drawn, calling the callback if it's due.
+    fn advance(&mut self, pixels: u32) {
+        self.count += pixels;
+        if self.count >= self.every {
+            self.count = 0;
+            (self.callback)();
+        }
+    }
+}
+
+impl<D: DrawTarget, F: FnMut()> Yielding<D, F> {
+    /// Draw `area` in parts which each fit in what's left before the next
+    /// callback.
+    fn chunks(
+        &mut self,
+        area: &Rectangle,
+        mut draw: impl FnMut(&mut D, &Rectangle) -> Result<(), D::Error>,
+    ) -> Result<(), D::Error> {
+        if util::is_empty(area) {
+            return Ok(());
+        }
+        let Size { width, height } = area.size;
+
+        if width <= self.every {
+            let mut y = 0;
+            while y < height {
+                if self.every - self.count < width {
+                    self.count = 0;
+                    (self.callback)();
+                }
+                let rows = ((self.every - self.count) / width).min(height - y);
+                let band = Rectangle::new(
+                    area.top_left + Point::new(0, y as i32),
+                    Size::new(width, rows),
+                );
+                draw(&mut self.target, &band)?;
+                self.advance(width * rows);
+                y += rows;
+            }
+        } else {
+            for y in area.rows() {
+                let mut x = 0;
+                while x < width {
+                    let len = (self.every - self.count).min(width - x);
+                    let run = Rectangle::new(
+                        Point::new(area.top_left.x + x as i32, y),
+                        Size::new(len, 1),
+                    );
+                    draw(&mut self.target, &run)?;
+                    self.advance(len);
+                    x += len;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl_wrapper!(Yielding<D, F>);
+
+impl<D: Dimensions, F> Dimensions for Yielding<D, F> {
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+impl<D: DrawTarget, F: FnMut()> DrawTarget for Yielding<D, F> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let mut pixels = pixels.into_iter().peekable();
+        while pixels.peek().is_some() {
+            let mut len = 0;
+            let mut chunk = pixels.by_ref().take((self.every - self.count) as usize);
+            self.target
+                .draw_iter(chunk.by_ref().inspect(|_| len += 1))?;
+            // The target needn't consume every pixel, so skip the rest of the
+            // chunk to make progress.
+            len += chunk.count() as u32;
+            self.advance(len);
+        }
+        Ok(())
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let mut colors = colors.into_iter();
+        self.chunks(area, |target, part| {
+            let len = part.size.width as usize * part.size.height as usize;
+            target.fill_contiguous(part, colors.by_ref().take(len))
+        })
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.chunks(area, |target, part| target.fill_solid(part, color))
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        let area = self.target.bounding_box();
+        self.fill_solid(&area, color)
+    }
+}
@@ -0,0 +1,110 @@
+use super::*;
+use crate::{tests::rect, Command, Rotate90, Trace};
+use core::cell::Cell;
+use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+/// A display which ignores the pixels it's given.
+#[derive(Default)]
+struct Ignoring {
+    calls: usize,
+}
+
+impl OriginDimensions for Ignoring {
+    fn size(&self) -> Size {
+        Size::new(64, 64)
+    }
+}
+
+impl DrawTarget for Ignoring {
+    type Color = BinaryColor;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, _pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.calls += 1;
+        Ok(())
+    }
+}
+
+#[test]
+fn yielding() {
+    let yields = Cell::new(0);
+    let mut disp = Rotate90::new(Yielding::new(MockDisplay::<BinaryColor>::new(), 10, || {
+        yields.set(yields.get() + 1)
+    }));
+
+    disp.draw_iter((0..25).map(|x| Pixel(Point::new(x, 0), BinaryColor::On)))
+        .unwrap();
+    assert_eq!(yields.get(), 2);
+    // The remaining 5 pixels carry over
+    disp.fill_solid(&rect(0, 1, 5, 1), BinaryColor::On).unwrap();
+    assert_eq!(yields.get(), 3);
+
+    disp.fill_contiguous(&rect(0, 2, 4, 3), [BinaryColor::On; 12])
+        .unwrap();
+    assert_eq!(yields.get(), 4);
+
+    let mut expected = Rotate90::new(MockDisplay::<BinaryColor>::new());
+    expected
+        .draw_iter((0..25).map(|x| Pixel(Point::new(x, 0), BinaryColor::On)))
+        .unwrap();
+    expected
+        .fill_solid(&rect(0, 1, 5, 1), BinaryColor::On)
+        .unwrap();
+    expected
+        .fill_contiguous(&rect(0, 2, 4, 3), [BinaryColor::On; 12])
+        .unwrap();
+    disp.into_inner()
+        .into_inner()
+        .assert_eq(&expected.into_inner());
+}
+
+#[test]
+fn yielding_chunks() {
+    let mut parts = [Rectangle::zero(); 6];
+    let mut count = 0;
+    {
+        let trace = Trace::new(MockDisplay::<BinaryColor>::new(), |cmd: &Command<_>| {
+            if let Command::FillSolid(area, _) = cmd {
+                parts[count] = *area;
+                count += 1;
+            }
+        });
+        let mut disp = Yielding::new(trace, 8, || ());
+
+        // Bands of whole rows, starting with what fits before the first yield
+        disp.draw_iter([Pixel(Point::new(0, 0), BinaryColor::On)])
+            .unwrap();
+        disp.fill_solid(&rect(0, 1, 3, 3), BinaryColor::On).unwrap();
+        // and parts of rows too wide to fit
+        disp.fill_solid(&rect(0, 4, 10, 1), BinaryColor::On)
+            .unwrap();
+    }
+
+    assert_eq!(
+        parts,
+        [
+            rect(0, 1, 3, 2),
+            rect(0, 3, 3, 1),
+            rect(0, 4, 5, 1),
+            rect(5, 4, 5, 1),
+            Rectangle::zero(),
+            Rectangle::zero(),
+        ]
+    );
+}
+
+#[test]
+fn yielding_unconsumed() {
+    let mut yields = 0;
+    let mut disp = Yielding::new(Ignoring::default(), 10, || yields += 1);
+
+    // Pixels the display doesn't take still count, rather than being offered
+    // again forever
+    disp.draw_iter((0..25).map(|x| Pixel(Point::new(x, 0), BinaryColor::On)))
+        .unwrap();
+    assert_eq!(disp.into_inner().calls, 3);
+    assert_eq!(yields, 2);
+}
@@ -0,0 +1,57 @@
+//! `compose_transforms!` used from outside the crate, with prelude names
+//! shadowed, to check that the expansion only uses absolute paths.
+
+use embedded_graphics::{
+    mock_display::MockDisplay, pixelcolor::BinaryColor, prelude::*, primitives::Rectangle,
+};
+use embedded_graphics_transform::compose_transforms;
+
+#[allow(dead_code)]
+type Result<T> = core::result::Result<T, ()>;
+
+#[allow(dead_code)]
+trait IntoIterator {}
+
+#[allow(dead_code)]
+trait AsRef {}
+
+#[allow(dead_code)]
+trait AsMut {}
+
+compose_transforms! {
+    /// A panel which is mirrored, transposed and offset on a larger controller.
+    pub PanelFix: FlipX, Transpose, WithSize(Size::new(40, 24)), Offset(Point::new(2, 1))
+}
+
+#[test]
+fn shadowed_prelude() {
+    let mut disp = PanelFix::new(MockDisplay::<BinaryColor>::new());
+    assert_eq!(disp.bounding_box().size, Size::new(24, 40));
+
+    disp.draw_iter([Pixel(Point::new(0, 0), BinaryColor::On)])
+        .unwrap();
+    disp.fill_contiguous(
+        &Rectangle::new(Point::new(1, 0), Size::new(1, 2)),
+        [BinaryColor::On, BinaryColor::Off],
+    )
+    .unwrap();
+    disp.fill_solid(
+        &Rectangle::new(Point::new(2, 0), Size::new(1, 1)),
+        BinaryColor::On,
+    )
+    .unwrap();
+
+    assert_eq!(disp.get_pixel(Point::new(2, 24)), Some(BinaryColor::On));
+    assert_eq!(disp.get_pixel(Point::new(2, 23)), Some(BinaryColor::On));
+    assert_eq!(disp.get_pixel(Point::new(3, 23)), Some(BinaryColor::Off));
+    assert_eq!(disp.get_pixel(Point::new(2, 22)), Some(BinaryColor::On));
+    assert_eq!(disp.as_ref().affected_area().size, Size::new(2, 3));
+
+    disp.set_allow_overdraw(true);
+    disp.clear(BinaryColor::Off).unwrap();
+    assert_eq!(disp.get_pixel(Point::new(2, 24)), Some(BinaryColor::Off));
+    assert_eq!(
+        disp.into_inner().affected_area(),
+        Rectangle::new(Point::new(2, 1), Size::new(40, 24))
+    );
+}